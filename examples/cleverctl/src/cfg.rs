@@ -2,9 +2,13 @@
 //!
 //! This module provides utilities to retrieve and parse configuration
 
-use std::path::PathBuf;
+use std::{fmt::Debug, path::PathBuf};
 
-use clevercloud_sdk::oauth10a::Credentials as CleverCloudCredentials;
+use clevercloud_sdk::{
+    oauth10a::{connector::Connect, ClientError, Credentials as CleverCloudCredentials},
+    v2::myself,
+    Client,
+};
 use config::{Config, ConfigError, File};
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +25,16 @@ pub enum Error {
     Cast(ConfigError),
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum ValidationError {
+    #[error("credentials are not authorized by the api")]
+    Unauthorized,
+    #[error("failed to reach the api, {0}")]
+    Network(ClientError),
+    #[error("failed to get information about the current user, {0}")]
+    Api(ClientError),
+}
+
 // -----------------------------------------------------------------------------
 // Credentials structure
 
@@ -102,4 +116,24 @@ impl Configuration {
             .try_deserialize()
             .map_err(Error::Cast)
     }
+
+    /// checks that the credentials are accepted by the api by retrieving
+    /// information about the current user
+    pub async fn validate<C>(&self, client: &Client<C>) -> Result<(), ValidationError>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        match myself::get(client).await {
+            Ok(_) => Ok(()),
+            Err(myself::Error::Get(ClientError::StatusCode(code, _))) if 401 == code.as_u16() => {
+                Err(ValidationError::Unauthorized)
+            }
+            Err(myself::Error::Get(err @ ClientError::Request(_))) => {
+                Err(ValidationError::Network(err))
+            }
+            Err(myself::Error::Get(err) | myself::Error::Update(err)) => {
+                Err(ValidationError::Api(err))
+            }
+        }
+    }
 }
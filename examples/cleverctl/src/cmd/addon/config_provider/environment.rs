@@ -27,6 +27,10 @@ pub enum Error {
     Get(environment::Error),
     #[error("failed to update environment for config-provider, {0}")]
     Put(environment::Error),
+    #[error("failed to clear environment for config-provider, {0}")]
+    Clear(environment::Error),
+    #[error("refusing to clear environment of config-provider '{0}', use '--confirm' to proceed")]
+    NotConfirmed(String),
     #[error("failed to build proxy connector, {0}")]
     ProxyConnector(proxy::Error),
     #[error("failed to read file, {0}")]
@@ -90,6 +94,15 @@ pub enum Environment {
         #[clap(name = "name")]
         name: String,
     },
+    #[clap(name = "clear", about = "Remove all environment variables")]
+    Clear {
+        /// Specify the config-provider identifier
+        #[clap(name = "config-provider-identifier")]
+        id: String,
+        /// Confirm the removal of all environment variables
+        #[clap(long = "confirm")]
+        confirm: bool,
+    },
 }
 
 #[async_trait::async_trait]
@@ -107,6 +120,7 @@ impl Executor for Environment {
             } => insert(config, output, id, name, value).await,
             Self::Put { output, id, file } => put(config, output, id, file).await,
             Self::Remove { output, id, name } => remove(config, output, id, name).await,
+            Self::Clear { id, confirm } => clear(config, id, *confirm).await,
         }
     }
 }
@@ -221,3 +235,17 @@ pub async fn remove(
 
     Ok(())
 }
+
+pub async fn clear(config: Arc<Configuration>, id: &str, confirm: bool) -> Result<(), Error> {
+    if !confirm {
+        return Err(Error::NotConfirmed(id.to_owned()));
+    }
+
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    environment::clear(&client, id).await.map_err(Error::Clear)
+}
@@ -9,7 +9,7 @@ use clevercloud_sdk::{
         proxy::{self, ProxyConnectorBuilder},
         Credentials,
     },
-    v2::addon,
+    v2::{addon, owner::OwnerId},
     Client,
 };
 
@@ -27,10 +27,16 @@ pub mod config_provider;
 pub enum Error {
     #[error("failed to format output, {0}")]
     FormatOutput(Box<cmd::Error>),
-    #[error("failed to list addons of organisation '{0}', {1}")]
-    List(String, addon::Error),
-    #[error("failed to get addon '{0}' of organisation '{1}', {2}")]
-    Get(String, String, addon::Error),
+    #[error("failed to list addons of '{0}', {1}")]
+    List(OwnerId, addon::Error),
+    #[error("failed to list addons of application '{0}' of '{1}', {2}")]
+    ListForApp(String, OwnerId, addon::Error),
+    #[error("failed to get addon '{0}' of '{1}', {2}")]
+    Get(String, OwnerId, addon::Error),
+    #[error("failed to link addon '{0}' to application '{1}' of '{2}', {3}")]
+    Link(String, String, OwnerId, addon::Error),
+    #[error("failed to unlink addon '{0}' from application '{1}' of '{2}', {3}")]
+    Unlink(String, String, OwnerId, addon::Error),
     #[error("failed to build proxy connector, {0}")]
     ProxyConnector(proxy::Error),
     #[error("failed to execute command on config-provider addon, {0}")]
@@ -42,23 +48,77 @@ pub enum Error {
 
 #[derive(Subcommand, Eq, PartialEq, Clone, Debug)]
 pub enum Command {
-    #[clap(name = "list", about = "List addons of an organisation")]
+    #[clap(
+        name = "list",
+        about = "List addons of an organisation or of the personal space"
+    )]
     List {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Target the personal space instead of an organisation
+        #[clap(long = "self", conflicts_with = "organisation-identifier")]
+        myself: bool,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier", required_unless_present = "myself")]
+        organisation_id: Option<String>,
+    },
+    #[clap(
+        name = "list-for-app",
+        aliases = &["lfa"],
+        about = "List addons linked to an application"
+    )]
+    ListForApp {
         /// Specify the output format
         #[clap(short = 'o', long = "output", default_value_t)]
         output: Output,
         /// Specify the organisation identifier
         #[clap(name = "organisation-identifier")]
         organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
     },
-    #[clap(name = "get", about = "Get addon of an organisation")]
+    #[clap(
+        name = "get",
+        about = "Get addon of an organisation or of the personal space",
+        allow_missing_positional = true
+    )]
     Get {
         /// Specify the output format
         #[clap(short = 'o', long = "output", default_value_t)]
         output: Output,
+        /// Target the personal space instead of an organisation
+        #[clap(long = "self", conflicts_with = "organisation-identifier")]
+        myself: bool,
+        /// Specify the organisation identifier, omitted when targeting the
+        /// personal space
+        #[clap(name = "organisation-identifier", required_unless_present = "myself")]
+        organisation_id: Option<String>,
+        /// Specify the addon identifier
+        #[clap(name = "addon-identifier")]
+        addon_id: String,
+    },
+    #[clap(name = "link", about = "Link an addon to an application")]
+    Link {
         /// Specify the organisation identifier
         #[clap(name = "organisation-identifier")]
         organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+        /// Specify the addon identifier
+        #[clap(name = "addon-identifier")]
+        addon_id: String,
+    },
+    #[clap(name = "unlink", about = "Unlink an addon from an application")]
+    Unlink {
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
         /// Specify the addon identifier
         #[clap(name = "addon-identifier")]
         addon_id: String,
@@ -74,14 +134,55 @@ impl Executor for Command {
     async fn execute(&self, config: Arc<Configuration>) -> Result<(), Self::Error> {
         match self {
             Self::List {
+                output,
+                myself,
+                organisation_id,
+            } => list(config, output, &owner(*myself, organisation_id)).await,
+            Self::ListForApp {
                 output,
                 organisation_id,
-            } => list(config, output, organisation_id).await,
+                application_id,
+            } => {
+                list_for_app(
+                    config,
+                    output,
+                    &OwnerId::from(organisation_id.as_str()),
+                    application_id,
+                )
+                .await
+            }
             Self::Get {
                 output,
+                myself,
                 organisation_id,
                 addon_id,
-            } => get(config, output, organisation_id, addon_id).await,
+            } => get(config, output, &owner(*myself, organisation_id), addon_id).await,
+            Self::Link {
+                organisation_id,
+                application_id,
+                addon_id,
+            } => {
+                link(
+                    config,
+                    &OwnerId::from(organisation_id.as_str()),
+                    application_id,
+                    addon_id,
+                )
+                .await
+            }
+            Self::Unlink {
+                organisation_id,
+                application_id,
+                addon_id,
+            } => {
+                unlink(
+                    config,
+                    &OwnerId::from(organisation_id.as_str()),
+                    application_id,
+                    addon_id,
+                )
+                .await
+            }
             Self::ConfigProvider(cmd) => cmd.execute(config).await.map_err(Error::ConfigProvider),
         }
     }
@@ -90,10 +191,19 @@ impl Executor for Command {
 // -----------------------------------------------------------------------------
 // helpers
 
+/// returns the owner targeted by the command line, clap ensures that the
+/// organisation identifier is given when the personal space is not targeted
+fn owner(myself: bool, organisation_id: &Option<String>) -> OwnerId {
+    match (myself, organisation_id) {
+        (false, Some(organisation_id)) => OwnerId::from(organisation_id.as_str()),
+        _ => OwnerId::Myself,
+    }
+}
+
 pub async fn list(
     config: Arc<Configuration>,
     output: &Output,
-    organisation_id: &str,
+    owner: &OwnerId,
 ) -> Result<(), Error> {
     let credentials: Credentials = config.credentials.to_owned().into();
     let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
@@ -101,9 +211,34 @@ pub async fn list(
         .with_credentials(credentials)
         .build(connector);
 
-    let addons = addon::list(&client, organisation_id)
+    let addons = addon::list_by_owner(&client, owner)
         .await
-        .map_err(|err| Error::List(organisation_id.to_owned(), err))?;
+        .map_err(|err| Error::List(owner.to_owned(), err))?;
+
+    println!(
+        "{}",
+        output
+            .format(&addons)
+            .map_err(|err| Error::FormatOutput(Box::new(err)))?
+    );
+    Ok(())
+}
+
+pub async fn list_for_app(
+    config: Arc<Configuration>,
+    output: &Output,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let addons = addon::list_for_app(&client, owner, application_id)
+        .await
+        .map_err(|err| Error::ListForApp(application_id.to_owned(), owner.to_owned(), err))?;
 
     println!(
         "{}",
@@ -117,7 +252,7 @@ pub async fn list(
 pub async fn get(
     config: Arc<Configuration>,
     output: &Output,
-    organisation_id: &str,
+    owner: &OwnerId,
     addon_id: &str,
 ) -> Result<(), Error> {
     let credentials: Credentials = config.credentials.to_owned().into();
@@ -126,9 +261,9 @@ pub async fn get(
         .with_credentials(credentials)
         .build(connector);
 
-    let addons = addon::get(&client, organisation_id, addon_id)
+    let addons = addon::get_by_owner(&client, owner, addon_id)
         .await
-        .map_err(|err| Error::Get(addon_id.to_owned(), organisation_id.to_owned(), err))?;
+        .map_err(|err| Error::Get(addon_id.to_owned(), owner.to_owned(), err))?;
 
     println!(
         "{}",
@@ -138,3 +273,51 @@ pub async fn get(
     );
     Ok(())
 }
+
+pub async fn link(
+    config: Arc<Configuration>,
+    owner: &OwnerId,
+    application_id: &str,
+    addon_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    addon::link_to_app(&client, owner, application_id, addon_id)
+        .await
+        .map_err(|err| {
+            Error::Link(
+                addon_id.to_owned(),
+                application_id.to_owned(),
+                owner.to_owned(),
+                err,
+            )
+        })
+}
+
+pub async fn unlink(
+    config: Arc<Configuration>,
+    owner: &OwnerId,
+    application_id: &str,
+    addon_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    addon::unlink_from_app(&client, owner, application_id, addon_id)
+        .await
+        .map_err(|err| {
+            Error::Unlink(
+                addon_id.to_owned(),
+                application_id.to_owned(),
+                owner.to_owned(),
+                err,
+            )
+        })
+}
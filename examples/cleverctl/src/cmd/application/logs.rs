@@ -0,0 +1,121 @@
+//! # Logs module
+//!
+//! This module provides commands to follow logs of applications
+
+use std::sync::Arc;
+
+use clap::Subcommand;
+use clevercloud_sdk::{
+    oauth10a::{
+        proxy::{self, ProxyConnectorBuilder},
+        Credentials,
+    },
+    v2::application::build_log,
+    Client,
+};
+use futures::StreamExt;
+
+use crate::{
+    cfg::Configuration,
+    cmd::{self, Executor, Output},
+};
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to format output, {0}")]
+    FormatOutput(Box<cmd::Error>),
+    #[error("failed to build proxy connector, {0}")]
+    ProxyConnector(proxy::Error),
+    #[error("failed to follow build log of deployment '{0}' of application '{1}', {2}")]
+    Build(String, String, build_log::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Logs enumeration
+
+/// Logs enum contains all operations that could be achieved on logs of applications
+#[derive(Subcommand, Eq, PartialEq, Clone, Debug)]
+pub enum Logs {
+    #[clap(name = "build", aliases = &["b"], about = "Follow the build output of a deployment")]
+    Build {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+        /// Specify the deployment identifier
+        #[clap(name = "deployment-identifier")]
+        deployment_id: String,
+    },
+}
+
+#[async_trait::async_trait]
+impl Executor for Logs {
+    type Error = Error;
+
+    async fn execute(&self, config: Arc<Configuration>) -> Result<(), Self::Error> {
+        match self {
+            Self::Build {
+                output,
+                organisation_id,
+                application_id,
+                deployment_id,
+            } => {
+                build(
+                    config,
+                    output,
+                    organisation_id,
+                    application_id,
+                    deployment_id,
+                )
+                .await
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// helpers
+
+pub async fn build(
+    config: Arc<Configuration>,
+    output: &Output,
+    organisation_id: &str,
+    application_id: &str,
+    deployment_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let mut lines = Box::pin(build_log::tail(
+        &client,
+        organisation_id,
+        application_id,
+        deployment_id,
+    ));
+
+    while let Some(line) = lines.next().await {
+        let line = line.map_err(|err| {
+            Error::Build(deployment_id.to_owned(), application_id.to_owned(), err)
+        })?;
+
+        println!(
+            "{}",
+            output
+                .format(&line)
+                .map_err(|err| Error::FormatOutput(Box::new(err)))?
+        );
+    }
+
+    Ok(())
+}
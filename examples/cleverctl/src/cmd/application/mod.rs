@@ -0,0 +1,345 @@
+//! # Application module
+//!
+//! This module provides command implementation related to applications
+use std::{collections::BTreeMap, sync::Arc};
+
+use clap::Subcommand;
+use clevercloud_sdk::{
+    oauth10a::{
+        proxy::{self, ProxyConnectorBuilder},
+        Credentials,
+    },
+    v2::{
+        application::{
+            self,
+            run::{self, RunOpts},
+        },
+        owner::OwnerId,
+    },
+    Client,
+};
+
+use crate::{
+    cfg::Configuration,
+    cmd::{self, application::logs::Logs, parse_btreemap, Executor, Output},
+};
+
+pub mod logs;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to format output, {0}")]
+    FormatOutput(Box<cmd::Error>),
+    #[error("failed to build proxy connector, {0}")]
+    ProxyConnector(proxy::Error),
+    #[error("failed to run task on application '{0}' of organisation '{1}', {2}")]
+    Run(String, String, run::Error),
+    #[error("failed to mark application '{0}' of organisation '{1}' as favourite, {2}")]
+    MarkFavourite(String, String, application::Error),
+    #[error("failed to unmark application '{0}' of organisation '{1}' as favourite, {2}")]
+    UnmarkFavourite(String, String, application::Error),
+    #[error("failed to list tags of application '{0}' of organisation '{1}', {2}")]
+    ListTags(String, String, application::Error),
+    #[error("failed to add tag '{0}' to application '{1}' of organisation '{2}', {3}")]
+    AddTag(String, String, String, application::Error),
+    #[error("failed to remove tag '{0}' from application '{1}' of organisation '{2}', {3}")]
+    RemoveTag(String, String, String, application::Error),
+    #[error("failed to execute command on application logs, {0}")]
+    Logs(logs::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Command enumeration
+
+/// Command enum contains all operations that could be achieved on applications
+#[derive(Subcommand, Eq, PartialEq, Clone, Debug)]
+pub enum Command {
+    #[clap(name = "run", aliases = &["r"], about = "Run a one-off task on an application")]
+    Run {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+        /// Specify environment for the task (format: k1=v1,k2=v2)
+        #[clap(short = 'e', long = "environment", value_parser =  parse_btreemap)]
+        environment: Option<BTreeMap<String, String>>,
+        /// Specify the command to run
+        #[clap(name = "command", last = true, required = true)]
+        command: Vec<String>,
+    },
+    #[clap(name = "favourite", aliases = &["fav"], about = "Mark an application as favourite")]
+    Favourite {
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+    },
+    #[clap(name = "unfavourite", aliases = &["unfav"], about = "Unmark an application as favourite")]
+    Unfavourite {
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+    },
+    #[clap(name = "tags", about = "List tags of an application")]
+    Tags {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+    },
+    #[clap(name = "tag", about = "Add a tag to an application")]
+    Tag {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+        /// Specify the name of the tag
+        #[clap(name = "name")]
+        name: String,
+    },
+    #[clap(name = "untag", about = "Remove a tag from an application")]
+    Untag {
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the application identifier
+        #[clap(name = "application-identifier")]
+        application_id: String,
+        /// Specify the tag identifier
+        #[clap(name = "tag-identifier")]
+        tag_id: String,
+    },
+    #[clap(name = "logs", aliases = &["log", "l"], subcommand, about = "Interact with logs of an application")]
+    Logs(Logs),
+}
+
+#[async_trait::async_trait]
+impl Executor for Command {
+    type Error = Error;
+
+    async fn execute(&self, config: Arc<Configuration>) -> Result<(), Self::Error> {
+        match self {
+            Self::Run {
+                output,
+                organisation_id,
+                application_id,
+                environment,
+                command,
+            } => {
+                let opts = RunOpts {
+                    command: command.join(" "),
+                    environment: environment.to_owned().unwrap_or_default(),
+                };
+
+                run(config, output, organisation_id, application_id, &opts).await
+            }
+            Self::Favourite {
+                organisation_id,
+                application_id,
+            } => favourite(config, organisation_id, application_id).await,
+            Self::Unfavourite {
+                organisation_id,
+                application_id,
+            } => unfavourite(config, organisation_id, application_id).await,
+            Self::Tags {
+                output,
+                organisation_id,
+                application_id,
+            } => tags(config, output, organisation_id, application_id).await,
+            Self::Tag {
+                output,
+                organisation_id,
+                application_id,
+                name,
+            } => tag(config, output, organisation_id, application_id, name).await,
+            Self::Untag {
+                organisation_id,
+                application_id,
+                tag_id,
+            } => untag(config, organisation_id, application_id, tag_id).await,
+            Self::Logs(logs) => logs.execute(config).await.map_err(Error::Logs),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// helpers
+
+pub async fn run(
+    config: Arc<Configuration>,
+    output: &Output,
+    organisation_id: &str,
+    application_id: &str,
+    opts: &RunOpts,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let result = run::run(&client, organisation_id, application_id, opts)
+        .await
+        .map_err(|err| Error::Run(application_id.to_owned(), organisation_id.to_owned(), err))?;
+
+    println!(
+        "{}",
+        output
+            .format(&result)
+            .map_err(|err| Error::FormatOutput(Box::new(err)))?
+    );
+    Ok(())
+}
+
+pub async fn favourite(
+    config: Arc<Configuration>,
+    organisation_id: &str,
+    application_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    application::mark_favourite(&client, &OwnerId::from(organisation_id), application_id)
+        .await
+        .map_err(|err| {
+            Error::MarkFavourite(application_id.to_owned(), organisation_id.to_owned(), err)
+        })
+}
+
+pub async fn unfavourite(
+    config: Arc<Configuration>,
+    organisation_id: &str,
+    application_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    application::unmark_favourite(&client, &OwnerId::from(organisation_id), application_id)
+        .await
+        .map_err(|err| {
+            Error::UnmarkFavourite(application_id.to_owned(), organisation_id.to_owned(), err)
+        })
+}
+
+pub async fn tags(
+    config: Arc<Configuration>,
+    output: &Output,
+    organisation_id: &str,
+    application_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let tags = application::list_tags(&client, &OwnerId::from(organisation_id), application_id)
+        .await
+        .map_err(|err| {
+            Error::ListTags(application_id.to_owned(), organisation_id.to_owned(), err)
+        })?;
+
+    println!(
+        "{}",
+        output
+            .format(&tags)
+            .map_err(|err| Error::FormatOutput(Box::new(err)))?
+    );
+    Ok(())
+}
+
+pub async fn tag(
+    config: Arc<Configuration>,
+    output: &Output,
+    organisation_id: &str,
+    application_id: &str,
+    name: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let tag = application::add_tag(
+        &client,
+        &OwnerId::from(organisation_id),
+        application_id,
+        name,
+    )
+    .await
+    .map_err(|err| {
+        Error::AddTag(
+            name.to_owned(),
+            application_id.to_owned(),
+            organisation_id.to_owned(),
+            err,
+        )
+    })?;
+
+    println!(
+        "{}",
+        output
+            .format(&tag)
+            .map_err(|err| Error::FormatOutput(Box::new(err)))?
+    );
+    Ok(())
+}
+
+pub async fn untag(
+    config: Arc<Configuration>,
+    organisation_id: &str,
+    application_id: &str,
+    tag_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    application::remove_tag(
+        &client,
+        &OwnerId::from(organisation_id),
+        application_id,
+        tag_id,
+    )
+    .await
+    .map_err(|err| {
+        Error::RemoveTag(
+            tag_id.to_owned(),
+            application_id.to_owned(),
+            organisation_id.to_owned(),
+            err,
+        )
+    })
+}
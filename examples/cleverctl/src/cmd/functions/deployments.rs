@@ -3,7 +3,12 @@
 //! This module provides all necessary commands to interact with a function's deployment including
 //! uploading and deploying it.
 
-use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use clap::Subcommand;
 use clevercloud_sdk::{
@@ -15,13 +20,20 @@ use clevercloud_sdk::{
     Client,
 };
 use tokio::fs::read;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
     cfg::Configuration,
     cmd::{self, parse_btreemap, Executor, Output},
+    progress::Spinner,
 };
 
+// ----------------------------------------------------------------------------
+// Constants
+
+/// Interval between two polls of a deployment while waiting for it to be ready
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 // ----------------------------------------------------------------------------
 // Error
 
@@ -45,6 +57,14 @@ pub enum Error {
     Upload(String, String, String, String, deployments::Error),
     #[error("failed to trigger deployment '{0}' of function '{1}' for organisation '{2}', {3}")]
     Trigger(String, String, String, deployments::Error),
+    #[error("failed to wait for deployment '{0}' of function '{1}' for organisation '{2}' to be ready, {3}")]
+    Wait(String, String, String, deployments::Error),
+    #[error("failed to wait for deployment '{0}' of function '{1}' for organisation '{2}' to be ready, still not ready after {3}s")]
+    WaitTimeout(String, String, String, u64),
+    #[error("failed to detect the platform of file '{0}', please specify it using '--platform'")]
+    DetectPlatform(String),
+    #[error("failed to read file '{0}', it is not a WebAssembly module")]
+    InvalidWasmFile(String),
 }
 
 // ----------------------------------------------------------------------------
@@ -87,9 +107,15 @@ pub enum Command {
         /// Specify the WebAssembly file to upload
         #[clap(short = 'f', long = "file")]
         file: PathBuf,
-        /// Specify the language of the functions (available options are 'rust', 'javascript', 'tinygo' and 'assemblyscript')
+        /// Specify the language of the functions (available options are 'rust', 'javascript', 'tinygo' and 'assemblyscript'), detected from the file if omitted
         #[clap(short = 'p', long = "platform")]
-        platform: Platform,
+        platform: Option<Platform>,
+        /// Wait for the deployment to be ready before returning
+        #[clap(short = 'w', long = "wait")]
+        wait: bool,
+        /// Specify how long to wait for the deployment to be ready, in seconds
+        #[clap(long = "wait-timeout", default_value_t = 300)]
+        wait_timeout: u64,
     },
     #[clap(name = "get", aliases = &["g"], about = "Get information about a function")]
     Get {
@@ -140,6 +166,8 @@ impl Executor for Command {
                 tags,
                 file,
                 platform,
+                wait,
+                wait_timeout,
             } => {
                 let tag = tags.as_ref().map(|tags| {
                     tags.iter()
@@ -150,14 +178,59 @@ impl Executor for Command {
                         .join(" ")
                 });
 
+                let buf = read(file)
+                    .await
+                    .map_err(|err| Error::Read(file.display().to_string(), err))?;
+
+                if !deployments::is_wasm(&buf) {
+                    return Err(Error::InvalidWasmFile(file.display().to_string()));
+                }
+
+                let detected = deployments::detect_platform(&buf);
+                let platform = match (platform, detected) {
+                    (Some(platform), Some(detected)) if *platform != detected => {
+                        warn!(
+                            file = file.display().to_string(),
+                            platform = platform.to_string(),
+                            detected = detected.to_string(),
+                            "Given platform does not match the one detected from the WebAssembly file"
+                        );
+                        platform.to_owned()
+                    }
+                    (Some(platform), _) => platform.to_owned(),
+                    (None, Some(detected)) => {
+                        info!(
+                            file = file.display().to_string(),
+                            platform = detected.to_string(),
+                            "Detected platform from the WebAssembly file"
+                        );
+                        detected
+                    }
+                    (None, None) => {
+                        return Err(Error::DetectPlatform(file.display().to_string()));
+                    }
+                };
+
                 let opts = deployments::Opts {
                     name: name.to_owned(),
                     description: description.to_owned(),
                     tag,
-                    platform: platform.to_owned(),
+                    platform,
                 };
 
-                create(config, output, organisation_id, function_id, file, &opts).await
+                let timeout = wait.then(|| Duration::from_secs(*wait_timeout));
+
+                create(
+                    config,
+                    output,
+                    organisation_id,
+                    function_id,
+                    file,
+                    buf,
+                    &opts,
+                    timeout,
+                )
+                .await
             }
             Self::Get {
                 output,
@@ -203,13 +276,16 @@ pub async fn list(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create(
     config: Arc<Configuration>,
     output: &Output,
     organisation_id: &str,
     function_id: &str,
-    file: &PathBuf,
+    file: &Path,
+    buf: Vec<u8>,
     opts: &Opts,
+    wait: Option<Duration>,
 ) -> Result<(), Error> {
     let credentials: Credentials = config.credentials.to_owned().into();
     let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
@@ -222,21 +298,11 @@ pub async fn create(
         function_id = function_id,
         "Create deployment for function"
     );
+    let spinner = Spinner::new("Create deployment for function");
     let deployment_c = deployments::create(&client, organisation_id, function_id, opts)
         .await
         .map_err(|err| Error::Create(function_id.to_string(), organisation_id.to_string(), err))?;
 
-    info!(
-        organisation_id = organisation_id,
-        function_id = function_id,
-        file = file.display().to_string(),
-        deployment_id = deployment_c.id,
-        "Read WebAssembly to a buffer"
-    );
-    let buf = read(file)
-        .await
-        .map_err(|err| Error::Read(file.display().to_string(), err))?;
-
     info!(
         organisation_id = organisation_id,
         function_id = function_id,
@@ -244,6 +310,7 @@ pub async fn create(
         deployment_id = deployment_c.id,
         "Upload WebAssembly for deployment"
     );
+    spinner.set_message("Upload WebAssembly for deployment");
 
     deployments::upload(&client, &deployment_c.upload_url, buf)
         .await
@@ -263,6 +330,7 @@ pub async fn create(
         deployment_id = deployment_c.id,
         "Trigger deployment of the function"
     );
+    spinner.set_message("Trigger deployment of the function");
 
     deployments::trigger(&client, organisation_id, function_id, &deployment_c.id)
         .await
@@ -275,24 +343,64 @@ pub async fn create(
             )
         })?;
 
-    info!(
-        organisation_id = organisation_id,
-        function_id = function_id,
-        deployment_id = deployment_c.id,
-        "Retrieve deployment"
-    );
-
-    let deployment = deployments::get(&client, organisation_id, function_id, &deployment_c.id)
-        .await
-        .map_err(|err| {
-            Error::Get(
-                deployment_c.id.to_string(),
-                function_id.to_string(),
-                organisation_id.to_string(),
-                err,
-            )
-        })?;
+    let deployment = match wait {
+        Some(timeout) => {
+            info!(
+                organisation_id = organisation_id,
+                function_id = function_id,
+                deployment_id = deployment_c.id,
+                timeout = timeout.as_secs(),
+                "Wait for deployment to be ready"
+            );
+            spinner.set_message("Wait for deployment to be ready");
+
+            deployment_c
+                .wait_until_ready(
+                    &client,
+                    organisation_id,
+                    function_id,
+                    POLL_INTERVAL,
+                    timeout,
+                )
+                .await
+                .map_err(|err| match err {
+                    deployments::Error::Timeout(id, _, secs) => Error::WaitTimeout(
+                        id,
+                        function_id.to_string(),
+                        organisation_id.to_string(),
+                        secs,
+                    ),
+                    err => Error::Wait(
+                        deployment_c.id.to_string(),
+                        function_id.to_string(),
+                        organisation_id.to_string(),
+                        err,
+                    ),
+                })?
+        }
+        None => {
+            info!(
+                organisation_id = organisation_id,
+                function_id = function_id,
+                deployment_id = deployment_c.id,
+                "Retrieve deployment"
+            );
+            spinner.set_message("Retrieve deployment");
+
+            deployments::get(&client, organisation_id, function_id, &deployment_c.id)
+                .await
+                .map_err(|err| {
+                    Error::Get(
+                        deployment_c.id.to_string(),
+                        function_id.to_string(),
+                        organisation_id.to_string(),
+                        err,
+                    )
+                })?
+        }
+    };
 
+    spinner.finish();
     println!(
         "{}",
         output
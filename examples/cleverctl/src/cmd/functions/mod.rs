@@ -180,19 +180,17 @@ impl Executor for Command {
                 environment,
                 max_memory,
             } => {
-                let tag = tags.as_ref().map(|tags| {
-                    tags.iter()
-                        .fold(vec![], |mut acc, (k, v)| {
-                            acc.push(format!("{k}:{v}"));
-                            acc
-                        })
-                        .join(" ")
-                });
-
                 let opts = functions::Opts {
                     name: name.to_owned(),
                     description: description.to_owned(),
-                    tag,
+                    tag: tags.as_ref().and_then(|tags| {
+                        functions::format_tags(
+                            &tags
+                                .iter()
+                                .map(|(k, v)| (k.to_owned(), Some(v.to_owned())))
+                                .collect(),
+                        )
+                    }),
                     environment: environment.to_owned().unwrap_or_default(),
                     max_memory: max_memory.unwrap_or_else(|| DEFAULT_MAX_MEMORY),
                     max_instances: DEFAULT_INSTANCES,
@@ -215,19 +213,17 @@ impl Executor for Command {
                 environment,
                 max_memory,
             } => {
-                let tag = tags.as_ref().map(|tags| {
-                    tags.iter()
-                        .fold(vec![], |mut acc, (k, v)| {
-                            acc.push(format!("{k}:{v}"));
-                            acc
-                        })
-                        .join(" ")
-                });
-
                 let opts = functions::Opts {
                     name: name.to_owned(),
                     description: description.to_owned(),
-                    tag,
+                    tag: tags.as_ref().and_then(|tags| {
+                        functions::format_tags(
+                            &tags
+                                .iter()
+                                .map(|(k, v)| (k.to_owned(), Some(v.to_owned())))
+                                .collect(),
+                        )
+                    }),
                     environment: environment.to_owned().unwrap_or_default(),
                     max_memory: max_memory.unwrap_or_else(|| DEFAULT_MAX_MEMORY),
                     max_instances: DEFAULT_INSTANCES,
@@ -390,15 +386,13 @@ pub async fn execute(
         "List deployment to take the latest one"
     );
 
-    let mut deploymentz = functions::deployments::list(&client, organisation_id, function_id)
+    let mut deploymentz = functions::deployments::list_ready(&client, organisation_id, function_id)
         .await
         .map_err(|err| {
             Error::ListDeployment(function_id.to_string(), organisation_id.to_string(), err)
         })?
         .into_iter()
-        .filter(|deployment| {
-            deployment.url.is_some() && functions::deployments::Status::Ready == deployment.status
-        })
+        .filter(|deployment| deployment.url.is_some())
         .collect::<Vec<_>>();
 
     deploymentz.sort_by(|a, b| a.created_at.cmp(&b.created_at));
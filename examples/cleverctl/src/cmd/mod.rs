@@ -13,12 +13,15 @@ use std::{
 use clap::{ArgAction, Parser, Subcommand};
 use paw::ParseArgs;
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::cfg::Configuration;
 
 pub mod addon;
+pub mod application;
 pub mod functions;
 pub mod myself;
+pub mod organisation;
 pub mod zone;
 
 // -----------------------------------------------------------------------------
@@ -26,16 +29,26 @@ pub mod zone;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("failed to parse output '{0}', available options are 'json' or 'yaml'")]
+    #[error("failed to parse output '{0}', available options are 'json', 'yaml', 'toml' or 'csv'")]
     ParseOutput(String),
     #[error("failed to serialize object into json, {0}")]
     SerializeJson(serde_json::Error),
     #[error("failed to serialize object into yaml, {0}")]
     SerializeYaml(serde_yaml::Error),
+    #[error("failed to serialize object into toml, {0}")]
+    SerializeToml(toml::ser::Error),
+    #[error("failed to serialize object into csv, {0}")]
+    SerializeCsv(csv::Error),
+    #[error("failed to encode csv as utf-8, {0}")]
+    EncodeCsv(std::string::FromUtf8Error),
     #[error("failed to execute command relative to the current user, {0}")]
     MyselfCommand(myself::Error),
     #[error("failed to execute command relative to addons, {0}")]
     AddonCommand(addon::Error),
+    #[error("failed to execute command relative to applications, {0}")]
+    ApplicationCommand(application::Error),
+    #[error("failed to execute command relative to organisations, {0}")]
+    OrganisationCommand(organisation::Error),
     #[error("failed to execute command relative to zones, {0}")]
     ZoneCommand(zone::Error),
     #[error("failed to execute command relative to functions, {0}")]
@@ -50,6 +63,8 @@ pub enum Output {
     #[default]
     Json,
     Yaml,
+    Toml,
+    Csv,
 }
 
 impl FromStr for Output {
@@ -59,6 +74,8 @@ impl FromStr for Output {
         match s.to_lowercase().as_str() {
             "json" => Ok(Self::Json),
             "yaml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            "csv" => Ok(Self::Csv),
             _ => Err(Error::ParseOutput(s.to_owned())),
         }
     }
@@ -69,6 +86,8 @@ impl Display for Output {
         match self {
             Self::Json => write!(f, "json"),
             Self::Yaml => write!(f, "yaml"),
+            Self::Toml => write!(f, "toml"),
+            Self::Csv => write!(f, "csv"),
         }
     }
 }
@@ -81,10 +100,108 @@ impl Output {
         Ok(match self {
             Output::Json => serde_json::to_string_pretty(obj).map_err(Error::SerializeJson)?,
             Output::Yaml => serde_yaml::to_string(obj).map_err(Error::SerializeYaml)?,
+            Output::Toml => obj.to_toml()?,
+            Output::Csv => obj.to_csv()?,
         })
     }
 }
 
+// -----------------------------------------------------------------------------
+// ToToml trait
+
+/// ToToml trait provides a way to format an object as toml, as toml documents
+/// must be tables, arrays are wrapped under the `items` key
+pub trait ToToml {
+    fn to_toml(&self) -> Result<String, Error>;
+}
+
+#[derive(Serialize)]
+struct Items<'a, T> {
+    #[serde(rename = "items")]
+    items: &'a T,
+}
+
+impl<T> ToToml for T
+where
+    T: Serialize,
+{
+    fn to_toml(&self) -> Result<String, Error> {
+        if serde_json::to_value(self)
+            .map_err(Error::SerializeJson)?
+            .is_array()
+        {
+            return toml::to_string_pretty(&Items { items: self }).map_err(Error::SerializeToml);
+        }
+
+        toml::to_string_pretty(self).map_err(Error::SerializeToml)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ToCsv trait
+
+/// ToCsv trait provides a way to format flat objects like zones, addons or
+/// functions as csv, an array gives a record per element and nested values are
+/// written as json
+pub trait ToCsv {
+    fn to_csv(&self) -> Result<String, Error>;
+}
+
+impl<T> ToCsv for T
+where
+    T: Serialize,
+{
+    fn to_csv(&self) -> Result<String, Error> {
+        let rows = match serde_json::to_value(self).map_err(Error::SerializeJson)? {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+
+        let mut headers: Vec<String> = vec![];
+        for row in &rows {
+            if let Value::Object(map) = row {
+                for key in map.keys() {
+                    if !headers.contains(key) {
+                        headers.push(key.to_owned());
+                    }
+                }
+            }
+        }
+
+        let mut writer = csv::Writer::from_writer(vec![]);
+        if headers.is_empty() {
+            writer
+                .write_record(["value"])
+                .map_err(Error::SerializeCsv)?;
+        } else {
+            writer.write_record(&headers).map_err(Error::SerializeCsv)?;
+        }
+
+        for row in &rows {
+            let record: Vec<String> = match row {
+                Value::Object(map) => headers.iter().map(|key| cell(map.get(key))).collect(),
+                value => vec![cell(Some(value))],
+            };
+
+            writer.write_record(&record).map_err(Error::SerializeCsv)?;
+        }
+
+        let buf = writer
+            .into_inner()
+            .map_err(|err| Error::SerializeCsv(err.into_error().into()))?;
+
+        String::from_utf8(buf).map_err(Error::EncodeCsv)
+    }
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.to_owned(),
+        Some(value) => value.to_string(),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Excutor trait
 
@@ -106,6 +223,10 @@ pub enum Command {
     Myself(myself::Command),
     #[clap(name = "addon", aliases = &["addo", "add", "ad", "a"], subcommand, about = "Interact with addons")]
     Addon(addon::Command),
+    #[clap(name = "application", aliases = &["applicatio", "applicati", "applicat", "applica", "applic", "appli", "appl", "app"], subcommand, about = "Interact with applications")]
+    Application(application::Command),
+    #[clap(name = "organisation", aliases = &["organization", "orga", "org", "o"], subcommand, about = "Interact with organisations")]
+    Organisation(organisation::Command),
     #[clap(name = "zone", aliases = &["zon", "zo", "z"], subcommand, about = "Interact with zones")]
     Zone(zone::Command),
     #[clap(name = "functions", aliases = &["functio", "functi", "funct", "func", "fun", "fu", "f"], subcommand, about = "Interact with functions")]
@@ -120,6 +241,11 @@ impl Executor for Command {
         match self {
             Self::Myself(cmd) => cmd.execute(config).await.map_err(Error::MyselfCommand),
             Self::Addon(cmd) => cmd.execute(config).await.map_err(Error::AddonCommand),
+            Self::Application(cmd) => cmd.execute(config).await.map_err(Error::ApplicationCommand),
+            Self::Organisation(cmd) => cmd
+                .execute(config)
+                .await
+                .map_err(Error::OrganisationCommand),
             Self::Zone(cmd) => cmd.execute(config).await.map_err(Error::ZoneCommand),
             Self::Function(cmd) => cmd.execute(config).await.map_err(Error::FunctionCommand),
         }
@@ -143,6 +269,11 @@ pub struct Args {
     /// Check the healthiness of the configuration
     #[clap(long = "check", global = true)]
     pub check: bool,
+    /// Show a spinner during long-running operations, to be given before the
+    /// command (e.g. `cleverctl -p functions deployments create ...`)
+    #[cfg(feature = "progress")]
+    #[clap(short = 'p', long = "progress")]
+    pub progress: bool,
     #[clap(subcommand)]
     pub cmd: Command,
 }
@@ -0,0 +1,139 @@
+//! # Organisation module
+//!
+//! This module provides command implementation related to organisations
+use std::sync::Arc;
+
+use clap::Subcommand;
+use clevercloud_sdk::{
+    oauth10a::{
+        proxy::{self, ProxyConnectorBuilder},
+        Credentials,
+    },
+    v2::organisation::{activity, quota},
+    Client,
+};
+
+use crate::{
+    cfg::Configuration,
+    cmd::{self, Executor, Output},
+};
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to format output, {0}")]
+    FormatOutput(Box<cmd::Error>),
+    #[error("failed to build proxy connector, {0}")]
+    ProxyConnector(proxy::Error),
+    #[error("failed to get quotas of organisation '{0}', {1}")]
+    Quota(String, quota::Error),
+    #[error("failed to list activities of organisation '{0}', {1}")]
+    Activity(String, activity::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Command enumeration
+
+/// Command enum contains all operations that could be achieved on organisations
+#[derive(Subcommand, Eq, PartialEq, Clone, Debug)]
+pub enum Command {
+    #[clap(name = "quota", aliases = &["quotas", "q"], about = "Get resource quotas of an organisation")]
+    Quota {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+    },
+    #[clap(name = "activity", aliases = &["activities", "a"], about = "List activities of an organisation")]
+    Activity {
+        /// Specify the output format
+        #[clap(short = 'o', long = "output", default_value_t)]
+        output: Output,
+        /// Specify the organisation identifier
+        #[clap(name = "organisation-identifier")]
+        organisation_id: String,
+        /// Specify the maximum number of activities to retrieve
+        #[clap(short = 'l', long = "limit")]
+        limit: Option<u32>,
+        /// Specify the number of activities to skip
+        #[clap(long = "offset")]
+        offset: Option<u32>,
+    },
+}
+
+#[async_trait::async_trait]
+impl Executor for Command {
+    type Error = Error;
+
+    async fn execute(&self, config: Arc<Configuration>) -> Result<(), Self::Error> {
+        match self {
+            Self::Quota {
+                output,
+                organisation_id,
+            } => quota(config, output, organisation_id).await,
+            Self::Activity {
+                output,
+                organisation_id,
+                limit,
+                offset,
+            } => activity(config, output, organisation_id, *limit, *offset).await,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// helpers
+
+pub async fn quota(
+    config: Arc<Configuration>,
+    output: &Output,
+    organisation_id: &str,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let quotas = quota::get(&client, organisation_id)
+        .await
+        .map_err(|err| Error::Quota(organisation_id.to_owned(), err))?;
+
+    println!(
+        "{}",
+        output
+            .format(&quotas)
+            .map_err(|err| Error::FormatOutput(Box::new(err)))?
+    );
+    Ok(())
+}
+
+pub async fn activity(
+    config: Arc<Configuration>,
+    output: &Output,
+    organisation_id: &str,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<(), Error> {
+    let credentials: Credentials = config.credentials.to_owned().into();
+    let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+    let client = Client::builder()
+        .with_credentials(credentials)
+        .build(connector);
+
+    let activities = activity::list(&client, organisation_id, limit, offset)
+        .await
+        .map_err(|err| Error::Activity(organisation_id.to_owned(), err))?;
+
+    println!(
+        "{}",
+        output
+            .format(&activities)
+            .map_err(|err| Error::FormatOutput(Box::new(err)))?
+    );
+    Ok(())
+}
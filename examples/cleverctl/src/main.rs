@@ -5,6 +5,13 @@
 //! project
 use std::sync::Arc;
 
+use clevercloud_sdk::{
+    oauth10a::{
+        proxy::{self, ProxyConnectorBuilder},
+        Credentials,
+    },
+    Client,
+};
 use tracing::{debug, error, info};
 
 use crate::{
@@ -15,6 +22,7 @@ use crate::{
 pub mod cfg;
 pub mod cmd;
 pub mod logging;
+pub mod progress;
 
 // -----------------------------------------------------------------------------
 // Error enumeration
@@ -29,6 +37,10 @@ pub enum Error {
     ParseCommandLine(std::io::Error),
     #[error("failed to initialize logging system, {0}")]
     Logging(logging::Error),
+    #[error("failed to build proxy connector, {0}")]
+    ProxyConnector(proxy::Error),
+    #[error("failed to validate configuration, {0}")]
+    Validation(cfg::ValidationError),
 }
 
 impl From<std::io::Error> for Error {
@@ -50,6 +62,8 @@ impl From<cmd::Error> for Error {
 #[tokio::main]
 pub async fn main(args: Args) -> Result<(), Error> {
     logging::initialize(args.verbosity as usize).map_err(Error::Logging)?;
+    #[cfg(feature = "progress")]
+    progress::initialize(args.progress);
 
     let result = match &args.config {
         Some(pb) => Configuration::try_from(pb).map_err(Error::Configuration),
@@ -65,6 +79,17 @@ pub async fn main(args: Args) -> Result<(), Error> {
     };
 
     if args.check {
+        let credentials: Credentials = config.credentials.to_owned().into();
+        let connector = ProxyConnectorBuilder::try_from_env().map_err(Error::ProxyConnector)?;
+        let client = Client::builder()
+            .with_credentials(credentials)
+            .build(connector);
+
+        if let Err(err) = config.validate(&client).await.map_err(Error::Validation) {
+            error!(error = err.to_string(), "Configuration is not healthy");
+            return Err(err);
+        }
+
         info!("Configuration is healthy!");
         debug!("Configuration is {:#?}", config);
         return Ok(());
@@ -0,0 +1,88 @@
+//! # Progress module
+//!
+//! This module provides a spinner giving feedback during long-running
+//! operations, it is only displayed if the progress indicator is enabled on the
+//! command line and the `progress` feature is compiled in
+
+#[cfg(feature = "progress")]
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+#[cfg(feature = "progress")]
+use indicatif::ProgressBar;
+
+// -----------------------------------------------------------------------------
+// Constants
+
+#[cfg(feature = "progress")]
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "progress")]
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+// -----------------------------------------------------------------------------
+// Spinner structure
+
+/// Spinner structure shows a status message while an operation is in progress,
+/// it does nothing if the progress indicator is disabled and is cleared once
+/// dropped
+#[derive(Debug, Default)]
+pub struct Spinner {
+    #[cfg(feature = "progress")]
+    bar: Option<ProgressBar>,
+}
+
+#[cfg(feature = "progress")]
+impl Spinner {
+    pub fn new(message: &str) -> Self {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return Self::default();
+        }
+
+        let bar = ProgressBar::new_spinner();
+        bar.enable_steady_tick(TICK_INTERVAL);
+        bar.set_message(message.to_owned());
+
+        Self { bar: Some(bar) }
+    }
+
+    pub fn set_message(&self, message: &str) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(message.to_owned());
+        }
+    }
+
+    pub fn finish(self) {
+        drop(self);
+    }
+}
+
+#[cfg(not(feature = "progress"))]
+impl Spinner {
+    pub fn new(_message: &str) -> Self {
+        Self::default()
+    }
+
+    pub fn set_message(&self, _message: &str) {}
+
+    pub fn finish(self) {}
+}
+
+#[cfg(feature = "progress")]
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// helpers
+
+#[cfg(feature = "progress")]
+pub fn initialize(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
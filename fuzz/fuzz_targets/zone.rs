@@ -0,0 +1,6 @@
+#![no_main]
+
+use clevercloud_sdk::v4::products::zones;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| zones::fuzz_round_trip(data));
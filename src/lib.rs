@@ -4,11 +4,21 @@
 //! api.
 
 use std::{fmt::Debug, marker::PhantomData};
+#[cfg(feature = "danger-accept-invalid-certs")]
+use std::{sync::Arc, time::SystemTime};
 
 pub use oauth10a::client as oauth10a;
 
 use async_trait::async_trait;
 use hyper::{Body, Method, Response};
+#[cfg(feature = "logging")]
+use log::{log_enabled, warn, Level};
+#[cfg(feature = "danger-accept-invalid-certs")]
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier},
+    ServerName,
+};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::oauth10a::{
@@ -19,11 +29,47 @@ use crate::oauth10a::{
 pub mod v2;
 pub mod v4;
 
+#[cfg(test)]
+mod mock;
+mod sse;
+
 // -----------------------------------------------------------------------------
 // Constants
 
 pub const PUBLIC_ENDPOINT: &str = "https://api.clever-cloud.com";
 
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to add custom certificate authority to the trusted roots, {0}")]
+    CaCertificate(rustls::Error),
+}
+
+// -----------------------------------------------------------------------------
+// NoCertificateVerification structure
+
+/// NoCertificateVerification structure accepts any certificate presented by
+/// the server, it must only be used in development environments
+#[cfg(feature = "danger-accept-invalid-certs")]
+struct NoCertificateVerification;
+
+#[cfg(feature = "danger-accept-invalid-certs")]
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Builder structure
 
@@ -34,6 +80,9 @@ where
 {
     endpoint: Option<String>,
     credentials: Option<Credentials>,
+    ca_certificate: Option<Certificate>,
+    #[cfg(feature = "danger-accept-invalid-certs")]
+    danger_accept_invalid_certs: bool,
     phantom: PhantomData<C>,
 }
 
@@ -45,6 +94,9 @@ where
         Self {
             endpoint: None,
             credentials: None,
+            ca_certificate: None,
+            #[cfg(feature = "danger-accept-invalid-certs")]
+            danger_accept_invalid_certs: false,
             phantom: Default::default(),
         }
     }
@@ -77,6 +129,77 @@ where
     }
 }
 
+impl Builder<HttpsConnector<HttpConnector<GaiResolver>>> {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// trust the given certificate authority in addition to the public ones,
+    /// this is useful when the api is served behind a proxy using a private
+    /// certificate authority
+    pub fn with_custom_ca_certificate(mut self, certificate: Certificate) -> Self {
+        self.ca_certificate = Some(certificate);
+        self
+    }
+
+    #[cfg(feature = "danger-accept-invalid-certs")]
+    #[deprecated(
+        note = "accepting invalid certificates disables tls verification, only use it in development environments"
+    )]
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// accept any certificate presented by the server, disabled by default
+    pub fn with_danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// build the client with an https connector that honours the custom
+    /// certificate authority and certificate verification settings
+    pub fn build_https(self) -> Result<Client<HttpsConnector<HttpConnector<GaiResolver>>>, Error> {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+
+        if let Some(certificate) = &self.ca_certificate {
+            #[cfg(feature = "logging")]
+            if log_enabled!(Level::Warn) {
+                warn!("a custom certificate authority is trusted to connect to the api");
+            }
+
+            roots.add(certificate).map_err(Error::CaCertificate)?;
+        }
+
+        #[cfg_attr(not(feature = "danger-accept-invalid-certs"), allow(unused_mut))]
+        let mut config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        #[cfg(feature = "danger-accept-invalid-certs")]
+        if self.danger_accept_invalid_certs {
+            #[cfg(feature = "logging")]
+            if log_enabled!(Level::Warn) {
+                warn!("certificates presented by the api are not verified");
+            }
+
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(NoCertificateVerification));
+        }
+
+        let connector = HttpsConnectorBuilder::new()
+            .with_tls_config(config)
+            .https_only()
+            .enable_http1()
+            .build();
+
+        Ok(self.build(connector))
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Client structure
 
@@ -87,6 +210,7 @@ where
 {
     inner: oauth10a::Client<C>,
     endpoint: String,
+    credentials: Option<Credentials>,
 }
 
 #[async_trait]
@@ -207,8 +331,9 @@ where
     #[cfg_attr(feature = "trace", tracing::instrument)]
     pub fn new(connector: C, endpoint: String, credentials: Option<Credentials>) -> Self {
         Self {
-            inner: oauth10a::Client::<C>::new(connector, credentials),
+            inner: oauth10a::Client::<C>::new(connector, credentials.to_owned()),
             endpoint,
+            credentials,
         }
     }
 
@@ -224,7 +349,13 @@ where
 
     #[cfg_attr(feature = "trace", tracing::instrument)]
     pub fn set_credentials(&mut self, credentials: Option<Credentials>) {
-        self.inner.set_credentials(credentials);
+        self.inner.set_credentials(credentials.to_owned());
+        self.credentials = credentials;
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.credentials.as_ref()
     }
 
     #[cfg_attr(feature = "trace", tracing::instrument)]
@@ -232,3 +363,79 @@ where
         self.inner.inner()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// der encoding of a self-signed certificate authority
+    const CA_CERTIFICATE: &[u8] = &[
+        0x30, 0x82, 0x01, 0x91, 0x30, 0x82, 0x01, 0x37, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x14,
+        0x36, 0x2b, 0x3e, 0x4d, 0xc7, 0x53, 0x77, 0x6a, 0xed, 0x6c, 0x81, 0xb4, 0x51, 0x17, 0xc4,
+        0xbe, 0xc1, 0x29, 0x2c, 0x90, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04,
+        0x03, 0x02, 0x30, 0x1d, 0x31, 0x1b, 0x30, 0x19, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x12,
+        0x45, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x20, 0x50, 0x72, 0x69, 0x76, 0x61, 0x74, 0x65,
+        0x20, 0x43, 0x41, 0x30, 0x20, 0x17, 0x0d, 0x32, 0x36, 0x31, 0x30, 0x31, 0x36, 0x31, 0x34,
+        0x35, 0x38, 0x31, 0x32, 0x5a, 0x18, 0x0f, 0x32, 0x31, 0x32, 0x36, 0x30, 0x39, 0x32, 0x32,
+        0x31, 0x34, 0x35, 0x38, 0x31, 0x32, 0x5a, 0x30, 0x1d, 0x31, 0x1b, 0x30, 0x19, 0x06, 0x03,
+        0x55, 0x04, 0x03, 0x0c, 0x12, 0x45, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x20, 0x50, 0x72,
+        0x69, 0x76, 0x61, 0x74, 0x65, 0x20, 0x43, 0x41, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a,
+        0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01,
+        0x07, 0x03, 0x42, 0x00, 0x04, 0xdb, 0x76, 0x48, 0xcf, 0xc2, 0x8f, 0x0b, 0x09, 0x39, 0x27,
+        0xca, 0xba, 0x85, 0xe0, 0x8c, 0xb5, 0xd8, 0x56, 0x08, 0xf1, 0x9f, 0x54, 0x9f, 0xc7, 0x86,
+        0x3b, 0xbe, 0xf5, 0xc4, 0xa8, 0x93, 0xe4, 0xdf, 0xd0, 0x51, 0x15, 0xa9, 0x21, 0xcc, 0x22,
+        0x9c, 0xaf, 0x39, 0x3e, 0x58, 0x10, 0xef, 0x45, 0x4a, 0xf5, 0xe6, 0x6a, 0x92, 0xab, 0x9a,
+        0x85, 0x62, 0x73, 0xf9, 0x38, 0x27, 0x22, 0x5e, 0x10, 0xa3, 0x53, 0x30, 0x51, 0x30, 0x1d,
+        0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14, 0xd1, 0x97, 0x07, 0xb1, 0x22, 0xc8,
+        0x14, 0xd6, 0x81, 0xf6, 0xea, 0xf5, 0x79, 0x02, 0x7c, 0x08, 0xf1, 0xf5, 0x97, 0x94, 0x30,
+        0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80, 0x14, 0xd1, 0x97, 0x07,
+        0xb1, 0x22, 0xc8, 0x14, 0xd6, 0x81, 0xf6, 0xea, 0xf5, 0x79, 0x02, 0x7c, 0x08, 0xf1, 0xf5,
+        0x97, 0x94, 0x30, 0x0f, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01, 0xff, 0x04, 0x05, 0x30,
+        0x03, 0x01, 0x01, 0xff, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03,
+        0x02, 0x03, 0x48, 0x00, 0x30, 0x45, 0x02, 0x20, 0x0c, 0x6d, 0x35, 0xae, 0x1d, 0x20, 0x68,
+        0xc6, 0x3d, 0x94, 0x5d, 0x98, 0xb1, 0x6e, 0xe9, 0xed, 0xd8, 0xc0, 0x56, 0x4f, 0x76, 0xe9,
+        0xe4, 0xe3, 0x95, 0x99, 0x99, 0xdf, 0xf0, 0x38, 0x24, 0x74, 0x02, 0x21, 0x00, 0xb3, 0x85,
+        0x0b, 0xaa, 0xac, 0x71, 0x42, 0x59, 0x5e, 0x07, 0x29, 0xd2, 0xe2, 0xa9, 0x3a, 0x21, 0xde,
+        0x76, 0xb8, 0x6a, 0x55, 0x6b, 0x18, 0xe5, 0x33, 0xcf, 0x6d, 0xdf, 0x28, 0x93, 0xfa, 0xdf,
+    ];
+
+    #[test]
+    fn build_https_trusts_the_custom_certificate_authority() {
+        let client = Client::builder()
+            .with_custom_ca_certificate(Certificate(CA_CERTIFICATE.to_vec()))
+            .build_https()
+            .expect("client");
+
+        assert_eq!(client.endpoint, PUBLIC_ENDPOINT);
+        assert!(matches!(
+            Client::builder()
+                .with_custom_ca_certificate(Certificate(b"not a certificate".to_vec()))
+                .build_https(),
+            Err(Error::CaCertificate(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn only_build_https_refuses_plain_http_endpoints() {
+        let server = Server::start(vec![Response::json(200, "{}")]).await;
+
+        let mut client = Client::from(Credentials::default());
+        client.set_endpoint(server.endpoint());
+        client
+            .get::<serde_json::Value>(&server.endpoint())
+            .await
+            .expect("plain http to be allowed");
+
+        let client = Client::builder()
+            .with_endpoint(server.endpoint())
+            .with_custom_ca_certificate(Certificate(CA_CERTIFICATE.to_vec()))
+            .build_https()
+            .expect("client");
+        assert!(client
+            .get::<serde_json::Value>(&server.endpoint())
+            .await
+            .is_err());
+        assert_eq!(server.uris(), vec!["/"]);
+    }
+}
@@ -0,0 +1,173 @@
+//! # Mock module
+//!
+//! This module provide a http server answering scripted responses in order and
+//! recording the requests it receives, it is used by tests to check what
+//! helpers send to the api and how they handle its answers
+
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    body::Bytes,
+    client::HttpConnector,
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, HeaderMap, Server as HyperServer,
+};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Request structure
+
+/// Request structure is a request received by the server
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub uri: String,
+    pub headers: HeaderMap,
+    pub bytes: Bytes,
+    pub body: String,
+}
+
+impl Request {
+    /// returns the body of the request parsed as json
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).expect("request body to be json")
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Response structure
+
+/// Response structure is a response answered by the server
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+impl Response {
+    /// returns a response with the given status and json body
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "application/json".to_string(),
+            body: body.into(),
+        }
+    }
+
+    /// returns a response streaming the given server-sent events, the stream
+    /// ends with the body
+    pub fn event_stream(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/event-stream".to_string(),
+            body: body.into(),
+        }
+    }
+
+    /// returns an error response formatted like the ones of the api
+    pub fn error(status: u16) -> Self {
+        Self::json(
+            status,
+            format!(r#"{{"id":{status},"message":"error","type":"error"}}"#),
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Server structure
+
+/// Server structure answers the scripted responses in order, once they are
+/// exhausted it answers with a 404 status code
+pub struct Server {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl Server {
+    /// start a server on a random port of the loopback interface
+    pub async fn start(responses: Vec<Response>) -> Self {
+        let responses = Arc::new(Mutex::new(VecDeque::from(responses)));
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let (svc_responses, svc_requests) = (responses.clone(), requests.clone());
+        let make_svc = make_service_fn(move |_| {
+            let (responses, requests) = (svc_responses.clone(), svc_requests.clone());
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: hyper::Request<Body>| {
+                    let (responses, requests) = (responses.clone(), requests.clone());
+                    async move {
+                        let method = req.method().to_string();
+                        let uri = req.uri().to_string();
+                        let headers = req.headers().to_owned();
+                        let bytes = hyper::body::to_bytes(req.into_body())
+                            .await
+                            .unwrap_or_default();
+                        let body = String::from_utf8_lossy(&bytes).into_owned();
+
+                        requests
+                            .lock()
+                            .expect("lock to not be poisoned")
+                            .push(Request {
+                                method,
+                                uri,
+                                headers,
+                                bytes,
+                                body,
+                            });
+
+                        let response = responses
+                            .lock()
+                            .expect("lock to not be poisoned")
+                            .pop_front()
+                            .unwrap_or_else(|| Response::error(404));
+
+                        Ok::<_, Infallible>(
+                            hyper::Response::builder()
+                                .status(response.status)
+                                .header(CONTENT_TYPE, response.content_type)
+                                .body(Body::from(response.body))
+                                .expect("response to be valid"),
+                        )
+                    }
+                }))
+            }
+        });
+
+        let server = HyperServer::bind(&([127, 0, 0, 1], 0).into()).serve(make_svc);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        Self { addr, requests }
+    }
+
+    /// returns the endpoint of the server
+    pub fn endpoint(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// returns a client targeting the server
+    pub fn client(&self) -> Client<HttpConnector> {
+        Client::new(HttpConnector::new(), self.endpoint(), None)
+    }
+
+    /// returns the requests received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests
+            .lock()
+            .expect("lock to not be poisoned")
+            .to_owned()
+    }
+
+    /// returns the uris of the requests received so far
+    pub fn uris(&self) -> Vec<String> {
+        self.requests().into_iter().map(|req| req.uri).collect()
+    }
+}
@@ -0,0 +1,111 @@
+//! # Server-sent events module
+//!
+//! This module provides a reader of server-sent events shared by the helpers
+//! following a stream of the api, see
+//! <https://html.spec.whatwg.org/multipage/server-sent-events.html>
+
+use hyper::{body::HttpBody, Body};
+
+// -----------------------------------------------------------------------------
+// Event structure
+
+/// Event structure is a server-sent event, lines of data are joined by a
+/// newline
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub(crate) struct Event {
+    pub name: Option<String>,
+    pub data: Option<String>,
+}
+
+impl From<&[u8]> for Event {
+    fn from(buf: &[u8]) -> Self {
+        let block = String::from_utf8_lossy(buf);
+        let mut name = None;
+        let mut data = vec![];
+
+        for line in block.lines() {
+            if let Some(value) = line.strip_prefix("event:") {
+                name = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                data.push(value.trim_start());
+            }
+        }
+
+        Self {
+            name,
+            data: (!data.is_empty()).then(|| data.join("\n")),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Reader structure
+
+/// Reader structure splits the body of a response into server-sent events
+pub(crate) struct Reader {
+    body: Body,
+    buf: Vec<u8>,
+}
+
+impl From<Body> for Reader {
+    fn from(body: Body) -> Self {
+        Self { body, buf: vec![] }
+    }
+}
+
+impl Reader {
+    /// returns the next event of the body, or `None` once the body ends
+    pub async fn next(&mut self) -> Option<Result<Event, hyper::Error>> {
+        loop {
+            if let Some(idx) = self.buf.windows(2).position(|w| w == b"\n\n") {
+                let block: Vec<u8> = self.buf.drain(..idx + 2).collect();
+                return Some(Ok(Event::from(block.as_slice())));
+            }
+
+            match self.body.data().await? {
+                Ok(chunk) => self.buf.extend(chunk.iter().filter(|b| **b != b'\r')),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_event_with_name_and_data() {
+        let event = Event::from(&b"event: END_OF_STREAM\ndata: {\"a\":1}\ndata: {}\n\n"[..]);
+
+        assert_eq!(event.name.as_deref(), Some("END_OF_STREAM"));
+        assert_eq!(event.data.as_deref(), Some("{\"a\":1}\n{}"));
+        assert_eq!(Event::from(&b": keep-alive\n\n"[..]), Event::default());
+    }
+
+    #[tokio::test]
+    async fn read_events_split_across_chunks() {
+        let (mut sender, body) = Body::channel();
+        let mut reader = Reader::from(body);
+
+        tokio::spawn(async move {
+            for chunk in ["data: 1\r\n", "\r\ndata: 2\n\ndata:", " 3\n\n"] {
+                sender
+                    .send_data(chunk.into())
+                    .await
+                    .expect("chunk to be sent");
+            }
+        });
+
+        for data in ["1", "2", "3"] {
+            let event = reader
+                .next()
+                .await
+                .expect("event")
+                .expect("body to be read");
+            assert_eq!(event.data.as_deref(), Some(data));
+        }
+
+        assert!(reader.next().await.is_none());
+    }
+}
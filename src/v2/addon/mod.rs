@@ -0,0 +1,1688 @@
+//! # Addon module
+//!
+//! This module expose structures and helpers to interact with the addon api
+//! version 2
+
+use std::time::Duration;
+#[cfg(feature = "tokio")]
+use std::time::Instant;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use hyper::{client::connect::Connect, StatusCode};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::{application::Instance, owner::OwnerId},
+    v4::addon_provider::{
+        config_provider::addon::environment::Variable, postgresql::migrations::MigrationStatus,
+        AddonProvider, AddonProviderId, VersionError,
+    },
+    Client,
+};
+
+pub mod connection;
+
+// -----------------------------------------------------------------------------
+// Provider structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Clone, Debug)]
+pub struct Provider {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "website")]
+    pub website: String,
+    #[serde(rename = "supportEmail")]
+    pub support_email: String,
+    #[serde(rename = "googlePlusName")]
+    pub google_plus_name: String,
+    #[serde(rename = "twitterName")]
+    pub twitter_name: String,
+    #[serde(rename = "analyticsId")]
+    pub analytics_id: String,
+    #[serde(rename = "shortDesc")]
+    pub short_description: String,
+    #[serde(rename = "longDesc")]
+    pub long_description: String,
+    #[serde(rename = "logoUrl")]
+    pub logo_url: String,
+    #[serde(rename = "status")]
+    pub status: String,
+    #[serde(rename = "openInNewTab")]
+    pub open_in_new_tab: bool,
+    #[serde(rename = "canUpgrade")]
+    pub can_upgrade: bool,
+    #[serde(rename = "regions")]
+    pub regions: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Feature structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Clone, Debug)]
+pub struct Feature {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(rename = "value")]
+    pub value: String,
+    #[serde(rename = "computable_value")]
+    pub computable_value: Option<String>,
+    #[serde(rename = "name_code")]
+    pub name_code: Option<String>,
+}
+
+impl Feature {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the kind of the feature
+    pub fn typed_kind(&self) -> FeatureKind {
+        FeatureKind::from(self.kind.to_owned())
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the value of the feature interpreted according to its kind, a
+    /// value that could not be interpreted is returned as text
+    pub fn typed_value(&self) -> FeatureValue {
+        let value = self.value.trim();
+        let typed = match self.typed_kind() {
+            FeatureKind::Boolean => parse_bool(value).map(FeatureValue::Bool),
+            FeatureKind::FileSize | FeatureKind::Bytes => {
+                parse_size(value).map(FeatureValue::Bytes)
+            }
+            FeatureKind::Number | FeatureKind::Interval => {
+                value.parse().ok().map(FeatureValue::Number)
+            }
+            FeatureKind::Object | FeatureKind::Other(_) => None,
+        };
+
+        typed.unwrap_or_else(|| FeatureValue::Text(self.value.to_owned()))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// FeatureKind enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum FeatureKind {
+    Boolean,
+    Interval,
+    FileSize,
+    Number,
+    Bytes,
+    Object,
+    Other(String),
+}
+
+impl From<String> for FeatureKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "BOOLEAN" => Self::Boolean,
+            "INTERVAL" => Self::Interval,
+            "FILESIZE" => Self::FileSize,
+            "NUMBER" => Self::Number,
+            "BYTES" => Self::Bytes,
+            "OBJECT" => Self::Object,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for FeatureKind {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for FeatureKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Boolean => write!(f, "BOOLEAN"),
+            Self::Interval => write!(f, "INTERVAL"),
+            Self::FileSize => write!(f, "FILESIZE"),
+            Self::Number => write!(f, "NUMBER"),
+            Self::Bytes => write!(f, "BYTES"),
+            Self::Object => write!(f, "OBJECT"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// FeatureValue enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Clone, Debug)]
+#[serde(untagged)]
+pub enum FeatureValue {
+    Bool(bool),
+    Bytes(u64),
+    Number(f64),
+    Text(String),
+}
+
+/// returns the boolean written as 'yes'/'no', 'true'/'false', 'on'/'off' or
+/// '1'/'0'
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.to_lowercase().as_str() {
+        "yes" | "true" | "on" | "1" => Some(true),
+        "no" | "false" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// returns the number of bytes of a size written like '40 GB', '512MiB' or
+/// '1.5 TB', decimal units are powers of 1000 and binary ones powers of 1024
+fn parse_size(s: &str) -> Option<u64> {
+    let idx = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+
+    let (number, unit) = s.split_at(idx);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1000,
+        "m" | "mb" => 1000_u64.pow(2),
+        "g" | "gb" => 1000_u64.pow(3),
+        "t" | "tb" => 1000_u64.pow(4),
+        "kib" => 1024,
+        "mib" => 1024_u64.pow(2),
+        "gib" => 1024_u64.pow(3),
+        "tib" => 1024_u64.pow(4),
+        _ => return None,
+    };
+
+    Some((number * multiplier as f64).round() as u64)
+}
+
+// -----------------------------------------------------------------------------
+// Plan structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Clone, Debug)]
+pub struct Plan {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "slug")]
+    pub slug: String,
+    #[serde(rename = "price")]
+    pub price: f32,
+    #[serde(rename = "price_id")]
+    pub price_id: String,
+    #[serde(rename = "features")]
+    pub features: Vec<Feature>,
+    #[serde(rename = "zones")]
+    pub zones: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Addon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Clone, Debug)]
+pub struct Addon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "realId")]
+    pub real_id: String,
+    #[serde(rename = "region")]
+    pub region: String,
+    #[serde(rename = "provider")]
+    pub provider: Provider,
+    #[serde(rename = "plan")]
+    pub plan: Plan,
+    #[serde(rename = "creationDate")]
+    pub creation_date: u64,
+    #[serde(rename = "configKeys")]
+    pub config_keys: Vec<String>,
+}
+
+impl Addon {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the provider of the addon deduced from its real identifier
+    pub fn provider_id(&self) -> Option<AddonProviderId> {
+        AddonProviderId::from_real_id(&self.real_id)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the creation date of the addon, the api gives it in milliseconds
+    /// since the unix epoch which is returned if the timestamp is out of range
+    pub fn created_at_datetime(&self) -> DateTime<Utc> {
+        i64::try_from(self.creation_date)
+            .ok()
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+            .unwrap_or_default()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the time elapsed since the creation of the addon
+    pub fn age(&self) -> Duration {
+        (Utc::now() - self.created_at_datetime())
+            .to_std()
+            .unwrap_or_default()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the addon was created more than `threshold` ago
+    pub fn is_older_than(&self, threshold: Duration) -> bool {
+        self.age() > threshold
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Opts enum
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct Opts {
+    #[serde(rename = "version", skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(rename = "encryption", skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<String>,
+    #[serde(rename = "services", skip_serializing_if = "Option::is_none")]
+    pub services: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// CreateOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Clone, Debug)]
+pub struct CreateOpts {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "region")]
+    pub region: String,
+    #[serde(rename = "providerId")]
+    pub provider_id: String,
+    #[serde(rename = "plan")]
+    pub plan: String,
+    #[serde(rename = "options")]
+    pub options: Opts,
+}
+
+impl CreateOpts {
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(provider, requested)))]
+    /// returns the options with the requested version of the addon provider,
+    /// or its default one if none is requested, an unavailable version is
+    /// rejected
+    pub fn with_version<T>(
+        mut self,
+        provider: &AddonProvider<T>,
+        requested: Option<T>,
+    ) -> Result<Self, VersionError>
+    where
+        T: Ord + Clone + Display,
+    {
+        self.options.version = Some(provider.resolve(requested)?.to_string());
+        Ok(self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// UpdateOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct UpdateOpts {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// MigrationOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct MigrationOpts {
+    #[serde(rename = "planId")]
+    pub plan_id: String,
+}
+
+// -----------------------------------------------------------------------------
+// Migration structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Migration {
+    #[serde(rename = "migrationId")]
+    pub id: String,
+    #[serde(rename = "status")]
+    pub status: MigrationStatus,
+}
+
+// -----------------------------------------------------------------------------
+// PollOpts structure
+
+/// PollOpts structure configures how to wait for the provisioning of an addon
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PollOpts {
+    pub interval: Duration,
+    pub timeout: Duration,
+    pub required_keys: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumerations
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list addons of organisation '{0}', {1}")]
+    List(String, ClientError),
+    #[error("failed to list addons of '{0}', {1}")]
+    ListByOwner(OwnerId, ClientError),
+    #[error("failed to list addons of application '{0}' of '{1}', {2}")]
+    ListForApp(String, OwnerId, ClientError),
+    #[error("failed to get addon '{0}' of organisation '{1}', {2}")]
+    Get(String, String, ClientError),
+    #[error("failed to get addon '{0}' of '{1}', {2}")]
+    GetByOwner(String, OwnerId, ClientError),
+    #[error("failed to get addon '{0}' environment of organisation '{1}', {2}")]
+    Environment(String, String, ClientError),
+    #[error("failed to get addon '{0}' environment of '{1}', {2}")]
+    EnvironmentByOwner(String, OwnerId, ClientError),
+    #[error("failed to create addon for organisation '{0}', {1}")]
+    Create(String, ClientError),
+    #[error("failed to create addon for '{0}', {1}")]
+    CreateByOwner(OwnerId, ClientError),
+    #[error("failed to update addon '{0}' of '{1}', {2}")]
+    Update(String, OwnerId, ClientError),
+    #[error("failed to migrate addon '{0}' of '{1}' to plan '{2}', {3}")]
+    MigratePlan(String, OwnerId, String, ClientError),
+    #[error("failed to get migration '{0}' of addon '{1}' of '{2}', {3}")]
+    MigrationStatus(String, String, OwnerId, ClientError),
+    #[error("failed to wait for migration '{0}' of addon '{1}', still '{2}' after {3}s")]
+    MigrationTimeout(String, String, MigrationStatus, u64),
+    #[error("failed to list instances of addon '{0}' of '{1}', {2}")]
+    Instances(String, OwnerId, ClientError),
+    #[error(
+        "failed to wait for addon '{0}' to be provisioned, keys {1:?} are still missing after {2}s"
+    )]
+    NotReady(String, Vec<String>, u64),
+    #[error("failed to delete addon '{0}' for organisation '{1}', {2}")]
+    Delete(String, String, ClientError),
+    #[error("failed to delete addon '{0}' for '{1}', {2}")]
+    DeleteByOwner(String, OwnerId, ClientError),
+    #[error("failed to link addon '{0}' to application '{1}' of '{2}', {3}")]
+    LinkToApp(String, String, OwnerId, ClientError),
+    #[error("failed to unlink addon '{0}' from application '{1}' of '{2}', {3}")]
+    UnlinkFromApp(String, String, OwnerId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of addons for the given organisation
+pub async fn list<C>(client: &Client<C>, organisation_id: &str) -> Result<Vec<Addon>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/addons",
+        client.endpoint, organisation_id,
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get the list of addons, path: '{}', organisation: '{}'",
+            &path, organisation_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of addons of the given owner
+pub async fn list_by_owner<C>(client: &Client<C>, owner: &OwnerId) -> Result<Vec<Addon>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/addons", client.endpoint, owner.path());
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get the list of addons, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::ListByOwner(owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of addons linked to the given application
+pub async fn list_for_app<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<Vec<Addon>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/addons",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get the list of addons linked to an application, path: '{}', owner: '{}', application: '{}'",
+            &path, owner, application_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::ListForApp(application_id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the addon for the given the organisation and identifier
+pub async fn get<C>(client: &Client<C>, organisation_id: &str, id: &str) -> Result<Addon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/addons/{}",
+        client.endpoint, organisation_id, id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to get information about an addon, path: '{}', organisation: '{}', id: '{}'", &path, organisation_id, id);
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_owned(), organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the addon of the given owner and identifier
+pub async fn get_by_owner<C>(client: &Client<C>, owner: &OwnerId, id: &str) -> Result<Addon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/addons/{}", client.endpoint, owner.path(), id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to get information about an addon, path: '{}', owner: '{}', id: '{}'", &path, owner, id);
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::GetByOwner(id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// create the addon and returns it
+pub async fn create<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    opts: &CreateOpts,
+) -> Result<Addon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/addons",
+        client.endpoint, organisation_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to create an addon, path: '{}', organisation: '{}', name: '{}', region: '{}', plan: '{}', provider-id: '{}'", &path, organisation_id, &opts.name, &opts.region, &opts.plan, &opts.provider_id.to_string());
+    }
+
+    client
+        .post(&path, opts)
+        .await
+        .map_err(|err| Error::Create(organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// create the addon on the given owner and returns it
+pub async fn create_by_owner<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    opts: &CreateOpts,
+) -> Result<Addon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/addons", client.endpoint, owner.path());
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to create an addon, path: '{}', owner: '{}', name: '{}', region: '{}', plan: '{}', provider-id: '{}'", &path, owner, &opts.name, &opts.region, &opts.plan, &opts.provider_id.to_string());
+    }
+
+    client
+        .post(&path, opts)
+        .await
+        .map_err(|err| Error::CreateByOwner(owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// update the given addon and returns it
+pub async fn update<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+    opts: &UpdateOpts,
+) -> Result<Addon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/addons/{}", client.endpoint, owner.path(), id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to update an addon, path: '{}', owner: '{}', id: '{}'",
+            &path, owner, id
+        );
+    }
+
+    client
+        .put(&path, opts)
+        .await
+        .map_err(|err| Error::Update(id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// delete the given addon
+pub async fn delete<C>(client: &Client<C>, organisation_id: &str, id: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/addons/{}",
+        client.endpoint, organisation_id, id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to delete an addon, path: '{}', organisation: '{}', id: '{}'",
+            &path, organisation_id, id
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::Delete(id.to_owned(), organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// delete the given addon of the owner
+pub async fn delete_by_owner<C>(client: &Client<C>, owner: &OwnerId, id: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/addons/{}", client.endpoint, owner.path(), id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to delete an addon, path: '{}', owner: '{}', id: '{}'",
+            &path, owner, id
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::DeleteByOwner(id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// link the given addon to the application, its environment is then exposed
+/// to the application
+pub async fn link_to_app<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+    id: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/addons",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to link an addon to an application, path: '{}', owner: '{}', application: '{}', id: '{}'",
+            &path, owner, application_id, id
+        );
+    }
+
+    // the api expects the addon identifier as a json string and answers with
+    // a message which is of no use here
+    client
+        .post::<_, serde_json::Value>(&path, &id)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            Error::LinkToApp(
+                id.to_owned(),
+                application_id.to_owned(),
+                owner.to_owned(),
+                err,
+            )
+        })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// unlink the given addon from the application
+pub async fn unlink_from_app<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+    id: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/addons/{}",
+        client.endpoint,
+        owner.path(),
+        application_id,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to unlink an addon from an application, path: '{}', owner: '{}', application: '{}', id: '{}'",
+            &path, owner, application_id, id
+        );
+    }
+
+    client.delete(&path).await.map_err(|err| {
+        Error::UnlinkFromApp(
+            id.to_owned(),
+            application_id.to_owned(),
+            owner.to_owned(),
+            err,
+        )
+    })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns environment variables for an addon in the order given by the api
+pub async fn environment_variables<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+) -> Result<Vec<Variable>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/addons/{}/env", client.endpoint, owner.path(), id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get secret of a addon, path: '{}', owner: '{}', id: '{}'",
+            &path, owner, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::EnvironmentByOwner(id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns environment variables for an addon
+pub async fn environment<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    id: &str,
+) -> Result<BTreeMap<String, String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/addons/{}/env",
+        client.endpoint, organisation_id, id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get secret of a addon, path: '{}', organisation: '{}', id: '{}'",
+            &path, organisation_id, id
+        );
+    }
+
+    let env: Vec<Variable> = client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Environment(id.to_owned(), organisation_id.to_owned(), err))?;
+
+    Ok(env.iter().fold(BTreeMap::new(), |mut acc, var| {
+        acc.insert(var.name.to_owned(), var.value.to_owned());
+        acc
+    }))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns environment variables for an addon of the given owner
+pub async fn environment_by_owner<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+) -> Result<BTreeMap<String, String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let env = environment_variables(client, owner, id).await?;
+
+    Ok(env.iter().fold(BTreeMap::new(), |mut acc, var| {
+        acc.insert(var.name.to_owned(), var.value.to_owned());
+        acc
+    }))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// migrate the given addon to another plan and returns the started migration
+pub async fn migrate_plan<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+    plan_id: &str,
+) -> Result<Migration, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/addons/{}/migrations",
+        client.endpoint,
+        owner.path(),
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to migrate the plan of an addon, path: '{}', owner: '{}', id: '{}', plan: '{}'",
+            &path, owner, id, plan_id
+        );
+    }
+
+    let opts = MigrationOpts {
+        plan_id: plan_id.to_owned(),
+    };
+
+    client
+        .post(&path, &opts)
+        .await
+        .map_err(|err| Error::MigratePlan(id.to_owned(), owner.to_owned(), plan_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the migration of the given addon
+pub async fn migration_status<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+    migration_id: &str,
+) -> Result<Migration, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/addons/{}/migrations/{}",
+        client.endpoint,
+        owner.path(),
+        id,
+        migration_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get the migration of an addon, path: '{}', owner: '{}', id: '{}', migration: '{}'",
+            &path, owner, id, migration_id
+        );
+    }
+
+    client.get(&path).await.map_err(|err| {
+        Error::MigrationStatus(
+            migration_id.to_owned(),
+            id.to_owned(),
+            owner.to_owned(),
+            err,
+        )
+    })
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// poll the migration of the given addon every `interval` until it reaches a
+/// terminal status and returns it, gives up after `timeout`
+pub async fn wait_for_migration<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+    migration_id: &str,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<Migration, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let start = Instant::now();
+    loop {
+        let migration = migration_status(client, owner, id, migration_id).await?;
+        if migration.status.is_terminal() {
+            return Ok(migration);
+        }
+
+        if start.elapsed() + interval > timeout {
+            return Err(Error::MigrationTimeout(
+                migration_id.to_owned(),
+                id.to_owned(),
+                migration.status,
+                timeout.as_secs(),
+            ));
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the instances running the given addon, addons which are not backed
+/// by instances have none
+pub async fn instances<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+) -> Result<Vec<Instance>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/addons/{}/instances",
+        client.endpoint,
+        owner.path(),
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list instances of an addon, path: '{}', owner: '{}', id: '{}'",
+            &path, owner, id
+        );
+    }
+
+    match client.get(&path).await {
+        Ok(instances) => Ok(instances),
+        Err(ClientError::StatusCode(code, _)) if StatusCode::NOT_FOUND == code => Ok(vec![]),
+        Err(err) => Err(Error::Instances(id.to_owned(), owner.to_owned(), err)),
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// poll the environment of the given addon every `interval` until all required
+/// keys are present and returns it, gives up after `timeout`
+pub async fn wait_ready<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+    opts: PollOpts,
+) -> Result<BTreeMap<String, String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let start = Instant::now();
+    loop {
+        let env = environment_by_owner(client, owner, id).await?;
+        let missing: Vec<String> = opts
+            .required_keys
+            .iter()
+            .filter(|key| !env.contains_key(*key))
+            .cloned()
+            .collect();
+
+        if missing.is_empty() {
+            return Ok(env);
+        }
+
+        if start.elapsed() + opts.interval > opts.timeout {
+            return Err(Error::NotReady(
+                id.to_owned(),
+                missing,
+                opts.timeout.as_secs(),
+            ));
+        }
+
+        tokio::time::sleep(opts.interval).await;
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{
+        mock::{Response, Server},
+        v4::addon_provider::{postgresql, tests::provider_fixture},
+    };
+
+    pub(crate) const ADDON: &str = r#"{
+        "id": "addon_6b3fc0ab-7b6f-4b8e-9a2f-1b6e4c1f2d3e",
+        "name": "my-database",
+        "realId": "postgresql_4c1e2d3f-5a6b-4c7d-8e9f-0a1b2c3d4e5f",
+        "region": "par",
+        "provider": {
+            "id": "postgresql-addon",
+            "name": "PostgreSQL",
+            "website": "https://www.clever-cloud.com",
+            "supportEmail": "support@clever-cloud.com",
+            "googlePlusName": "",
+            "twitterName": "",
+            "analyticsId": "",
+            "shortDesc": "PostgreSQL database",
+            "longDesc": "PostgreSQL database",
+            "logoUrl": "https://assets.clever-cloud.com/logos/pgsql.svg",
+            "status": "RELEASE",
+            "openInNewTab": false,
+            "canUpgrade": true,
+            "regions": ["par", "mtl"]
+        },
+        "plan": {
+            "id": "plan_1a2b3c4d",
+            "name": "XS Small Space",
+            "slug": "xs_sml",
+            "price": 5.25,
+            "price_id": "postgresql_xs_sml",
+            "features": [
+                { "name": "Max DB size", "type": "FILESIZE", "value": "1 GB", "computable_value": null, "name_code": "max-db-size" },
+                { "name": "Backups", "type": "BOOLEAN", "value": "yes", "computable_value": null, "name_code": null }
+            ],
+            "zones": ["par", "mtl"]
+        },
+        "creationDate": 1700000000123,
+        "configKeys": ["POSTGRESQL_ADDON_HOST", "POSTGRESQL_ADDON_PASSWORD"]
+    }"#;
+
+    fn create_opts() -> CreateOpts {
+        CreateOpts {
+            name: "my-database".to_string(),
+            region: "par".to_string(),
+            provider_id: "postgresql-addon".to_string(),
+            plan: "plan_1a2b3c4d".to_string(),
+            options: Opts::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn organisation_helpers_target_organisation_paths() {
+        let server = Server::start(vec![
+            Response::json(200, format!("[{ADDON}]")),
+            Response::json(200, ADDON),
+            Response::json(200, ADDON),
+            Response::json(200, r#"[{ "name": "FOO", "value": "bar" }]"#),
+            Response::json(204, ""),
+        ])
+        .await;
+        let client = server.client();
+
+        assert_eq!(list(&client, "orga_1").await.expect("list").len(), 1);
+        get(&client, "orga_1", "addon_1").await.expect("get");
+        create(&client, "orga_1", &create_opts())
+            .await
+            .expect("create");
+        let env = environment(&client, "orga_1", "addon_1")
+            .await
+            .expect("environment");
+        assert_eq!(env.get("FOO").map(String::as_str), Some("bar"));
+        delete(&client, "orga_1", "addon_1").await.expect("delete");
+
+        let requests = server.requests();
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/organisations/orga_1/addons",
+                "/v2/organisations/orga_1/addons/addon_1",
+                "/v2/organisations/orga_1/addons",
+                "/v2/organisations/orga_1/addons/addon_1/env",
+                "/v2/organisations/orga_1/addons/addon_1",
+            ]
+        );
+        assert_eq!(requests[2].method, "POST");
+        assert_eq!(requests[2].json()["providerId"], "postgresql-addon");
+        assert_eq!(requests[4].method, "DELETE");
+    }
+
+    #[tokio::test]
+    async fn organisation_helpers_keep_organisation_errors() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+
+        match list(&client, "orga_1").await {
+            Err(Error::List(organisation_id, ClientError::StatusCode(code, _))) => {
+                assert_eq!(organisation_id, "orga_1");
+                assert_eq!(code, StatusCode::NOT_FOUND);
+            }
+            res => panic!("expected a list error, got {res:?}"),
+        }
+
+        match get(&client, "orga_1", "addon_1").await {
+            Err(Error::Get(id, organisation_id, _)) => {
+                assert_eq!(id, "addon_1");
+                assert_eq!(organisation_id, "orga_1");
+            }
+            res => panic!("expected a get error, got {res:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn owner_helpers_target_the_personal_space() {
+        let server = Server::start(vec![
+            Response::json(200, format!("[{ADDON}]")),
+            Response::json(200, ADDON),
+            Response::json(200, ADDON),
+            Response::json(200, r#"[{ "name": "FOO", "value": "bar" }]"#),
+            Response::json(204, ""),
+        ])
+        .await;
+        let client = server.client();
+        let owner = OwnerId::Myself;
+
+        list_by_owner(&client, &owner).await.expect("list");
+        get_by_owner(&client, &owner, "addon_1").await.expect("get");
+        create_by_owner(&client, &owner, &create_opts())
+            .await
+            .expect("create");
+        environment_by_owner(&client, &owner, "addon_1")
+            .await
+            .expect("environment");
+        delete_by_owner(&client, &owner, "addon_1")
+            .await
+            .expect("delete");
+
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/self/addons",
+                "/v2/self/addons/addon_1",
+                "/v2/self/addons",
+                "/v2/self/addons/addon_1/env",
+                "/v2/self/addons/addon_1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn owner_helpers_target_organisation_paths() {
+        let server = Server::start(vec![Response::json(200, "[]")]).await;
+        let client = server.client();
+
+        list_by_owner(&client, &OwnerId::from("orga_1"))
+            .await
+            .expect("list");
+
+        assert_eq!(server.uris(), vec!["/v2/organisations/orga_1/addons"]);
+    }
+
+    /// returns a feature of the given kind and value
+    fn feature(kind: &str, value: &str) -> Feature {
+        Feature {
+            name: "feature".to_string(),
+            kind: kind.to_string(),
+            value: value.to_string(),
+            computable_value: None,
+            name_code: None,
+        }
+    }
+
+    #[test]
+    fn parse_sizes() {
+        for (size, bytes) in [
+            ("0", 0),
+            ("100", 100),
+            ("100 B", 100),
+            ("2k", 2_000),
+            ("2 KiB", 2_048),
+            ("40 GB", 40_000_000_000),
+            ("40GB", 40_000_000_000),
+            ("40 gb", 40_000_000_000),
+            ("1.5 TB", 1_500_000_000_000),
+            ("1.5gb", 1_500_000_000),
+            ("512MiB", 536_870_912),
+            ("512 MB", 512_000_000),
+            ("1 TiB", 1_099_511_627_776),
+            ("0.5 GiB", 536_870_912),
+        ] {
+            assert_eq!(parse_size(size), Some(bytes), "size '{size}'");
+        }
+
+        for size in ["", "GB", "-1 GB", "12 parsecs", "1.2.3 GB", "40 GB free"] {
+            assert_eq!(parse_size(size), None, "size '{size}'");
+        }
+    }
+
+    #[test]
+    fn parse_booleans() {
+        for value in ["yes", "Yes", "TRUE", "on", "1"] {
+            assert_eq!(parse_bool(value), Some(true), "value '{value}'");
+        }
+
+        for value in ["no", "False", "OFF", "0"] {
+            assert_eq!(parse_bool(value), Some(false), "value '{value}'");
+        }
+
+        assert_eq!(parse_bool("maybe"), None);
+    }
+
+    #[test]
+    fn typed_values_follow_the_kind_of_the_feature() {
+        assert_eq!(
+            feature("BOOLEAN", " Yes ").typed_value(),
+            FeatureValue::Bool(true)
+        );
+        assert_eq!(
+            feature("FILESIZE", "40 GB").typed_value(),
+            FeatureValue::Bytes(40_000_000_000)
+        );
+        assert_eq!(
+            feature("BYTES", "512MiB").typed_value(),
+            FeatureValue::Bytes(536_870_912)
+        );
+        assert_eq!(
+            feature("NUMBER", "2.5").typed_value(),
+            FeatureValue::Number(2.5)
+        );
+        assert_eq!(
+            feature("INTERVAL", "30").typed_value(),
+            FeatureValue::Number(30.0)
+        );
+        assert_eq!(
+            feature("OBJECT", "{}").typed_value(),
+            FeatureValue::Text("{}".to_string())
+        );
+        assert_eq!(
+            feature("FILESIZE", "unlimited").typed_value(),
+            FeatureValue::Text("unlimited".to_string())
+        );
+        assert_eq!(
+            feature("COLOR", "blue").typed_value(),
+            FeatureValue::Text("blue".to_string())
+        );
+    }
+
+    #[test]
+    fn feature_kinds_round_trip() {
+        for kind in [
+            "BOOLEAN", "INTERVAL", "FILESIZE", "NUMBER", "BYTES", "OBJECT", "COLOR",
+        ] {
+            let typed = feature(kind, "").typed_kind();
+            assert_eq!(typed.to_string(), kind);
+            assert_eq!(FeatureKind::from(kind.to_string()), typed);
+        }
+
+        assert_eq!(
+            feature("COLOR", "").typed_kind(),
+            FeatureKind::Other("COLOR".to_string())
+        );
+    }
+
+    #[test]
+    fn with_version_sets_the_resolved_version() {
+        let provider: AddonProvider<postgresql::Version> =
+            serde_json::from_str(&provider_fixture("postgresql-addon", &["15", "14", "13"]))
+                .expect("provider to deserialize");
+
+        let opts = create_opts().with_version(&provider, None).expect("opts");
+        assert_eq!(opts.options.version.as_deref(), Some("15"));
+
+        let opts = create_opts()
+            .with_version(&provider, Some(postgresql::Version::V13))
+            .expect("opts");
+        assert_eq!(opts.options.version.as_deref(), Some("13"));
+
+        assert!(create_opts()
+            .with_version(&provider, Some(postgresql::Version::V17))
+            .is_err());
+    }
+
+    #[test]
+    fn created_at_datetime_converts_the_timestamp() {
+        let mut addon: Addon = serde_json::from_str(ADDON).expect("addon to deserialize");
+
+        assert_eq!(
+            addon.created_at_datetime().to_rfc3339(),
+            "2023-11-14T22:13:20.123+00:00"
+        );
+
+        addon.creation_date = u64::MAX;
+        assert_eq!(addon.created_at_datetime(), DateTime::<Utc>::default());
+    }
+
+    #[test]
+    fn age_is_measured_from_the_creation_date() {
+        let mut addon: Addon = serde_json::from_str(ADDON).expect("addon to deserialize");
+        let elapsed = (Utc::now()
+            - Utc
+                .timestamp_millis_opt(1_700_000_000_123)
+                .single()
+                .expect("date"))
+        .to_std()
+        .expect("elapsed time");
+
+        assert!(addon.age() >= elapsed);
+        assert!(addon.age() < elapsed + Duration::from_secs(60));
+
+        addon.creation_date = Utc::now().timestamp_millis() as u64 + 3_600_000;
+        assert_eq!(addon.age(), Duration::ZERO);
+    }
+
+    #[test]
+    fn is_older_than_compares_the_age_to_the_threshold() {
+        let mut addon: Addon = serde_json::from_str(ADDON).expect("addon to deserialize");
+        let created_at = Utc
+            .with_ymd_and_hms(2023, 11, 14, 22, 13, 20)
+            .single()
+            .expect("date");
+        let elapsed = (Utc::now() - created_at).to_std().expect("elapsed time");
+
+        assert!(addon.is_older_than(Duration::from_secs(24 * 3600)));
+        assert!(addon.is_older_than(elapsed - Duration::from_secs(60)));
+        assert!(!addon.is_older_than(elapsed + Duration::from_secs(60)));
+
+        addon.creation_date = Utc::now().timestamp_millis() as u64 - 60_000;
+        assert!(addon.is_older_than(Duration::from_secs(30)));
+        assert!(!addon.is_older_than(Duration::from_secs(3600)));
+    }
+
+    #[tokio::test]
+    async fn update_sends_the_new_name_and_returns_the_addon() {
+        let server = Server::start(vec![
+            Response::json(200, ADDON.replace("my-database", "my-clone")),
+            Response::json(200, ADDON),
+        ])
+        .await;
+        let client = server.client();
+        let opts = UpdateOpts {
+            name: Some("my-clone".to_string()),
+        };
+
+        let addon = update(&client, &OwnerId::from("orga_1"), "addon_1", &opts)
+            .await
+            .expect("addon");
+        assert_eq!(addon.name.as_deref(), Some("my-clone"));
+
+        update(&client, &OwnerId::Myself, "addon_1", &UpdateOpts::default())
+            .await
+            .expect("addon");
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|req| "PUT" == req.method));
+        assert_eq!(requests[0].uri, "/v2/organisations/orga_1/addons/addon_1");
+        assert_eq!(
+            requests[0].json(),
+            serde_json::json!({ "name": "my-clone" })
+        );
+        assert_eq!(requests[1].uri, "/v2/self/addons/addon_1");
+        assert_eq!(requests[1].json(), serde_json::json!({}));
+        assert!(matches!(
+            update(&client, &OwnerId::Myself, "addon_1", &opts).await,
+            Err(Error::Update(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn environment_is_exposed_as_ordered_list_and_as_map() {
+        const ENV: &str = r#"[
+            { "name": "POSTGRESQL_ADDON_USER", "value": "user_1" },
+            { "name": "POSTGRESQL_ADDON_HOST", "value": "host_1" },
+            { "name": "POSTGRESQL_ADDON_DB", "value": "db_1" }
+        ]"#;
+
+        let server = Server::start(vec![Response::json(200, ENV), Response::json(200, ENV)]).await;
+        let client = server.client();
+        let owner = OwnerId::from("orga_1");
+
+        let variables = environment_variables(&client, &owner, "addon_1")
+            .await
+            .expect("variables");
+        assert_eq!(
+            variables
+                .iter()
+                .map(|var| (var.name.as_str(), var.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("POSTGRESQL_ADDON_USER", "user_1"),
+                ("POSTGRESQL_ADDON_HOST", "host_1"),
+                ("POSTGRESQL_ADDON_DB", "db_1"),
+            ]
+        );
+
+        let env = environment_by_owner(&client, &owner, "addon_1")
+            .await
+            .expect("environment");
+        assert_eq!(
+            env,
+            variables
+                .into_iter()
+                .map(|var| (var.name, var.value))
+                .collect::<BTreeMap<_, _>>()
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/organisations/orga_1/addons/addon_1/env"; 2]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_ready_polls_until_the_required_keys_are_present() {
+        let server = Server::start(vec![
+            Response::json(200, "[]"),
+            Response::json(
+                200,
+                r#"[{ "name": "POSTGRESQL_ADDON_USER", "value": "user_1" }]"#,
+            ),
+            Response::json(
+                200,
+                r#"[
+                    { "name": "POSTGRESQL_ADDON_USER", "value": "user_1" },
+                    { "name": "POSTGRESQL_ADDON_HOST", "value": "host_1" }
+                ]"#,
+            ),
+        ])
+        .await;
+        let client = server.client();
+        let opts = PollOpts {
+            interval: Duration::from_millis(1),
+            timeout: Duration::from_secs(5),
+            required_keys: vec![
+                "POSTGRESQL_ADDON_HOST".to_string(),
+                "POSTGRESQL_ADDON_USER".to_string(),
+            ],
+        };
+
+        let env = wait_ready(&client, &OwnerId::from("orga_1"), "addon_1", opts)
+            .await
+            .expect("environment");
+
+        assert_eq!(
+            env.get("POSTGRESQL_ADDON_HOST").map(String::as_str),
+            Some("host_1")
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/organisations/orga_1/addons/addon_1/env"; 3]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_ready_reports_the_missing_keys_on_timeout() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"[{ "name": "POSTGRESQL_ADDON_USER", "value": "user_1" }]"#,
+        )])
+        .await;
+        let client = server.client();
+        let opts = PollOpts {
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_millis(10),
+            required_keys: vec![
+                "POSTGRESQL_ADDON_HOST".to_string(),
+                "POSTGRESQL_ADDON_USER".to_string(),
+            ],
+        };
+
+        match wait_ready(&client, &OwnerId::Myself, "addon_1", opts).await {
+            Err(Error::NotReady(id, missing, _)) => {
+                assert_eq!(id, "addon_1");
+                assert_eq!(missing, vec!["POSTGRESQL_ADDON_HOST"]);
+            }
+            res => panic!("expected a timeout, got {res:?}"),
+        }
+    }
+
+    /// returns a plan migration of the addon in the given status
+    fn migration(status: &str) -> String {
+        format!(r#"{{"migrationId":"migration_1","status":"{status}"}}"#)
+    }
+
+    #[tokio::test]
+    async fn migrate_plan_posts_the_plan_and_returns_the_migration() {
+        let server = Server::start(vec![
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("DONE")),
+        ])
+        .await;
+        let client = server.client();
+        let owner = OwnerId::from("orga_1");
+
+        let migration = migrate_plan(&client, &owner, "addon_1", "plan_m_med")
+            .await
+            .expect("migration");
+        assert_eq!(migration.id, "migration_1");
+        assert_eq!(migration.status, MigrationStatus::Running);
+
+        let migration = migration_status(&client, &owner, "addon_1", "migration_1")
+            .await
+            .expect("migration");
+        assert_eq!(migration.status, MigrationStatus::Done);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(
+            requests[0].uri,
+            "/v2/organisations/orga_1/addons/addon_1/migrations"
+        );
+        assert_eq!(
+            requests[0].json(),
+            serde_json::json!({ "planId": "plan_m_med" })
+        );
+        assert_eq!(
+            requests[1].uri,
+            "/v2/organisations/orga_1/addons/addon_1/migrations/migration_1"
+        );
+        assert!(matches!(
+            migrate_plan(&client, &owner, "addon_1", "plan_m_med").await,
+            Err(Error::MigratePlan(..))
+        ));
+        assert!(matches!(
+            migration_status(&client, &owner, "addon_1", "migration_1").await,
+            Err(Error::MigrationStatus(..))
+        ));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_for_migration_resolves_once_done() {
+        let server = Server::start(vec![
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("DONE")),
+        ])
+        .await;
+        let client = server.client();
+
+        let migration = wait_for_migration(
+            &client,
+            &OwnerId::Myself,
+            "addon_1",
+            "migration_1",
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("migration");
+
+        assert_eq!(migration.status, MigrationStatus::Done);
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/self/addons/addon_1/migrations/migration_1"; 3]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_for_migration_returns_errored_migrations() {
+        let server = Server::start(vec![
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("ERROR")),
+        ])
+        .await;
+        let client = server.client();
+
+        let migration = wait_for_migration(
+            &client,
+            &OwnerId::Myself,
+            "addon_1",
+            "migration_1",
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("migration");
+
+        assert_eq!(migration.status, MigrationStatus::Error);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_for_migration_gives_up_after_the_timeout() {
+        let server = Server::start(vec![Response::json(200, migration("RUNNING"))]).await;
+        let client = server.client();
+
+        assert!(matches!(
+            wait_for_migration(
+                &client,
+                &OwnerId::Myself,
+                "addon_1",
+                "migration_1",
+                Duration::from_secs(1),
+                Duration::from_millis(10),
+            )
+            .await,
+            Err(Error::MigrationTimeout(_, _, MigrationStatus::Running, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn instances_of_runtime_backed_addons_are_listed() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"[
+                {
+                    "id": "instance_1",
+                    "appId": "app_keycloak_1",
+                    "state": "UP",
+                    "commit": "3f5a2c1",
+                    "deployNumber": 4,
+                    "instanceNumber": 0,
+                    "displayName": "Hairy Hawk",
+                    "creationDate": 1700000000123
+                }
+            ]"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let instances = instances(&client, &OwnerId::from("orga_1"), "addon_1")
+            .await
+            .expect("instances");
+
+        assert_eq!(
+            instances,
+            vec![Instance {
+                id: "instance_1".to_string(),
+                application_id: "app_keycloak_1".to_string(),
+                state: "UP".to_string(),
+                commit: Some("3f5a2c1".to_string()),
+                deploy_number: 4,
+                instance_number: 0,
+                display_name: "Hairy Hawk".to_string(),
+                creation_date: 1_700_000_000_123,
+            }]
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/organisations/orga_1/addons/addon_1/instances"]
+        );
+    }
+
+    #[tokio::test]
+    async fn addons_without_instances_have_none() {
+        let server = Server::start(vec![Response::error(404)]).await;
+        let client = server.client();
+
+        let instances = instances(&client, &OwnerId::Myself, "addon_1")
+            .await
+            .expect("instances");
+
+        assert!(instances.is_empty());
+        assert_eq!(server.uris(), vec!["/v2/self/addons/addon_1/instances"]);
+    }
+
+    #[tokio::test]
+    async fn instances_propagate_other_errors() {
+        let server = Server::start(vec![Response::error(500)]).await;
+        let client = server.client();
+
+        assert!(matches!(
+            instances(&client, &OwnerId::Myself, "addon_1").await,
+            Err(Error::Instances(id, OwnerId::Myself, ClientError::StatusCode(code, _)))
+                if id == "addon_1" && code == StatusCode::INTERNAL_SERVER_ERROR
+        ));
+    }
+}
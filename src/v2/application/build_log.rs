@@ -0,0 +1,199 @@
+//! # Build log module
+//!
+//! This module provides structures and helpers to follow the build output of a
+//! deployment of an application
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream};
+use hyper::{client::connect::Connect, header::ACCEPT, Body, Method};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request};
+use percent_encoding::utf8_percent_encode;
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{sse, v2::logs::QUERY_VALUE, Client};
+
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Name of the server-sent event closing the build log stream
+pub const EVENT_END_OF_STREAM: &str = "END_OF_STREAM";
+
+// -----------------------------------------------------------------------------
+// BuildLogLine structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct BuildLogLine {
+    #[serde(rename = "date")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "message")]
+    pub message: String,
+    #[serde(rename = "source", default)]
+    pub source: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to connect to the build log stream, {0}")]
+    Connect(ClientError),
+    #[error("failed to parse build log event '{0}'")]
+    Parse(String),
+    #[error("build log stream ended before the end of stream event")]
+    EndOfStream,
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to execute request, got status code {0}")]
+    StatusCode(u16),
+    #[error("failed to read build log stream, {0}")]
+    Body(hyper::Error),
+}
+
+// -----------------------------------------------------------------------------
+// State enumeration
+
+/// State enum holds the progression of the build log stream
+enum State<C>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    Connect(Box<Client<C>>, String),
+    Read(Box<sse::Reader>),
+    Done,
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns a stream of the build output of the given deployment, the stream
+/// ends once the api notifies the end of the build
+pub fn tail<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    application_id: &str,
+    deployment_id: &str,
+) -> impl Stream<Item = Result<BuildLogLine, Error>>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/logs/organisations/{}/applications/{}/logs?deploymentId={}",
+        client.endpoint,
+        organisation_id,
+        application_id,
+        utf8_percent_encode(deployment_id, QUERY_VALUE)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to follow the build log of a deployment, path: '{}', deployment: '{}'",
+            &path, deployment_id
+        );
+    }
+
+    stream::unfold(State::Connect(Box::new(client.to_owned()), path), next)
+}
+
+async fn next<C>(mut state: State<C>) -> Option<(Result<BuildLogLine, Error>, State<C>)>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    loop {
+        state = match state {
+            State::Connect(client, path) => {
+                let req = match hyper::Request::builder()
+                    .method(Method::GET)
+                    .uri(&path)
+                    .header(ACCEPT, "text/event-stream")
+                    .body(Body::empty())
+                {
+                    Ok(req) => req,
+                    Err(err) => return Some((Err(Error::Request(err)), State::Done)),
+                };
+
+                let res = match client.execute(req).await {
+                    Ok(res) => res,
+                    Err(err) => return Some((Err(Error::Connect(err)), State::Done)),
+                };
+
+                let status = res.status();
+                if !status.is_success() {
+                    return Some((Err(Error::StatusCode(status.as_u16())), State::Done));
+                }
+
+                State::Read(Box::new(sse::Reader::from(res.into_body())))
+            }
+            State::Read(mut reader) => match reader.next().await {
+                Some(Ok(event)) if Some(EVENT_END_OF_STREAM) == event.name.as_deref() => {
+                    return None;
+                }
+                Some(Ok(sse::Event {
+                    data: Some(data), ..
+                })) => {
+                    let result = serde_json::from_str(&data).map_err(|_| Error::Parse(data));
+                    return Some((result, State::Read(reader)));
+                }
+                Some(Ok(_)) => State::Read(reader),
+                Some(Err(err)) => return Some((Err(Error::Body(err)), State::Done)),
+                None => return Some((Err(Error::EndOfStream), State::Done)),
+            },
+            State::Done => return None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    #[tokio::test]
+    async fn tail_stops_at_the_end_of_stream_event() {
+        let server = Server::start(vec![Response::event_stream(
+            "data: {\"date\":\"2024-03-01T10:00:00Z\",\"message\":\"build\"}\n\n\
+             : keep-alive\n\n\
+             data: {\"date\":\"2024-03-01T10:00:01Z\",\"message\":\"done\",\"source\":\"cc\"}\n\n\
+             event: END_OF_STREAM\ndata: {}\n\n\
+             data: {\"date\":\"2024-03-01T10:00:02Z\",\"message\":\"ignored\"}\n\n",
+        )])
+        .await;
+        let client = server.client();
+
+        let lines: Vec<_> = tail(&client, "orga_1", "app_1", "deployment_1")
+            .map(|result| result.expect("build log line").message)
+            .collect()
+            .await;
+
+        assert_eq!(lines, vec!["build", "done"]);
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/logs/organisations/orga_1/applications/app_1/logs?deploymentId=deployment_1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn tail_fails_if_the_stream_ends_early() {
+        let server = Server::start(vec![Response::event_stream("data: nope\n\n")]).await;
+        let client = server.client();
+
+        let results: Vec<_> = tail(&client, "orga_1", "app_1", "deployment_1")
+            .collect()
+            .await;
+
+        assert!(matches!(results[0], Err(Error::Parse(ref data)) if data == "nope"));
+        assert!(matches!(results[1], Err(Error::EndOfStream)));
+        assert_eq!(results.len(), 2);
+    }
+}
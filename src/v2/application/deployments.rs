@@ -0,0 +1,242 @@
+//! # Deployments module
+//!
+//! This module provides structures and helpers to interact with the
+//! deployments of an application
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::application::build_log::{self, BuildLogLine},
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// DeploymentState enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum DeploymentState {
+    Ok,
+    Fail,
+    Cancelled,
+    InProgress,
+    Other(String),
+}
+
+impl From<String> for DeploymentState {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "OK" => Self::Ok,
+            "FAIL" => Self::Fail,
+            "CANCELLED" => Self::Cancelled,
+            "WIP" => Self::InProgress,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for DeploymentState {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for DeploymentState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Cancelled => write!(f, "CANCELLED"),
+            Self::InProgress => write!(f, "WIP"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Deployment structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Deployment {
+    #[serde(rename = "uuid")]
+    pub id: String,
+    #[serde(rename = "date")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "state")]
+    pub state: DeploymentState,
+    #[serde(rename = "action")]
+    pub action: String,
+    #[serde(rename = "commit", default)]
+    pub commit: Option<String>,
+    #[serde(rename = "cause", default)]
+    pub cause: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list deployments of application '{0}' of organisation '{1}', {2}")]
+    List(String, String, ClientError),
+    #[error(
+        "failed to get logs of deployment '{0}' of application '{1}' of organisation '{2}', {3}"
+    )]
+    Logs(String, String, String, build_log::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of deployments of the application
+pub async fn list<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    application_id: &str,
+) -> Result<Vec<Deployment>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/applications/{}/deployments",
+        client.endpoint, organisation_id, application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list deployments of an application, path: '{}', organisation: '{}', application: '{}'",
+            &path, organisation_id, application_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(application_id.to_owned(), organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the build log of the given deployment, the log of a deployment
+/// still in progress is returned once the build ends
+pub async fn logs<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    application_id: &str,
+    deployment_id: &str,
+) -> Result<Vec<BuildLogLine>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    build_log::tail(client, organisation_id, application_id, deployment_id)
+        .try_collect()
+        .await
+        .map_err(|err| {
+            Error::Logs(
+                deployment_id.to_owned(),
+                application_id.to_owned(),
+                organisation_id.to_owned(),
+                err,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const DEPLOYMENTS: &str = r#"[
+        {
+            "uuid": "deployment_2",
+            "date": "2024-03-01T10:00:00Z",
+            "state": "WIP",
+            "action": "DEPLOY",
+            "commit": "b1946ac92492d2347c6235b4d2611184"
+        },
+        {
+            "uuid": "deployment_1",
+            "date": "2024-02-29T09:00:00Z",
+            "state": "CANCELLED_BY_USER",
+            "action": "UNDEPLOY",
+            "cause": "manual"
+        }
+    ]"#;
+
+    #[tokio::test]
+    async fn list_deployments_of_the_organisation() {
+        let server = Server::start(vec![Response::json(200, DEPLOYMENTS)]).await;
+        let client = server.client();
+
+        let deployments = list(&client, "orga_1", "app_1").await.expect("deployments");
+
+        assert_eq!(deployments[0].state, DeploymentState::InProgress);
+        assert_eq!(deployments[0].cause, None);
+        assert_eq!(
+            deployments[1].state,
+            DeploymentState::Other("CANCELLED_BY_USER".to_string())
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/organisations/orga_1/applications/app_1/deployments"]
+        );
+    }
+
+    #[tokio::test]
+    async fn logs_are_the_build_log_of_the_deployment() {
+        let server = Server::start(vec![Response::event_stream(
+            "data: {\"date\":\"2024-03-01T10:00:00Z\",\"message\":\"build\"}\n\n\
+             event: END_OF_STREAM\ndata: {}\n\n",
+        )])
+        .await;
+        let client = server.client();
+
+        let lines = logs(&client, "orga_1", "app_1", "deployment 2")
+            .await
+            .expect("logs");
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].message, "build");
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v4/logs/organisations/orga_1/applications/app_1/logs?deploymentId=deployment%202"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn logs_of_an_unknown_deployment_fail() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+
+        match logs(&client, "orga_1", "app_1", "deployment_1").await {
+            Err(Error::Logs(deployment, application, organisation, err)) => {
+                assert_eq!(
+                    (
+                        deployment.as_str(),
+                        application.as_str(),
+                        organisation.as_str()
+                    ),
+                    ("deployment_1", "app_1", "orga_1")
+                );
+                assert!(matches!(err, build_log::Error::StatusCode(404)));
+            }
+            res => panic!("expected a logs error, got {res:?}"),
+        }
+    }
+}
@@ -0,0 +1,585 @@
+//! # Application module
+//!
+//! This module expose structures and helpers to interact with the application
+//! api version 2
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, TimeZone, Utc};
+use hyper::{client::connect::Connect, header::CONTENT_LENGTH, Body, Method};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::{
+        addon::{self, Addon},
+        owner::OwnerId,
+    },
+    v4::addon_provider::AddonProviderId,
+    Client,
+};
+
+pub mod build_log;
+pub mod deployments;
+pub mod run;
+
+// -----------------------------------------------------------------------------
+// Application structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Clone, Debug)]
+pub struct Application {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "description")]
+    pub description: String,
+    #[serde(rename = "zone")]
+    pub zone: String,
+    #[serde(rename = "creationDate")]
+    pub creation_date: u64,
+    #[serde(rename = "last_deploy")]
+    pub last_deploy: u64,
+    #[serde(rename = "archived")]
+    pub archived: bool,
+    #[serde(rename = "stickySessions")]
+    pub sticky_sessions: bool,
+    #[serde(rename = "homogeneous")]
+    pub homogeneous: bool,
+    #[serde(rename = "cancelOnPush")]
+    pub cancel_on_push: bool,
+    #[serde(rename = "separateBuild")]
+    pub separate_build: bool,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "state")]
+    pub state: String,
+    #[serde(rename = "commitId")]
+    pub commit_id: Option<String>,
+    #[serde(rename = "branch")]
+    pub branch: String,
+    #[serde(rename = "forceHttps")]
+    pub force_https: String,
+    #[serde(rename = "favourite", default)]
+    pub is_favourite: bool,
+    #[serde(rename = "deployUrl", default)]
+    pub deploy_url: Option<String>,
+}
+
+impl Application {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the application is expected to be up and running
+    pub fn is_running(&self) -> bool {
+        "SHOULD_BE_UP" == self.state
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the application is being deployed or restarted
+    pub fn is_deploying(&self) -> bool {
+        matches!(
+            self.state.as_str(),
+            "WANTS_TO_BE_UP" | "DEPLOYING" | "RESTART_IN_PROGRESS"
+        )
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the application is stopped
+    pub fn is_stopped(&self) -> bool {
+        matches!(self.state.as_str(), "SHOULD_BE_DOWN" | "STOPPED")
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the date of the last deployment, if the application has ever
+    /// been deployed
+    pub fn last_deployed_at(&self) -> Option<DateTime<Utc>> {
+        if 0 == self.last_deploy {
+            return None;
+        }
+
+        i64::try_from(self.last_deploy)
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the ssh url of the git remote to push to in order to deploy the
+    /// application, see <https://www.clever-cloud.com/developers/doc/quickstart/>
+    ///
+    /// it is the url given by the api, `None` is returned if the api does not
+    /// give one, as the host of the git remote could not be known otherwise
+    pub fn deployment_url(&self) -> Option<String> {
+        self.deploy_url.to_owned()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the https url of the git remote of the application to clone the
+    /// deployed code, see <https://www.clever-cloud.com/developers/doc/quickstart/>
+    ///
+    /// the api only gives the ssh url, the https one is derived from it by
+    /// replacing its scheme and user, as the git remotes are served on both,
+    /// `None` is returned if the api does not give the ssh url
+    pub fn clone_url(&self) -> Option<String> {
+        self.deploy_url
+            .as_ref()
+            .map(|url| url.replacen("git+ssh://git@", "https://", 1))
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// link the given addon of the owner to the application
+    pub async fn link_addon<C>(
+        &self,
+        client: &Client<C>,
+        owner: &OwnerId,
+        addon_id: &str,
+    ) -> Result<(), addon::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        addon::link_to_app(client, owner, &self.id, addon_id).await
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// unlink the given addon of the owner from the application
+    pub async fn unlink_addon<C>(
+        &self,
+        client: &Client<C>,
+        owner: &OwnerId,
+        addon_id: &str,
+    ) -> Result<(), addon::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        addon::unlink_from_app(client, owner, &self.id, addon_id).await
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the list of addons linked to the application
+    pub async fn linked_addons<C>(
+        &self,
+        client: &Client<C>,
+        owner: &OwnerId,
+    ) -> Result<Vec<Addon>, addon::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        addon::list_for_app(client, owner, &self.id).await
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Instance structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Instance {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "appId")]
+    pub application_id: String,
+    #[serde(rename = "state")]
+    pub state: String,
+    #[serde(rename = "commit", default)]
+    pub commit: Option<String>,
+    #[serde(rename = "deployNumber")]
+    pub deploy_number: u64,
+    #[serde(rename = "instanceNumber")]
+    pub instance_number: u64,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "creationDate")]
+    pub creation_date: u64,
+}
+
+// -----------------------------------------------------------------------------
+// Tag structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct Tag {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+// -----------------------------------------------------------------------------
+// TagOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct TagOpts {
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list applications of '{0}', {1}")]
+    List(OwnerId, ClientError),
+    #[error("failed to get application '{0}' of '{1}', {2}")]
+    Get(String, OwnerId, ClientError),
+    #[error("failed to mark application '{0}' of '{1}' as favourite, {2}")]
+    MarkFavourite(String, OwnerId, ClientError),
+    #[error("failed to unmark application '{0}' of '{1}' as favourite, {2}")]
+    UnmarkFavourite(String, OwnerId, ClientError),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to execute request, got status code {0}")]
+    StatusCode(u16),
+    #[error("failed to list tags of application '{0}' of '{1}', {2}")]
+    ListTags(String, OwnerId, ClientError),
+    #[error("failed to add tag '{0}' to application '{1}' of '{2}', {3}")]
+    AddTag(String, String, OwnerId, ClientError),
+    #[error("failed to remove tag '{0}' from application '{1}' of '{2}', {3}")]
+    RemoveTag(String, String, OwnerId, ClientError),
+    #[error("failed to list addons of the application, {0}")]
+    AddonList(Box<addon::Error>),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of applications of the given owner
+pub async fn list<C>(client: &Client<C>, owner: &OwnerId) -> Result<Vec<Application>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/{}/applications", client.endpoint, owner.path());
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get the list of applications, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the application of the given owner
+pub async fn get<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<Application, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about an application, path: '{}', owner: '{}', application: '{}'",
+            &path, owner, application_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(application_id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// mark the application of the given owner as favourite
+pub async fn mark_favourite<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/favourite",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to mark an application as favourite, path: '{}', owner: '{}', application: '{}'",
+            &path, owner, application_id
+        );
+    }
+
+    let req = hyper::Request::builder()
+        .method(Method::PUT)
+        .uri(&path)
+        .header(CONTENT_LENGTH, 0)
+        .body(Body::empty())
+        .map_err(Error::Request)?;
+
+    let res = client
+        .execute(req)
+        .await
+        .map_err(|err| Error::MarkFavourite(application_id.to_owned(), owner.to_owned(), err))?;
+
+    let status = res.status();
+    if !status.is_success() {
+        return Err(Error::StatusCode(status.as_u16()));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// unmark the application of the given owner as favourite
+pub async fn unmark_favourite<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/favourite",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to unmark an application as favourite, path: '{}', owner: '{}', application: '{}'",
+            &path, owner, application_id
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::UnmarkFavourite(application_id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the tags of the application of the given owner
+pub async fn list_tags<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<Vec<Tag>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/tags",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list tags of an application, path: '{}', owner: '{}', application: '{}'",
+            &path, owner, application_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::ListTags(application_id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// add a tag with the given name to the application of the given owner and
+/// returns it
+pub async fn add_tag<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+    name: &str,
+) -> Result<Tag, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/tags",
+        client.endpoint,
+        owner.path(),
+        application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to add a tag to an application, path: '{}', owner: '{}', application: '{}', name: '{}'",
+            &path, owner, application_id, name
+        );
+    }
+
+    let opts = TagOpts {
+        name: name.to_owned(),
+    };
+
+    client.post(&path, &opts).await.map_err(|err| {
+        Error::AddTag(
+            name.to_owned(),
+            application_id.to_owned(),
+            owner.to_owned(),
+            err,
+        )
+    })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// remove the given tag from the application of the given owner
+pub async fn remove_tag<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+    tag_id: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/applications/{}/tags/{}",
+        client.endpoint,
+        owner.path(),
+        application_id,
+        tag_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to remove a tag from an application, path: '{}', owner: '{}', application: '{}', tag: '{}'",
+            &path, owner, application_id, tag_id
+        );
+    }
+
+    client.delete(&path).await.map_err(|err| {
+        Error::RemoveTag(
+            tag_id.to_owned(),
+            application_id.to_owned(),
+            owner.to_owned(),
+            err,
+        )
+    })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the config-provider addon linked to the given application, if any
+pub async fn config_provider_addon<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    application_id: &str,
+) -> Result<Option<Addon>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    Ok(addon::list_for_app(client, owner, application_id)
+        .await
+        .map_err(|err| Error::AddonList(Box::new(err)))?
+        .into_iter()
+        .find(|addon| Some(AddonProviderId::ConfigProvider) == addon.provider_id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const APPLICATION: &str = r#"{
+        "id": "app_3c4d5e6f-7a8b-4c9d-0e1f-2a3b4c5d6e7f",
+        "name": "my-application",
+        "description": "my application",
+        "zone": "par",
+        "creationDate": 1700000000,
+        "last_deploy": 1700000600,
+        "archived": false,
+        "stickySessions": false,
+        "homogeneous": false,
+        "cancelOnPush": false,
+        "separateBuild": false,
+        "ownerId": "orga_1",
+        "state": "SHOULD_BE_UP",
+        "commitId": "b1946ac92492d2347c6235b4d2611184",
+        "branch": "master",
+        "forceHttps": "ENABLED"
+    }"#;
+
+    #[tokio::test]
+    async fn helpers_target_the_paths_of_the_owner() {
+        let server = Server::start(vec![
+            Response::json(200, format!("[{APPLICATION}]")),
+            Response::json(200, APPLICATION),
+            Response::json(200, format!("[{APPLICATION}]")),
+            Response::json(200, APPLICATION),
+        ])
+        .await;
+        let client = server.client();
+        let app_id = "app_3c4d5e6f-7a8b-4c9d-0e1f-2a3b4c5d6e7f";
+
+        for owner in [OwnerId::from("orga_1"), OwnerId::Myself] {
+            assert_eq!(list(&client, &owner).await.expect("list").len(), 1);
+            assert_eq!(get(&client, &owner, app_id).await.expect("get").id, app_id);
+        }
+
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/organisations/orga_1/applications".to_string(),
+                format!("/v2/organisations/orga_1/applications/{app_id}"),
+                "/v2/self/applications".to_string(),
+                format!("/v2/self/applications/{app_id}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn git_urls_use_the_deploy_url_of_the_api() {
+        let mut application: Application =
+            serde_json::from_str(APPLICATION).expect("application to deserialize");
+        application.deploy_url = Some(
+            "git+ssh://git@push-n2-mtl-clevercloud-customers.services.clever-cloud.com/app_1.git"
+                .to_string(),
+        );
+
+        assert_eq!(
+            application.deployment_url().as_deref(),
+            Some("git+ssh://git@push-n2-mtl-clevercloud-customers.services.clever-cloud.com/app_1.git")
+        );
+        assert_eq!(
+            application.clone_url().as_deref(),
+            Some("https://push-n2-mtl-clevercloud-customers.services.clever-cloud.com/app_1.git")
+        );
+    }
+
+    #[test]
+    fn git_urls_are_unknown_without_the_deploy_url() {
+        let application: Application =
+            serde_json::from_str(APPLICATION).expect("application to deserialize");
+
+        assert_eq!(application.deploy_url, None);
+        assert_eq!(application.deployment_url(), None);
+        assert_eq!(application.clone_url(), None);
+    }
+}
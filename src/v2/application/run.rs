@@ -0,0 +1,89 @@
+//! # Run module
+//!
+//! This module expose structures and helpers to execute one-off tasks on the
+//! image of an application
+
+use std::{collections::BTreeMap, fmt::Debug};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// RunOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct RunOpts {
+    #[serde(rename = "command")]
+    pub command: String,
+    #[serde(rename = "environment")]
+    pub environment: BTreeMap<String, String>,
+}
+
+// -----------------------------------------------------------------------------
+// RunResult structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct RunResult {
+    #[serde(rename = "exitCode")]
+    pub exit_code: i32,
+    #[serde(rename = "stdout")]
+    pub stdout: String,
+    #[serde(rename = "stderr")]
+    pub stderr: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to run task, {0}")]
+    Run(ClientError),
+    #[error("task exited with non-zero code {0}, {1}")]
+    NonZeroExit(i32, String),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// run a one-off task on the image of the application and returns its output,
+/// a task exiting with a non-zero code is considered as an error
+pub async fn run<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    application_id: &str,
+    opts: &RunOpts,
+) -> Result<RunResult, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/applications/{}/run",
+        client.endpoint, organisation_id, application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to run a task on an application, path: '{}', organisation: '{}', application: '{}', command: '{}'",
+            &path, organisation_id, application_id, &opts.command
+        );
+    }
+
+    let result: RunResult = client.post(&path, opts).await.map_err(Error::Run)?;
+    if result.exit_code != 0 {
+        return Err(Error::NonZeroExit(result.exit_code, result.stderr));
+    }
+
+    Ok(result)
+}
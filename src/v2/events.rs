@@ -0,0 +1,391 @@
+//! # Events module
+//!
+//! This module provides structures and helpers to follow the live events of the
+//! platform, such as deployments or addon provisioning
+
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream};
+use hyper::{client::connect::Connect, header::ACCEPT, Body, Method};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request};
+use percent_encoding::utf8_percent_encode;
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{sse, v2::logs::QUERY_VALUE, Client};
+
+// -----------------------------------------------------------------------------
+// EventKind enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum EventKind {
+    DeploymentBegin,
+    DeploymentEnd,
+    ApplicationCreation,
+    ApplicationEdition,
+    ApplicationDeletion,
+    AddonCreation,
+    AddonDeletion,
+    Other(String),
+}
+
+impl From<String> for EventKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "DEPLOYMENT_ACTION_BEGIN" => Self::DeploymentBegin,
+            "DEPLOYMENT_ACTION_END" => Self::DeploymentEnd,
+            "APPLICATION_CREATION" => Self::ApplicationCreation,
+            "APPLICATION_EDITION" => Self::ApplicationEdition,
+            "APPLICATION_DELETION" => Self::ApplicationDeletion,
+            "ADDON_CREATION" => Self::AddonCreation,
+            "ADDON_DELETION" => Self::AddonDeletion,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for EventKind {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for EventKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::DeploymentBegin => write!(f, "DEPLOYMENT_ACTION_BEGIN"),
+            Self::DeploymentEnd => write!(f, "DEPLOYMENT_ACTION_END"),
+            Self::ApplicationCreation => write!(f, "APPLICATION_CREATION"),
+            Self::ApplicationEdition => write!(f, "APPLICATION_EDITION"),
+            Self::ApplicationDeletion => write!(f, "APPLICATION_DELETION"),
+            Self::AddonCreation => write!(f, "ADDON_CREATION"),
+            Self::AddonDeletion => write!(f, "ADDON_DELETION"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Event structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Event {
+    #[serde(rename = "event")]
+    pub kind: EventKind,
+    #[serde(rename = "date")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "data", default)]
+    pub payload: serde_json::Value,
+    #[serde(rename = "appId", default)]
+    pub application_id: Option<String>,
+    #[serde(rename = "addonId", default)]
+    pub addon_id: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// ReconnectOpts structure
+
+/// ReconnectOpts structure configures how to reconnect to the events endpoint
+/// once disconnected, the delay between two attempts doubles from `backoff` up
+/// to `max_backoff`
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ReconnectOpts {
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_retries: Option<u32>,
+}
+
+impl Default for ReconnectOpts {
+    fn default() -> Self {
+        Self {
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_retries: None,
+        }
+    }
+}
+
+impl ReconnectOpts {
+    /// returns the delay to wait before the given reconnection attempt
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to connect to the events stream, {0}")]
+    Connect(ClientError),
+    #[error("failed to parse event '{0}'")]
+    Parse(String),
+    #[error("failed to reconnect to the events stream after {0} attempts")]
+    Reconnect(u32),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to execute request, got status code {0}")]
+    StatusCode(u16),
+    #[error("failed to read events stream, {0}")]
+    Body(hyper::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Connection structure
+
+/// Connection structure holds what is needed to (re)open the events stream
+struct Connection<C>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    client: Client<C>,
+    path: String,
+    opts: ReconnectOpts,
+    attempt: u32,
+}
+
+// -----------------------------------------------------------------------------
+// State enumeration
+
+/// State enum holds the progression of the events stream
+enum State<C>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    Connect(Box<Connection<C>>),
+    Read(Box<Connection<C>>, Box<sse::Reader>),
+    Done,
+}
+
+impl<C> State<C>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    /// returns the state to reconnect after a disconnection, or to give up once
+    /// the maximum number of attempts is reached
+    fn reconnect(mut conn: Box<Connection<C>>) -> Result<Self, Error> {
+        conn.attempt += 1;
+        match conn.opts.max_retries {
+            Some(max_retries) if conn.attempt > max_retries => Err(Error::Reconnect(max_retries)),
+            _ => Ok(Self::Connect(conn)),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns a stream of the events of the platform, restricted to the given
+/// organisation if any, the stream reconnects with the default options
+pub fn stream<C>(
+    client: &Client<C>,
+    organisation_id: Option<&str>,
+) -> impl Stream<Item = Result<Event, Error>>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    stream_with_opts(client, organisation_id, ReconnectOpts::default())
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns a stream of the events of the platform, restricted to the given
+/// organisation if any, the stream reconnects according to the given options
+/// and only ends once they are exhausted
+pub fn stream_with_opts<C>(
+    client: &Client<C>,
+    organisation_id: Option<&str>,
+    opts: ReconnectOpts,
+) -> impl Stream<Item = Result<Event, Error>>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let mut path = format!("{}/v2/events/event-stream", client.endpoint);
+    if let Some(organisation_id) = organisation_id {
+        path.push_str(&format!(
+            "?orga_id={}",
+            utf8_percent_encode(organisation_id, QUERY_VALUE)
+        ));
+    }
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to follow the events of the platform, path: '{}'",
+            &path
+        );
+    }
+
+    let conn = Connection {
+        client: client.to_owned(),
+        path,
+        opts,
+        attempt: 0,
+    };
+
+    stream::unfold(State::Connect(Box::new(conn)), next)
+}
+
+async fn next<C>(mut state: State<C>) -> Option<(Result<Event, Error>, State<C>)>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    loop {
+        state = match state {
+            State::Connect(mut conn) => {
+                if conn.attempt > 0 {
+                    let delay = conn.opts.delay(conn.attempt);
+
+                    #[cfg(feature = "logging")]
+                    if log_enabled!(Level::Debug) {
+                        debug!(
+                            "reconnect to the events stream in {}ms, attempt: {}",
+                            delay.as_millis(),
+                            conn.attempt
+                        );
+                    }
+
+                    tokio::time::sleep(delay).await;
+                }
+
+                let req = match hyper::Request::builder()
+                    .method(Method::GET)
+                    .uri(&conn.path)
+                    .header(ACCEPT, "text/event-stream")
+                    .body(Body::empty())
+                {
+                    Ok(req) => req,
+                    Err(err) => return Some((Err(Error::Request(err)), State::Done)),
+                };
+
+                let result = match conn.client.execute(req).await {
+                    Ok(res) if res.status().is_success() => Ok(res.into_body()),
+                    Ok(res) if res.status().is_client_error() && 429 != res.status() => {
+                        return Some((Err(Error::StatusCode(res.status().as_u16())), State::Done));
+                    }
+                    Ok(res) => Err(Error::StatusCode(res.status().as_u16())),
+                    Err(err) => Err(Error::Connect(err)),
+                };
+
+                match result {
+                    Ok(body) => {
+                        conn.attempt = 0;
+                        State::Read(conn, Box::new(sse::Reader::from(body)))
+                    }
+                    Err(err) => match State::reconnect(conn) {
+                        Ok(state) => return Some((Err(err), state)),
+                        Err(err) => return Some((Err(err), State::Done)),
+                    },
+                }
+            }
+            State::Read(conn, mut reader) => match reader.next().await {
+                Some(Ok(sse::Event {
+                    data: Some(data), ..
+                })) => {
+                    let result = serde_json::from_str(&data).map_err(|_| Error::Parse(data));
+                    return Some((result, State::Read(conn, reader)));
+                }
+                Some(Ok(_)) => State::Read(conn, reader),
+                Some(Err(err)) => match State::reconnect(conn) {
+                    Ok(state) => return Some((Err(Error::Body(err)), state)),
+                    Err(err) => return Some((Err(err), State::Done)),
+                },
+                None => match State::reconnect(conn) {
+                    Ok(state) => state,
+                    Err(err) => return Some((Err(err), State::Done)),
+                },
+            },
+            State::Done => return None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// returns a server-sent event holding an event of the given kind
+    fn event(kind: &str) -> String {
+        format!(
+            "event: message\ndata: {{\"event\":\"{kind}\",\"date\":\"2024-03-01T10:00:00Z\",\"appId\":\"app_1\"}}\n\n"
+        )
+    }
+
+    #[tokio::test]
+    async fn stream_reconnects_once_disconnected() {
+        let server = Server::start(vec![
+            Response::event_stream(format!(
+                "{}: keep-alive\n\n{}{}",
+                event("DEPLOYMENT_ACTION_BEGIN"),
+                event("DEPLOYMENT_ACTION_END"),
+                event("ADDON_CREATION"),
+            )),
+            Response::event_stream(event("APPLICATION_DELETION")),
+        ])
+        .await;
+        let client = server.client();
+        let opts = ReconnectOpts {
+            backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            max_retries: Some(3),
+        };
+
+        let results: Vec<_> = stream_with_opts(&client, Some("orga_1 &x=y"), opts)
+            .collect()
+            .await;
+
+        let kinds: Vec<_> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|event| event.kind.to_owned())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::DeploymentBegin,
+                EventKind::DeploymentEnd,
+                EventKind::AddonCreation,
+                EventKind::ApplicationDeletion,
+            ]
+        );
+        assert_eq!(
+            results[0]
+                .as_ref()
+                .expect("event")
+                .application_id
+                .as_deref(),
+            Some("app_1")
+        );
+        assert!(matches!(results.last(), Some(Err(Error::StatusCode(404)))));
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/events/event-stream?orga_id=orga_1%20%26x%3Dy"; 3]
+        );
+    }
+
+    #[test]
+    fn reconnect_delay_doubles_up_to_the_maximum() {
+        let opts = ReconnectOpts::default();
+
+        assert_eq!(opts.delay(1), Duration::from_secs(1));
+        assert_eq!(opts.delay(3), Duration::from_secs(4));
+        assert_eq!(opts.delay(10), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,342 @@
+//! # Logs module
+//!
+//! This module provides structures and helpers to retrieve the recent logs of
+//! applications and addons
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Characters to encode in the value of a query parameter
+pub(crate) const QUERY_VALUE: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>')
+    .add(b'`');
+
+// -----------------------------------------------------------------------------
+// LogLine structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct LogLine {
+    #[serde(rename = "@timestamp")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "host", alias = "hostname")]
+    pub hostname: String,
+    #[serde(rename = "instanceId", alias = "instance_id", default)]
+    pub instance_id: Option<String>,
+    #[serde(rename = "syslog_severity", default)]
+    pub level: String,
+    #[serde(rename = "message")]
+    pub message: String,
+}
+
+/// Hit structure wraps a log line as returned by the api
+#[derive(Deserialize, Debug)]
+struct Hit {
+    #[serde(rename = "_source")]
+    source: LogLine,
+}
+
+// -----------------------------------------------------------------------------
+// LogFilter structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct LogFilter {
+    #[serde(rename = "since", skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(rename = "until", skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(rename = "limit", skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(rename = "filter", skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+impl LogFilter {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the filter as a query string including the leading '?', or an
+    /// empty string if no criteria is set
+    pub fn to_query(&self) -> String {
+        let mut query = vec![];
+        if let Some(since) = &self.since {
+            query.push(format!(
+                "since={}",
+                since.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
+
+        if let Some(until) = &self.until {
+            query.push(format!(
+                "until={}",
+                until.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
+
+        if let Some(limit) = self.limit {
+            query.push(format!("limit={limit}"));
+        }
+
+        if let Some(filter) = &self.filter {
+            query.push(format!(
+                "filter={}",
+                utf8_percent_encode(filter, QUERY_VALUE)
+            ));
+        }
+
+        if query.is_empty() {
+            return String::new();
+        }
+
+        format!("?{}", query.join("&"))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get logs of application '{0}', {1}")]
+    Application(String, ClientError),
+    #[error("failed to get logs of addon '{0}', {1}")]
+    Addon(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the recent logs of the given application matching the filter
+pub async fn application<C>(
+    client: &Client<C>,
+    application_id: &str,
+    filter: &LogFilter,
+) -> Result<Vec<LogLine>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/logs/{}{}",
+        client.endpoint,
+        application_id,
+        filter.to_query()
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get logs of an application, path: '{}', application: '{}'",
+            &path, application_id
+        );
+    }
+
+    let hits: Vec<Hit> = client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Application(application_id.to_owned(), err))?;
+
+    Ok(hits.into_iter().map(|hit| hit.source).collect())
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the recent logs of the given addon matching the filter, lines of
+/// addons which are not backed by instances have no instance identifier
+pub async fn addon<C>(
+    client: &Client<C>,
+    addon_id: &str,
+    filter: &LogFilter,
+) -> Result<Vec<LogLine>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/logs/addon/{}{}",
+        client.endpoint,
+        addon_id,
+        filter.to_query()
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get logs of an addon, path: '{}', addon: '{}'",
+            &path, addon_id
+        );
+    }
+
+    let hits: Vec<Hit> = client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Addon(addon_id.to_owned(), err))?;
+
+    Ok(hits.into_iter().map(|hit| hit.source).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const HITS: &str = r#"[{
+        "_index": "logs-2024.05.01",
+        "_id": "a1b2c3",
+        "_source": {
+            "@timestamp": "2024-05-01T10:00:00.123Z",
+            "host": "host_1",
+            "instanceId": "instance_1",
+            "syslog_severity": "info",
+            "message": "listening on 8080"
+        }
+    }, {
+        "_index": "logs-2024.05.01",
+        "_id": "d4e5f6",
+        "_source": {
+            "@timestamp": "2024-05-01T10:00:01Z",
+            "hostname": "host_2",
+            "message": "connection accepted"
+        }
+    }]"#;
+
+    #[test]
+    fn empty_filter_has_no_query() {
+        assert_eq!(LogFilter::default().to_query(), "");
+    }
+
+    #[test]
+    fn filter_is_serialized_as_query_parameters() {
+        let filter = LogFilter {
+            since: Some(
+                Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0)
+                    .single()
+                    .expect("date"),
+            ),
+            until: Some(
+                Utc.with_ymd_and_hms(2024, 5, 1, 11, 30, 0)
+                    .single()
+                    .expect("date"),
+            ),
+            limit: Some(100),
+            filter: Some("status=500 & path=\"/api\"".to_string()),
+        };
+
+        assert_eq!(
+            filter.to_query(),
+            "?since=2024-05-01T10:00:00.000Z&until=2024-05-01T11:30:00.000Z&limit=100&filter=status%3D500%20%26%20path%3D%22/api%22"
+        );
+
+        let filter = LogFilter {
+            limit: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(filter.to_query(), "?limit=10");
+    }
+
+    #[tokio::test]
+    async fn application_deserializes_the_captured_response() {
+        let server = Server::start(vec![Response::json(200, HITS)]).await;
+        let client = server.client();
+        let filter = LogFilter {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let lines = application(&client, "app_1", &filter).await.expect("logs");
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0].timestamp,
+            Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0)
+                .single()
+                .expect("date")
+                + chrono::Duration::milliseconds(123)
+        );
+        assert_eq!(lines[0].hostname, "host_1");
+        assert_eq!(lines[0].instance_id.as_deref(), Some("instance_1"));
+        assert_eq!(lines[0].level, "info");
+        assert_eq!(lines[1].hostname, "host_2");
+        assert_eq!(lines[1].instance_id, None);
+        assert_eq!(lines[1].level, "");
+        assert_eq!(server.uris(), vec!["/v2/logs/app_1?limit=2"]);
+    }
+
+    #[tokio::test]
+    async fn addon_targets_the_logs_of_the_addon() {
+        let server = Server::start(vec![Response::json(200, HITS)]).await;
+        let client = server.client();
+
+        let lines = addon(&client, "addon_1", &LogFilter::default())
+            .await
+            .expect("logs");
+
+        assert_eq!(lines[1].message, "connection accepted");
+        assert_eq!(server.uris(), vec!["/v2/logs/addon/addon_1"]);
+        assert!(matches!(
+            addon(&client, "addon_1", &LogFilter::default()).await,
+            Err(Error::Addon(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn addon_lines_may_lack_an_instance() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"[{
+                "_index": "logs-addons-2024.05.01",
+                "_source": {
+                    "@timestamp": "2024-05-01T10:00:00Z",
+                    "hostname": "bxyz-postgresql",
+                    "syslog_severity": "warning",
+                    "message": "checkpoints are occurring too frequently (24 seconds apart)"
+                }
+            }, {
+                "_index": "logs-addons-2024.05.01",
+                "_source": {
+                    "@timestamp": "2024-05-01T10:00:02Z",
+                    "host": "bxyz-postgresql",
+                    "instance_id": "instance_1",
+                    "syslog_severity": "info",
+                    "message": "checkpoint complete"
+                }
+            }]"#,
+        )])
+        .await;
+        let client = server.client();
+        let filter = LogFilter {
+            limit: Some(200),
+            ..Default::default()
+        };
+
+        let lines = addon(&client, "postgresql_1", &filter).await.expect("logs");
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].hostname, "bxyz-postgresql");
+        assert_eq!(lines[0].instance_id, None);
+        assert_eq!(lines[0].level, "warning");
+        assert_eq!(lines[1].instance_id.as_deref(), Some("instance_1"));
+        assert_eq!(server.uris(), vec!["/v2/logs/addon/postgresql_1?limit=200"]);
+    }
+}
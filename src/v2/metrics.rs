@@ -0,0 +1,129 @@
+//! # Metrics module
+//!
+//! This module provides structures and helpers to get access to the metrics of
+//! an owner stored in Warp10
+
+use std::fmt::{self, Debug, Formatter};
+
+use chrono::{DateTime, Utc};
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v2::owner::OwnerId, Client};
+
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Public endpoint of the Warp10 instance storing the metrics
+pub const WARP10_ENDPOINT: &str =
+    "https://c2-warp10-clevercloud-customers.services.clever-cloud.com/api/v0";
+
+// -----------------------------------------------------------------------------
+// MetricsToken structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct MetricsToken {
+    #[serde(rename = "token")]
+    pub token: String,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Debug for MetricsToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsToken")
+            .field("token", &"[redacted]")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get metrics read token of '{0}', {1}")]
+    ReadToken(OwnerId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the public endpoint of the Warp10 instance storing the metrics
+pub fn warp10_endpoint() -> &'static str {
+    WARP10_ENDPOINT
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns a token allowed to read the metrics of the given owner from Warp10
+pub async fn read_token<C>(client: &Client<C>, owner: &OwnerId) -> Result<MetricsToken, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/metrics/read/{}", client.endpoint, owner);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get a metrics read token, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::ReadToken(owner.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const TOKEN: &str = r#"{"token":"secret_token","expiresAt":"2024-05-02T10:00:00Z"}"#;
+
+    #[test]
+    fn deserialize_token() {
+        let token: MetricsToken = serde_json::from_str(TOKEN).expect("token to deserialize");
+
+        assert_eq!(token.token, "secret_token");
+        assert_eq!(token.expires_at.to_rfc3339(), "2024-05-02T10:00:00+00:00");
+    }
+
+    #[test]
+    fn debug_masks_the_token() {
+        let token: MetricsToken = serde_json::from_str(TOKEN).expect("token to deserialize");
+
+        let debug = format!("{token:?}");
+
+        assert!(!debug.contains("secret_token"));
+        assert!(debug.contains("[redacted]"));
+        assert!(debug.contains("2024-05-02T10:00:00Z"));
+    }
+
+    #[tokio::test]
+    async fn read_token_targets_the_owner() {
+        let server = Server::start(vec![Response::json(200, TOKEN)]).await;
+        let client = server.client();
+
+        let token = read_token(&client, &OwnerId::from("orga_1"))
+            .await
+            .expect("token");
+
+        assert_eq!(token.token, "secret_token");
+        assert_eq!(server.uris(), vec!["/v2/metrics/read/orga_1"]);
+        assert!(matches!(
+            read_token(&client, &OwnerId::Myself).await,
+            Err(Error::ReadToken(OwnerId::Myself, _))
+        ));
+    }
+}
@@ -3,4 +3,14 @@
 //! This module expose resources under the version 2 of the Clever-Cloud Api.
 
 pub mod addon;
+pub mod application;
+#[cfg(feature = "tokio")]
+pub mod events;
+pub mod logs;
+pub mod metrics;
 pub mod myself;
+pub mod notifications;
+pub mod organisation;
+pub mod owner;
+pub mod ssh_key;
+pub mod summary;
@@ -0,0 +1,335 @@
+//! # Multi-factor authentication module
+//!
+//! This module provides structures and helpers to enrol the current user to
+//! multi-factor authentication
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use hyper::{
+    client::connect::Connect,
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Body, Method,
+};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Constants
+
+pub const KIND_TOTP: &str = "totp";
+
+// -----------------------------------------------------------------------------
+// Kind structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(transparent)]
+pub struct Kind(String);
+
+impl Default for Kind {
+    fn default() -> Self {
+        Self::totp()
+    }
+}
+
+impl From<&str> for Kind {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Kind {
+    pub fn totp() -> Self {
+        Self(KIND_TOTP.to_string())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// MfaSecret structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct MfaSecret {
+    #[serde(rename = "secret")]
+    pub secret: String,
+    #[serde(rename = "url")]
+    pub url: String,
+}
+
+impl Debug for MfaSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MfaSecret")
+            .field("secret", &"[redacted]")
+            .field("url", &"[redacted]")
+            .finish()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Code structure
+
+#[derive(Serialize)]
+struct Code<'a> {
+    #[serde(rename = "code")]
+    code: &'a str,
+}
+
+impl Debug for Code<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Code").field("code", &"[redacted]").finish()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to create '{0}' multi-factor authentication of the current user, {1}")]
+    Create(Kind, ClientError),
+    #[error("failed to confirm '{0}' multi-factor authentication of the current user, {1}")]
+    Confirm(Kind, ClientError),
+    #[error("failed to delete '{0}' multi-factor authentication of the current user, {1}")]
+    Delete(Kind, ClientError),
+    #[error(
+        "failed to get backup codes of '{0}' multi-factor authentication of the current user, {1}"
+    )]
+    BackupCodes(Kind, ClientError),
+    #[error("failed to serialize code, {0}")]
+    Serialize(serde_json::Error),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to execute request, got status code {0}")]
+    StatusCode(u16),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// create the multi-factor authentication of the given kind for the person
+/// logged in and returns the secret to register in an authenticator
+pub async fn create<C>(client: &Client<C>, kind: &Kind) -> Result<MfaSecret, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/mfa/{}", client.endpoint, kind);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to create multi-factor authentication of the logged in user, path: '{}', kind: '{}'",
+            &path, kind
+        );
+    }
+
+    client
+        .post(&path, &())
+        .await
+        .map_err(|err| Error::Create(kind.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument(skip(code)))]
+/// confirm the multi-factor authentication of the given kind for the person
+/// logged in using a code generated by the authenticator
+pub async fn confirm<C>(client: &Client<C>, kind: &Kind, code: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/mfa/{}/confirmation", client.endpoint, kind);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to confirm multi-factor authentication of the logged in user, path: '{}', kind: '{}'",
+            &path, kind
+        );
+    }
+
+    send(client, Method::POST, &path, code, |err| {
+        Error::Confirm(kind.to_owned(), err)
+    })
+    .await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument(skip(code)))]
+/// delete the multi-factor authentication of the given kind for the person
+/// logged in using a code generated by the authenticator
+pub async fn delete<C>(client: &Client<C>, kind: &Kind, code: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/mfa/{}", client.endpoint, kind);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to delete multi-factor authentication of the logged in user, path: '{}', kind: '{}'",
+            &path, kind
+        );
+    }
+
+    send(client, Method::DELETE, &path, code, |err| {
+        Error::Delete(kind.to_owned(), err)
+    })
+    .await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the backup codes of the multi-factor authentication of the given
+/// kind for the person logged in
+pub async fn backup_codes<C>(client: &Client<C>, kind: &Kind) -> Result<Vec<String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/mfa/{}/backupcodes", client.endpoint, kind);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get backup codes of multi-factor authentication of the logged in user, path: '{}', kind: '{}'",
+            &path, kind
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::BackupCodes(kind.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument(skip(code, on_error)))]
+/// send the code to the given path using a signed request
+async fn send<C, F>(
+    client: &Client<C>,
+    method: Method,
+    path: &str,
+    code: &str,
+    on_error: F,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+    F: FnOnce(ClientError) -> Error,
+{
+    let buf = serde_json::to_vec(&Code { code }).map_err(Error::Serialize)?;
+    let req = hyper::Request::builder()
+        .method(method)
+        .uri(path)
+        .header(CONTENT_TYPE, "application/json")
+        .header(CONTENT_LENGTH, buf.len())
+        .body(Body::from(buf))
+        .map_err(Error::Request)?;
+
+    let res = client.execute(req).await.map_err(on_error)?;
+    let status = res.status();
+    if !status.is_success() {
+        return Err(Error::StatusCode(status.as_u16()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const SECRET: &str = r#"{
+        "secret": "JBSWY3DPEHPK3PXP",
+        "url": "otpauth://totp/Clever%20Cloud:jane?secret=JBSWY3DPEHPK3PXP"
+    }"#;
+
+    #[test]
+    fn kind_defaults_to_totp() {
+        assert_eq!(Kind::default().to_string(), KIND_TOTP);
+        assert_eq!(Kind::from("webauthn").to_string(), "webauthn");
+        assert_eq!(
+            serde_json::to_value(Kind::totp()).expect("kind to serialize"),
+            "totp"
+        );
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let secret: MfaSecret = serde_json::from_str(SECRET).expect("secret to deserialize");
+
+        let debug = format!("{secret:?}");
+
+        assert!(!debug.contains("JBSWY3DPEHPK3PXP"));
+        assert!(!debug.contains("otpauth"));
+        assert!(!format!("{:?}", Code { code: "123456" }).contains("123456"));
+    }
+
+    #[tokio::test]
+    async fn create_returns_the_secret() {
+        let server = Server::start(vec![Response::json(200, SECRET)]).await;
+        let client = server.client();
+
+        let secret = create(&client, &Kind::totp()).await.expect("secret");
+
+        assert_eq!(secret.secret, "JBSWY3DPEHPK3PXP");
+        assert_eq!(server.requests()[0].method, "POST");
+        assert_eq!(server.uris(), vec!["/v2/self/mfa/totp"]);
+    }
+
+    #[tokio::test]
+    async fn confirm_sends_the_code() {
+        let server = Server::start(vec![Response::json(204, "")]).await;
+        let client = server.client();
+
+        confirm(&client, &Kind::totp(), "123456")
+            .await
+            .expect("confirmation");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].uri, "/v2/self/mfa/totp/confirmation");
+        assert_eq!(requests[0].json(), serde_json::json!({ "code": "123456" }));
+        assert!(matches!(
+            confirm(&client, &Kind::totp(), "123456").await,
+            Err(Error::StatusCode(404))
+        ));
+    }
+
+    #[tokio::test]
+    async fn delete_sends_the_code() {
+        let server = Server::start(vec![Response::json(204, "")]).await;
+        let client = server.client();
+
+        delete(&client, &Kind::totp(), "654321")
+            .await
+            .expect("deletion");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "DELETE");
+        assert_eq!(requests[0].uri, "/v2/self/mfa/totp");
+        assert_eq!(requests[0].json(), serde_json::json!({ "code": "654321" }));
+    }
+
+    #[tokio::test]
+    async fn backup_codes_are_listed() {
+        let server = Server::start(vec![Response::json(200, r#"["a1b2c3", "d4e5f6"]"#)]).await;
+        let client = server.client();
+
+        let codes = backup_codes(&client, &Kind::totp()).await.expect("codes");
+
+        assert_eq!(codes, vec!["a1b2c3", "d4e5f6"]);
+        assert_eq!(server.uris(), vec!["/v2/self/mfa/totp/backupcodes"]);
+        assert!(matches!(
+            backup_codes(&client, &Kind::totp()).await,
+            Err(Error::BackupCodes(..))
+        ));
+    }
+}
@@ -0,0 +1,486 @@
+//! # Myself module
+//!
+//! This module provides structures and helpers to interact with the user api
+//! version 2
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::myself::token::OauthToken,
+    v2::{
+        addon::{self, Addon},
+        application::{self, Application},
+        owner::OwnerId,
+        ssh_key::{self, SshKey},
+    },
+    Client,
+};
+
+pub mod mfa;
+pub mod token;
+
+// -----------------------------------------------------------------------------
+// Myself structure and helpers
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, PartialEq, Eq, Deserialize, Clone, Debug)]
+pub struct Myself {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name", default)]
+    pub first_name: Option<String>,
+    #[serde(rename = "surname", default)]
+    pub last_name: Option<String>,
+    #[serde(rename = "email")]
+    pub email: String,
+    #[serde(rename = "phone")]
+    pub phone: String,
+    #[serde(rename = "address")]
+    pub address: String,
+    #[serde(rename = "city")]
+    pub city: String,
+    #[serde(rename = "zipcode")]
+    pub zipcode: String,
+    #[serde(rename = "country")]
+    pub country: String,
+    #[serde(rename = "avatar")]
+    pub avatar: String,
+    #[serde(rename = "creationDate")]
+    pub creation_date: u64,
+    #[serde(rename = "lang")]
+    pub lang: String,
+    #[serde(rename = "emailValidated")]
+    pub email_validated: bool,
+    #[serde(rename = "oauthApps")]
+    pub oauth_apps: Vec<String>,
+    #[serde(rename = "admin")]
+    pub admin: bool,
+    #[serde(rename = "canPay")]
+    pub can_pay: bool,
+    #[serde(rename = "preferredMFA")]
+    pub preferred_mfa: String,
+    #[serde(rename = "hasPassword")]
+    pub has_password: bool,
+}
+
+impl Myself {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the first name of the current user, if set
+    pub fn name(&self) -> Option<&str> {
+        self.first_name.as_deref()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the first and last name of the current user separated by a
+    /// space, missing or empty parts are left out
+    pub fn full_name(&self) -> String {
+        [&self.first_name, &self.last_name]
+            .iter()
+            .filter_map(|part| part.as_deref().map(str::trim))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the current user could create an organisation, which needs
+    /// both a payment method and a validated email address
+    pub fn can_create_organisation(&self) -> bool {
+        self.can_pay && self.email_validated
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the current user still has to validate its email address
+    pub fn requires_email_validation(&self) -> bool {
+        !self.email_validated
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the current user is an administrator of Clever Cloud
+    pub fn is_admin(&self) -> bool {
+        self.admin
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the ssh keys of the current user
+    pub async fn ssh_keys<C>(&self, client: &Client<C>) -> Result<Vec<SshKey>, ssh_key::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        ssh_key::list(client).await
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the list of addons for the given organisation
+    pub async fn addons<C>(
+        &self,
+        client: &Client<C>,
+        organisation_id: &str,
+    ) -> Result<Vec<Addon>, addon::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        addon::list(client, organisation_id).await
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the list of addons of the personal space
+    pub async fn personal_addons<C>(&self, client: &Client<C>) -> Result<Vec<Addon>, addon::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        addon::list_by_owner(client, &OwnerId::Myself).await
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the list of applications for the given organisation
+    pub async fn applications<C>(
+        &self,
+        client: &Client<C>,
+        organisation_id: &str,
+    ) -> Result<Vec<Application>, application::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        application::list(client, &OwnerId::from(organisation_id)).await
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the list of applications of the personal space
+    pub async fn personal_applications<C>(
+        &self,
+        client: &Client<C>,
+    ) -> Result<Vec<Application>, application::Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        application::list(client, &OwnerId::Myself).await
+    }
+}
+
+// -----------------------------------------------------------------------------
+// UpdateOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct UpdateOpts {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "surname", skip_serializing_if = "Option::is_none")]
+    pub surname: Option<String>,
+    #[serde(rename = "phone", skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(rename = "address", skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(rename = "city", skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(rename = "zipcode", skip_serializing_if = "Option::is_none")]
+    pub zipcode: Option<String>,
+    #[serde(rename = "country", skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(rename = "lang", skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+impl UpdateOpts {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns a copy of the options where unset fields are filled with the
+    /// current values of the given user
+    pub fn merge(&self, myself: &Myself) -> Self {
+        Self {
+            name: self
+                .name
+                .to_owned()
+                .or_else(|| myself.first_name.to_owned()),
+            surname: self
+                .surname
+                .to_owned()
+                .or_else(|| myself.last_name.to_owned()),
+            phone: self
+                .phone
+                .to_owned()
+                .or_else(|| Some(myself.phone.to_owned())),
+            address: self
+                .address
+                .to_owned()
+                .or_else(|| Some(myself.address.to_owned())),
+            city: self
+                .city
+                .to_owned()
+                .or_else(|| Some(myself.city.to_owned())),
+            zipcode: self
+                .zipcode
+                .to_owned()
+                .or_else(|| Some(myself.zipcode.to_owned())),
+            country: self
+                .country
+                .to_owned()
+                .or_else(|| Some(myself.country.to_owned())),
+            lang: self
+                .lang
+                .to_owned()
+                .or_else(|| Some(myself.lang.to_owned())),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information about the current user, {0}")]
+    Get(ClientError),
+    #[error("failed to update information about the current user, {0}")]
+    Update(ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the person logged in
+pub async fn get<C>(client: &Client<C>) -> Result<Myself, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self", client.endpoint);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the logged in user, path: '{}'",
+            &path
+        );
+    }
+
+    client.get(&path).await.map_err(Error::Get)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// update information about the person logged in and returns it, as the api
+/// expects the whole profile, unset fields are filled with the current values
+pub async fn update<C>(client: &Client<C>, opts: &UpdateOpts) -> Result<Myself, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let payload = opts.merge(&get(client).await?);
+    let path = format!("{}/v2/self", client.endpoint);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to update information about the logged in user, path: '{}'",
+            &path
+        );
+    }
+
+    client.put(&path, &payload).await.map_err(Error::Update)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of ssh keys of the person logged in
+pub async fn ssh_keys<C>(client: &Client<C>) -> Result<Vec<SshKey>, ssh_key::Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    ssh_key::list(client).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// add the public key under the given name to the person logged in
+pub async fn add_ssh_key<C>(
+    client: &Client<C>,
+    name: &str,
+    public_key: &str,
+) -> Result<(), ssh_key::Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    ssh_key::add(client, name, public_key).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// remove the ssh key with the given name from the person logged in
+pub async fn remove_ssh_key<C>(client: &Client<C>, name: &str) -> Result<(), ssh_key::Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    ssh_key::remove(client, name).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of oauth tokens granted by the person logged in
+pub async fn tokens<C>(client: &Client<C>) -> Result<Vec<OauthToken>, token::Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    token::list(client).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// revoke the given oauth token of the person logged in
+pub async fn revoke_token<C>(client: &Client<C>, token: &str) -> Result<(), token::Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    token::revoke(client, token).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// revoke all oauth tokens of the person logged in, except the ones granted to
+/// the consumer in use, and returns the revoked tokens
+pub async fn revoke_all_tokens<C>(client: &Client<C>) -> Result<Vec<OauthToken>, token::Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    token::revoke_all(client).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const MYSELF: &str = r#"{
+        "id": "user_1b2c3d4e-5f6a-4b7c-8d9e-0f1a2b3c4d5e",
+        "name": "Ada",
+        "surname": "Lovelace",
+        "email": "ada@example.com",
+        "phone": "+33 6 00 00 00 00",
+        "address": "1 rue de la Paix",
+        "city": "Nantes",
+        "zipcode": "44000",
+        "country": "FRANCE",
+        "avatar": "",
+        "creationDate": 1600000000000,
+        "lang": "EN",
+        "emailValidated": true,
+        "oauthApps": [],
+        "admin": false,
+        "canPay": true,
+        "preferredMFA": "NONE",
+        "hasPassword": true
+    }"#;
+
+    fn myself() -> Myself {
+        serde_json::from_str(MYSELF).expect("myself to deserialize")
+    }
+
+    #[test]
+    fn merge_keeps_unchanged_fields() {
+        let opts = UpdateOpts {
+            city: Some("Paris".to_string()),
+            ..Default::default()
+        };
+
+        let merged = opts.merge(&myself());
+
+        assert_eq!(
+            merged,
+            UpdateOpts {
+                name: Some("Ada".to_string()),
+                surname: Some("Lovelace".to_string()),
+                phone: Some("+33 6 00 00 00 00".to_string()),
+                address: Some("1 rue de la Paix".to_string()),
+                city: Some("Paris".to_string()),
+                zipcode: Some("44000".to_string()),
+                country: Some("FRANCE".to_string()),
+                lang: Some("EN".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn merge_leaves_out_names_the_user_never_set() {
+        let mut myself = myself();
+        myself.first_name = None;
+        myself.last_name = None;
+
+        let payload = serde_json::to_value(UpdateOpts::default().merge(&myself))
+            .expect("options to serialize");
+
+        assert!(payload.get("name").is_none());
+        assert!(payload.get("surname").is_none());
+        assert_eq!(payload["city"], "Nantes");
+    }
+
+    #[tokio::test]
+    async fn update_puts_the_merged_profile() {
+        let server = Server::start(vec![
+            Response::json(200, MYSELF),
+            Response::json(200, MYSELF),
+        ])
+        .await;
+        let client = server.client();
+        let opts = UpdateOpts {
+            lang: Some("FR".to_string()),
+            ..Default::default()
+        };
+
+        update(&client, &opts).await.expect("update");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(requests[1].uri, "/v2/self");
+        assert_eq!(
+            requests[1].json(),
+            serde_json::json!({
+                "name": "Ada",
+                "surname": "Lovelace",
+                "phone": "+33 6 00 00 00 00",
+                "address": "1 rue de la Paix",
+                "city": "Nantes",
+                "zipcode": "44000",
+                "country": "FRANCE",
+                "lang": "FR"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn shortcuts_target_the_organisation_or_the_personal_space() {
+        let server = Server::start(vec![
+            Response::json(200, "[]"),
+            Response::json(200, "[]"),
+            Response::json(200, "[]"),
+            Response::json(200, "[]"),
+        ])
+        .await;
+        let client = server.client();
+        let myself = myself();
+
+        myself.addons(&client, "orga_1").await.expect("addons");
+        myself
+            .personal_addons(&client)
+            .await
+            .expect("personal addons");
+        myself
+            .applications(&client, "orga_1")
+            .await
+            .expect("applications");
+        myself
+            .personal_applications(&client)
+            .await
+            .expect("personal applications");
+
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/organisations/orga_1/addons",
+                "/v2/self/addons",
+                "/v2/organisations/orga_1/applications",
+                "/v2/self/applications",
+            ]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! # OAuth token module
+//!
+//! This module provides structures and helpers to list and revoke the oauth
+//! tokens granted by the current user to applications
+
+use std::{collections::BTreeMap, fmt::Debug};
+
+use chrono::{DateTime, Utc};
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Consumer structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Consumer {
+    #[serde(rename = "key")]
+    pub key: String,
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+// -----------------------------------------------------------------------------
+// OauthToken structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct OauthToken {
+    #[serde(rename = "token")]
+    pub token: String,
+    #[serde(rename = "consumer")]
+    pub consumer: Consumer,
+    #[serde(rename = "creationDate")]
+    pub creation_date: DateTime<Utc>,
+    #[serde(rename = "lastUtilisation", default)]
+    pub last_utilisation: Option<DateTime<Utc>>,
+    #[serde(rename = "rights", default)]
+    pub rights: BTreeMap<String, bool>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list oauth tokens of the current user, {0}")]
+    List(ClientError),
+    #[error("failed to revoke oauth token '{0}' of the current user, {1}")]
+    Revoke(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of oauth tokens granted by the person logged in
+pub async fn list<C>(client: &Client<C>) -> Result<Vec<OauthToken>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/tokens", client.endpoint);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list oauth tokens of the logged in user, path: '{}'",
+            &path
+        );
+    }
+
+    client.get(&path).await.map_err(Error::List)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// revoke the given oauth token of the person logged in
+pub async fn revoke<C>(client: &Client<C>, token: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/tokens/{}", client.endpoint, token);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to revoke an oauth token of the logged in user, path: '{}'",
+            &path
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::Revoke(token.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// revoke all oauth tokens of the person logged in and returns the revoked
+/// ones, tokens granted to the consumer configured on the client are kept to
+/// not revoke the access in use
+pub async fn revoke_all<C>(client: &Client<C>) -> Result<Vec<OauthToken>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let consumer_key = client
+        .credentials()
+        .map(|credentials| credentials.consumer_key.to_owned());
+
+    let mut revoked = vec![];
+    for token in list(client).await? {
+        if Some(&token.consumer.key) == consumer_key.as_ref() {
+            continue;
+        }
+
+        revoke(client, &token.token).await?;
+        revoked.push(token);
+    }
+
+    Ok(revoked)
+}
+
+#[cfg(test)]
+mod tests {
+    use oauth10a::client::Credentials;
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const TOKENS: &str = r#"[{
+        "token": "token_cli",
+        "consumer": { "key": "consumer_cli", "name": "Clever Tools" },
+        "creationDate": "2024-01-01T00:00:00Z",
+        "lastUtilisation": "2024-05-01T00:00:00Z",
+        "rights": { "access_organisations": true, "manage_ssh_keys": false }
+    }, {
+        "token": "token_ci",
+        "consumer": { "key": "consumer_ci", "name": "CI" },
+        "creationDate": "2024-02-01T00:00:00Z"
+    }, {
+        "token": "token_grafana",
+        "consumer": { "key": "consumer_grafana", "name": "Grafana" },
+        "creationDate": "2024-03-01T00:00:00Z"
+    }]"#;
+
+    #[tokio::test]
+    async fn list_deserializes_the_tokens() {
+        let server = Server::start(vec![Response::json(200, TOKENS)]).await;
+        let client = server.client();
+
+        let tokens = list(&client).await.expect("tokens");
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0].consumer.name, "Clever Tools");
+        assert!(tokens[0].last_utilisation.is_some());
+        assert_eq!(tokens[0].rights.get("access_organisations"), Some(&true));
+        assert_eq!(tokens[1].last_utilisation, None);
+        assert!(tokens[1].rights.is_empty());
+        assert_eq!(server.uris(), vec!["/v2/self/tokens"]);
+    }
+
+    #[tokio::test]
+    async fn revoke_all_skips_the_token_in_use() {
+        let server = Server::start(vec![
+            Response::json(200, TOKENS),
+            Response::json(204, ""),
+            Response::json(204, ""),
+        ])
+        .await;
+        let mut client = server.client();
+        client.set_credentials(Some(Credentials {
+            token: "token_cli".to_string(),
+            secret: "secret".to_string(),
+            consumer_key: "consumer_cli".to_string(),
+            consumer_secret: "consumer_secret".to_string(),
+        }));
+
+        let revoked = revoke_all(&client).await.expect("revoked tokens");
+
+        assert_eq!(
+            revoked.iter().map(|t| t.token.as_str()).collect::<Vec<_>>(),
+            vec!["token_ci", "token_grafana"]
+        );
+        assert!(server.requests()[1..]
+            .iter()
+            .all(|req| "DELETE" == req.method));
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/self/tokens",
+                "/v2/self/tokens/token_ci",
+                "/v2/self/tokens/token_grafana",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn revoke_all_stops_on_the_first_failure() {
+        let server = Server::start(vec![Response::json(200, TOKENS), Response::error(500)]).await;
+        let client = server.client();
+
+        assert!(matches!(
+            revoke_all(&client).await,
+            Err(Error::Revoke(token, _)) if token == "token_cli"
+        ));
+        assert_eq!(server.uris().len(), 2);
+    }
+}
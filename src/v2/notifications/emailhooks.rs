@@ -0,0 +1,299 @@
+//! # Email hooks module
+//!
+//! This module provides structures and helpers to manage the email alerts sent
+//! on events of an owner
+
+use std::fmt::Debug;
+
+use hyper::{client::connect::Connect, StatusCode};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v2::owner::OwnerId, Client};
+
+// -----------------------------------------------------------------------------
+// EmailTarget enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum EmailTarget {
+    #[serde(rename = "email")]
+    Email {
+        #[serde(rename = "target")]
+        address: String,
+    },
+    #[serde(rename = "organisation")]
+    OrganisationMembers,
+}
+
+// -----------------------------------------------------------------------------
+// EmailHook structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct EmailHook {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "name", default)]
+    pub name: Option<String>,
+    #[serde(rename = "notified")]
+    pub notified: Vec<EmailTarget>,
+    #[serde(rename = "scope", default)]
+    pub scope: Vec<String>,
+    #[serde(rename = "events", default)]
+    pub events: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// EmailHookOpts structure
+
+/// EmailHookOpts structure is the body expected by the api to create an email
+/// hook, an empty scope or list of events stands for all of them
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct EmailHookOpts {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "notified")]
+    pub notified: Vec<EmailTarget>,
+    #[serde(rename = "scope", skip_serializing_if = "Vec::is_empty", default)]
+    pub scope: Vec<String>,
+    #[serde(rename = "events", skip_serializing_if = "Vec::is_empty", default)]
+    pub events: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list email hooks of '{0}', {1}")]
+    List(OwnerId, ClientError),
+    #[error("failed to create email hook for '{0}', {1}")]
+    Create(OwnerId, ClientError),
+    #[error("failed to create email hook for '{0}', the api rejected the hook, {1}")]
+    InvalidHook(OwnerId, String),
+    #[error("failed to delete email hook '{0}' of '{1}', {2}")]
+    Delete(String, OwnerId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of email hooks of the given owner
+pub async fn list<C>(client: &Client<C>, owner: &OwnerId) -> Result<Vec<EmailHook>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/notifications/emailhooks/{}", client.endpoint, owner);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list email hooks, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// create an email hook for the given owner and returns it, a hook rejected by
+/// the api, e.g. because of an unknown event name, is reported as invalid
+pub async fn create<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    opts: &EmailHookOpts,
+) -> Result<EmailHook, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/notifications/emailhooks/{}", client.endpoint, owner);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to create an email hook, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    match client.post(&path, opts).await {
+        Ok(hook) => Ok(hook),
+        Err(ClientError::StatusCode(code, err)) if StatusCode::BAD_REQUEST == code => {
+            Err(Error::InvalidHook(owner.to_owned(), err.to_string()))
+        }
+        Err(err) => Err(Error::Create(owner.to_owned(), err)),
+    }
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// delete the given email hook of the owner
+pub async fn delete<C>(client: &Client<C>, owner: &OwnerId, id: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/notifications/emailhooks/{}/{}",
+        client.endpoint, owner, id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to delete an email hook, path: '{}', owner: '{}', id: '{}'",
+            &path, owner, id
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::Delete(id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const EMAIL_HOOK: &str = r#"{
+        "id": "emailhook_1",
+        "ownerId": "orga_1",
+        "name": "on-call",
+        "notified": [
+            { "type": "email", "target": "oncall@example.com" },
+            { "type": "organisation" }
+        ],
+        "events": ["META_DEPLOYMENT_RESULT"]
+    }"#;
+
+    #[test]
+    fn targets_of_both_kinds_round_trip() {
+        let hook: EmailHook = serde_json::from_str(EMAIL_HOOK).expect("hook to deserialize");
+
+        assert_eq!(
+            hook.notified,
+            vec![
+                EmailTarget::Email {
+                    address: "oncall@example.com".to_string()
+                },
+                EmailTarget::OrganisationMembers,
+            ]
+        );
+        assert!(hook.scope.is_empty());
+        assert_eq!(
+            serde_json::to_value(&hook.notified).expect("targets to serialize"),
+            serde_json::json!([
+                { "type": "email", "target": "oncall@example.com" },
+                { "type": "organisation" }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn create_sends_the_targets() {
+        let server = Server::start(vec![Response::json(200, EMAIL_HOOK)]).await;
+        let client = server.client();
+        let opts = EmailHookOpts {
+            name: Some("on-call".to_string()),
+            notified: vec![
+                EmailTarget::Email {
+                    address: "oncall@example.com".to_string(),
+                },
+                EmailTarget::OrganisationMembers,
+            ],
+            scope: vec![],
+            events: vec!["META_DEPLOYMENT_RESULT".to_string()],
+        };
+
+        let hook = create(&client, &OwnerId::from("orga_1"), &opts)
+            .await
+            .expect("hook");
+
+        assert_eq!(hook.id, "emailhook_1");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].uri, "/v2/notifications/emailhooks/orga_1");
+        assert_eq!(
+            requests[0].json(),
+            serde_json::json!({
+                "name": "on-call",
+                "notified": [
+                    { "type": "email", "target": "oncall@example.com" },
+                    { "type": "organisation" }
+                ],
+                "events": ["META_DEPLOYMENT_RESULT"]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn create_reports_hooks_rejected_by_the_api() {
+        let server = Server::start(vec![
+            Response::json(
+                400,
+                r#"{"id":4000,"message":"unknown event 'DEPLOY_FAILED'","type":"error"}"#,
+            ),
+            Response::error(500),
+        ])
+        .await;
+        let client = server.client();
+        let opts = EmailHookOpts {
+            name: None,
+            notified: vec![EmailTarget::OrganisationMembers],
+            scope: vec![],
+            events: vec!["DEPLOY_FAILED".to_string()],
+        };
+
+        match create(&client, &OwnerId::Myself, &opts).await {
+            Err(Error::InvalidHook(OwnerId::Myself, message)) => {
+                assert_eq!(message, "unknown event 'DEPLOY_FAILED'");
+            }
+            res => panic!("expected an invalid hook, got {res:?}"),
+        }
+
+        assert!(matches!(
+            create(&client, &OwnerId::Myself, &opts).await,
+            Err(Error::Create(OwnerId::Myself, ClientError::StatusCode(..)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_and_delete_target_the_owner() {
+        let server = Server::start(vec![
+            Response::json(200, format!("[{EMAIL_HOOK}]")),
+            Response::json(204, ""),
+        ])
+        .await;
+        let client = server.client();
+
+        let hooks = list(&client, &OwnerId::Myself).await.expect("hooks");
+        assert_eq!(hooks[0].name.as_deref(), Some("on-call"));
+
+        delete(&client, &OwnerId::Myself, "emailhook_1")
+            .await
+            .expect("deletion");
+
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/notifications/emailhooks/self",
+                "/v2/notifications/emailhooks/self/emailhook_1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,7 @@
+//! # Notifications module
+//!
+//! This module expose structures and helpers to interact with the notifications
+//! api version 2
+
+pub mod emailhooks;
+pub mod webhooks;
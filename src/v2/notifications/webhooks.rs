@@ -0,0 +1,355 @@
+//! # Webhooks module
+//!
+//! This module provides structures and helpers to manage the webhooks notified
+//! on events of an owner
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v2::owner::OwnerId, Client};
+
+// -----------------------------------------------------------------------------
+// WebhookFormat enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum WebhookFormat {
+    Raw,
+    Slack,
+    Gitter,
+    Flowdock,
+    Other(String),
+}
+
+impl From<String> for WebhookFormat {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "RAW" => Self::Raw,
+            "SLACK" => Self::Slack,
+            "GITTER" => Self::Gitter,
+            "FLOWDOCK" => Self::Flowdock,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for WebhookFormat {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for WebhookFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Raw => write!(f, "RAW"),
+            Self::Slack => write!(f, "SLACK"),
+            Self::Gitter => write!(f, "GITTER"),
+            Self::Flowdock => write!(f, "FLOWDOCK"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// WebhookUrl structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct WebhookUrl {
+    #[serde(rename = "format")]
+    pub format: WebhookFormat,
+    #[serde(rename = "url")]
+    pub url: String,
+}
+
+// -----------------------------------------------------------------------------
+// Webhook structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Webhook {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "name", default)]
+    pub name: Option<String>,
+    #[serde(rename = "urls")]
+    pub urls: Vec<WebhookUrl>,
+    #[serde(rename = "scope", default)]
+    pub scope: Vec<String>,
+    #[serde(rename = "events", default)]
+    pub events: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// WebhookOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct WebhookOpts {
+    pub name: Option<String>,
+    pub url: String,
+    pub format: WebhookFormat,
+    pub scope: Vec<String>,
+    pub events: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// WebhookPayload structure
+
+/// WebhookPayload structure is the body expected by the api to create a
+/// webhook, an empty scope or list of events stands for all of them
+#[derive(Serialize, PartialEq, Eq, Clone, Debug)]
+struct WebhookPayload<'a> {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(rename = "urls")]
+    urls: Vec<WebhookUrl>,
+    #[serde(rename = "scope", skip_serializing_if = "<[_]>::is_empty")]
+    scope: &'a [String],
+    #[serde(rename = "events", skip_serializing_if = "<[_]>::is_empty")]
+    events: &'a [String],
+}
+
+impl<'a> From<&'a WebhookOpts> for WebhookPayload<'a> {
+    fn from(opts: &'a WebhookOpts) -> Self {
+        Self {
+            name: opts.name.as_deref(),
+            urls: vec![WebhookUrl {
+                format: opts.format.to_owned(),
+                url: opts.url.to_owned(),
+            }],
+            scope: &opts.scope,
+            events: &opts.events,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list webhooks of '{0}', {1}")]
+    List(OwnerId, ClientError),
+    #[error("failed to create webhook for '{0}', {1}")]
+    Create(OwnerId, ClientError),
+    #[error("failed to delete webhook '{0}' of '{1}', {2}")]
+    Delete(String, OwnerId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of webhooks of the given owner
+pub async fn list<C>(client: &Client<C>, owner: &OwnerId) -> Result<Vec<Webhook>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/notifications/webhooks/{}", client.endpoint, owner);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list webhooks, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// create a webhook for the given owner and returns it
+pub async fn create<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    opts: &WebhookOpts,
+) -> Result<Webhook, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/notifications/webhooks/{}", client.endpoint, owner);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to create a webhook, path: '{}', owner: '{}', format: '{}'",
+            &path, owner, &opts.format
+        );
+    }
+
+    client
+        .post(&path, &WebhookPayload::from(opts))
+        .await
+        .map_err(|err| Error::Create(owner.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// delete the given webhook of the owner
+pub async fn delete<C>(client: &Client<C>, owner: &OwnerId, id: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/notifications/webhooks/{}/{}",
+        client.endpoint, owner, id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to delete a webhook, path: '{}', owner: '{}', id: '{}'",
+            &path, owner, id
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::Delete(id.to_owned(), owner.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const WEBHOOK: &str = r#"{
+        "id": "webhook_1",
+        "ownerId": "orga_1",
+        "name": "deployments",
+        "urls": [{ "format": "SLACK", "url": "https://hooks.slack.com/services/T0/B0/X" }],
+        "scope": ["app_1"],
+        "events": ["META_DEPLOYMENT_RESULT"]
+    }"#;
+
+    const MATTERMOST_WEBHOOK: &str = r#"{
+        "id": "webhook_2",
+        "ownerId": "orga_1",
+        "urls": [{ "format": "MATTERMOST", "url": "https://chat.example.com/hooks/1" }]
+    }"#;
+
+    /// returns the options of a slack webhook notified of failed deployments
+    fn slack_opts() -> WebhookOpts {
+        WebhookOpts {
+            name: Some("deployments".to_string()),
+            url: "https://hooks.slack.com/services/T0/B0/X".to_string(),
+            format: WebhookFormat::Slack,
+            scope: vec!["app_1".to_string()],
+            events: vec!["META_DEPLOYMENT_RESULT".to_string()],
+        }
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        for format in ["RAW", "SLACK", "GITTER", "FLOWDOCK", "MATTERMOST"] {
+            assert_eq!(WebhookFormat::from(format.to_string()).to_string(), format);
+        }
+
+        assert_eq!(
+            WebhookFormat::from("MATTERMOST".to_string()),
+            WebhookFormat::Other("MATTERMOST".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn list_deserializes_the_webhooks() {
+        let server = Server::start(vec![Response::json(
+            200,
+            format!("[{WEBHOOK}, {MATTERMOST_WEBHOOK}]"),
+        )])
+        .await;
+        let client = server.client();
+
+        let webhooks = list(&client, &OwnerId::from("orga_1"))
+            .await
+            .expect("webhooks");
+
+        assert_eq!(webhooks.len(), 2);
+        assert_eq!(webhooks[0].name.as_deref(), Some("deployments"));
+        assert_eq!(webhooks[0].urls[0].format, WebhookFormat::Slack);
+        assert_eq!(webhooks[0].events, vec!["META_DEPLOYMENT_RESULT"]);
+        assert_eq!(webhooks[1].name, None);
+        assert!(webhooks[1].scope.is_empty());
+        assert_eq!(
+            webhooks[1].urls[0].format,
+            WebhookFormat::Other("MATTERMOST".to_string())
+        );
+        assert_eq!(server.uris(), vec!["/v2/notifications/webhooks/orga_1"]);
+    }
+
+    #[tokio::test]
+    async fn create_sends_the_expected_payload() {
+        let server = Server::start(vec![
+            Response::json(200, WEBHOOK),
+            Response::json(200, WEBHOOK),
+        ])
+        .await;
+        let client = server.client();
+        let owner = OwnerId::from("orga_1");
+
+        let webhook = create(&client, &owner, &slack_opts())
+            .await
+            .expect("webhook");
+        assert_eq!(webhook.id, "webhook_1");
+
+        let opts = WebhookOpts {
+            name: None,
+            scope: vec![],
+            events: vec![],
+            ..slack_opts()
+        };
+        create(&client, &owner, &opts).await.expect("webhook");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].uri, "/v2/notifications/webhooks/orga_1");
+        assert_eq!(
+            requests[0].json(),
+            serde_json::json!({
+                "name": "deployments",
+                "urls": [{ "format": "SLACK", "url": "https://hooks.slack.com/services/T0/B0/X" }],
+                "scope": ["app_1"],
+                "events": ["META_DEPLOYMENT_RESULT"]
+            })
+        );
+        assert_eq!(
+            requests[1].json(),
+            serde_json::json!({
+                "urls": [{ "format": "SLACK", "url": "https://hooks.slack.com/services/T0/B0/X" }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_targets_the_webhook_of_the_owner() {
+        let server = Server::start(vec![Response::json(204, "")]).await;
+        let client = server.client();
+
+        delete(&client, &OwnerId::Myself, "webhook_1")
+            .await
+            .expect("deletion");
+
+        assert_eq!(server.requests()[0].method, "DELETE");
+        assert_eq!(
+            server.uris(),
+            vec!["/v2/notifications/webhooks/self/webhook_1"]
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! # Activity module
+//!
+//! This module expose structures and helpers to read the activity log of an
+//! organisation and to browse the audit log of an owner
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+use percent_encoding::utf8_percent_encode;
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::{logs::QUERY_VALUE, owner::OwnerId},
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// Activity structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Activity {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "action")]
+    pub action: String,
+    #[serde(rename = "source")]
+    pub source: String,
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    #[serde(rename = "resourceId")]
+    pub resource_id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+// -----------------------------------------------------------------------------
+// AuditAction enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum AuditAction {
+    Login,
+    Deployment,
+    EnvironmentUpdate,
+    ApplicationCreation,
+    ApplicationDeletion,
+    AddonCreation,
+    AddonDeletion,
+    MemberInvitation,
+    MemberRemoval,
+    Other(String),
+}
+
+impl From<String> for AuditAction {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "LOGIN" => Self::Login,
+            "DEPLOYMENT" => Self::Deployment,
+            "ENVIRONMENT_UPDATE" => Self::EnvironmentUpdate,
+            "APPLICATION_CREATION" => Self::ApplicationCreation,
+            "APPLICATION_DELETION" => Self::ApplicationDeletion,
+            "ADDON_CREATION" => Self::AddonCreation,
+            "ADDON_DELETION" => Self::AddonDeletion,
+            "MEMBER_INVITATION" => Self::MemberInvitation,
+            "MEMBER_REMOVAL" => Self::MemberRemoval,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for AuditAction {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Login => write!(f, "LOGIN"),
+            Self::Deployment => write!(f, "DEPLOYMENT"),
+            Self::EnvironmentUpdate => write!(f, "ENVIRONMENT_UPDATE"),
+            Self::ApplicationCreation => write!(f, "APPLICATION_CREATION"),
+            Self::ApplicationDeletion => write!(f, "APPLICATION_DELETION"),
+            Self::AddonCreation => write!(f, "ADDON_CREATION"),
+            Self::AddonDeletion => write!(f, "ADDON_DELETION"),
+            Self::MemberInvitation => write!(f, "MEMBER_INVITATION"),
+            Self::MemberRemoval => write!(f, "MEMBER_REMOVAL"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// UserRef structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct UserRef {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "email", default)]
+    pub email: Option<String>,
+    #[serde(rename = "name", default)]
+    pub name: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// AuditEntry structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct AuditEntry {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "date")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "actor")]
+    pub actor: UserRef,
+    #[serde(rename = "action")]
+    pub action: AuditAction,
+    #[serde(rename = "resourceId", default)]
+    pub resource_id: Option<String>,
+    #[serde(rename = "ip", default)]
+    pub ip: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// AuditFilter structure
+
+/// AuditFilter structure restricts the entries of the audit log, entries are
+/// returned from the most recent one and `cursor` is the identifier of the
+/// entry after which the page starts
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct AuditFilter {
+    #[serde(rename = "from", skip_serializing_if = "Option::is_none")]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(rename = "to", skip_serializing_if = "Option::is_none")]
+    pub to: Option<DateTime<Utc>>,
+    #[serde(rename = "limit", skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(rename = "cursor", skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl AuditFilter {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the filter as a query string including the leading '?', or an
+    /// empty string if no criteria is set
+    pub fn to_query(&self) -> String {
+        let mut query = vec![];
+        if let Some(from) = &self.from {
+            query.push(format!(
+                "from={}",
+                from.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
+
+        if let Some(to) = &self.to {
+            query.push(format!(
+                "to={}",
+                to.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
+
+        if let Some(limit) = self.limit {
+            query.push(format!("limit={limit}"));
+        }
+
+        if let Some(cursor) = &self.cursor {
+            query.push(format!(
+                "cursor={}",
+                utf8_percent_encode(cursor, QUERY_VALUE)
+            ));
+        }
+
+        if query.is_empty() {
+            return String::new();
+        }
+
+        format!("?{}", query.join("&"))
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the filter of the page following the given entries, if the page
+    /// was full and more entries could be available
+    pub fn next_page(&self, entries: &[AuditEntry]) -> Option<Self> {
+        let last = entries.last()?;
+        if let Some(limit) = self.limit {
+            if entries.len() < limit as usize {
+                return None;
+            }
+        }
+
+        Some(Self {
+            cursor: Some(last.id.to_owned()),
+            ..self.to_owned()
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list activities of organisation '{0}', {1}")]
+    List(String, ClientError),
+    #[error("failed to list audit log entries of '{0}', {1}")]
+    Audit(OwnerId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the activities of the organisation, most recent first, the page is
+/// selected using the limit and offset if any
+pub async fn list<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<Activity>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let mut query = vec![];
+    if let Some(limit) = limit {
+        query.push(format!("limit={limit}"));
+    }
+
+    if let Some(offset) = offset {
+        query.push(format!("offset={offset}"));
+    }
+
+    let mut path = format!(
+        "{}/v2/organisations/{}/activities",
+        client.endpoint, organisation_id
+    );
+
+    if !query.is_empty() {
+        path = format!("{}?{}", path, query.join("&"));
+    }
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list activities of an organisation, path: '{}', organisation: '{}'",
+            &path, organisation_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the entries of the audit log of the given owner matching the
+/// filter, see [`AuditFilter::next_page`] to browse the following ones
+pub async fn audit<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    filter: &AuditFilter,
+) -> Result<Vec<AuditEntry>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/{}/audit-logs{}",
+        client.endpoint,
+        owner.path(),
+        filter.to_query()
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list audit log entries, path: '{}', owner: '{}'",
+            &path, owner
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Audit(owner.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const AUDIT_ENTRIES: &str = r#"[
+        {
+            "id": "audit_2",
+            "date": "2024-03-01T10:00:00Z",
+            "actor": { "id": "user_1", "email": "jane@example.com" },
+            "action": "ENVIRONMENT_UPDATE",
+            "resourceId": "app_1",
+            "ip": "192.0.2.1"
+        },
+        {
+            "id": "audit_1",
+            "date": "2024-02-29T09:00:00Z",
+            "actor": { "id": "user_2" },
+            "action": "TOKEN_REVOCATION"
+        }
+    ]"#;
+
+    #[test]
+    fn deserialize_audit_entries() {
+        let entries: Vec<AuditEntry> =
+            serde_json::from_str(AUDIT_ENTRIES).expect("entries to deserialize");
+
+        assert_eq!(entries[0].action, AuditAction::EnvironmentUpdate);
+        assert_eq!(entries[0].actor.email.as_deref(), Some("jane@example.com"));
+        assert_eq!(entries[0].ip.as_deref(), Some("192.0.2.1"));
+        assert_eq!(
+            entries[1].action,
+            AuditAction::Other("TOKEN_REVOCATION".to_string())
+        );
+        assert_eq!(entries[1].actor.name, None);
+        assert_eq!(entries[1].resource_id, None);
+        assert_eq!(
+            serde_json::to_value(&entries[1].action).expect("action to serialize"),
+            "TOKEN_REVOCATION"
+        );
+    }
+
+    #[test]
+    fn filter_query_is_encoded() {
+        assert_eq!(AuditFilter::default().to_query(), "");
+
+        let filter = AuditFilter {
+            from: Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).single(),
+            to: None,
+            limit: Some(2),
+            cursor: Some("audit 1&limit=100".to_string()),
+        };
+
+        assert_eq!(
+            filter.to_query(),
+            "?from=2024-02-01T00:00:00.000Z&limit=2&cursor=audit%201%26limit%3D100"
+        );
+    }
+
+    #[test]
+    fn next_page_starts_after_the_last_entry() {
+        let entries: Vec<AuditEntry> =
+            serde_json::from_str(AUDIT_ENTRIES).expect("entries to deserialize");
+        let filter = AuditFilter {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let next = filter.next_page(&entries).expect("a next page");
+        assert_eq!(next.cursor.as_deref(), Some("audit_1"));
+        assert_eq!(next.limit, Some(2));
+
+        assert_eq!(next.next_page(&entries[..1]), None);
+        assert_eq!(next.next_page(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn audit_targets_the_path_of_the_owner() {
+        let server = Server::start(vec![
+            Response::json(200, AUDIT_ENTRIES),
+            Response::json(200, "[]"),
+        ])
+        .await;
+        let client = server.client();
+        let filter = AuditFilter {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let entries = audit(&client, &OwnerId::from("orga_1"), &filter)
+            .await
+            .expect("audit log");
+        let next = filter.next_page(&entries).expect("a next page");
+        assert!(audit(&client, &OwnerId::Myself, &next)
+            .await
+            .expect("audit log")
+            .is_empty());
+        assert!(matches!(
+            audit(&client, &OwnerId::Myself, &filter).await,
+            Err(Error::Audit(OwnerId::Myself, ClientError::StatusCode(..)))
+        ));
+
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v2/organisations/orga_1/audit-logs?limit=2",
+                "/v2/self/audit-logs?limit=2&cursor=audit_1",
+                "/v2/self/audit-logs?limit=2",
+            ]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! # Organisation module
+//!
+//! This module expose structures and helpers to interact with the organisation
+//! api version 2
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use hyper::{
+    body::{Buf, Bytes},
+    client::connect::Connect,
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Body, Method,
+};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v2::application::Application, Client};
+
+pub mod activity;
+pub mod quota;
+
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Maximum size in bytes of an avatar accepted by the api
+pub const MAX_AVATAR_SIZE: usize = 2 * 1024 * 1024;
+
+// -----------------------------------------------------------------------------
+// Avatar structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Avatar {
+    #[serde(rename = "url")]
+    pub url: String,
+}
+
+// -----------------------------------------------------------------------------
+// OrganisationTier enumeration
+
+/// OrganisationTier enumeration is the subscription level of an organisation,
+/// tiers are ordered from the lowest to the highest one
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum OrganisationTier {
+    Free,
+    Startup,
+    Business,
+    Enterprise,
+}
+
+impl Display for OrganisationTier {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Free => write!(f, "free"),
+            Self::Startup => write!(f, "startup"),
+            Self::Business => write!(f, "business"),
+            Self::Enterprise => write!(f, "enterprise"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Organisation structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Organisation {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "description", default)]
+    pub description: Option<String>,
+    #[serde(rename = "billingEmail", default)]
+    pub billing_email: Option<String>,
+    #[serde(rename = "avatar", default)]
+    pub avatar: Option<String>,
+    #[serde(rename = "plan", default)]
+    pub plan: Option<String>,
+    #[serde(rename = "cleverEnterprise", default)]
+    pub clever_enterprise: bool,
+}
+
+impl Organisation {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the tier of the organisation derived from its plan, an unknown
+    /// or missing plan stands for the free tier unless the organisation is
+    /// flagged as an enterprise one
+    pub fn tier(&self) -> OrganisationTier {
+        if self.clever_enterprise {
+            return OrganisationTier::Enterprise;
+        }
+
+        match self.plan.as_deref().map(str::to_lowercase).as_deref() {
+            Some("startup") => OrganisationTier::Startup,
+            Some("business") => OrganisationTier::Business,
+            Some("enterprise") => OrganisationTier::Enterprise,
+            _ => OrganisationTier::Free,
+        }
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the organisation could use dedicated resources, which is
+    /// granted from the business tier
+    pub fn can_use_dedicated(&self) -> bool {
+        self.tier() >= OrganisationTier::Business
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to set avatar of organisation '{0}', payload of {1} bytes exceeds the limit of {MAX_AVATAR_SIZE} bytes")]
+    AvatarTooLarge(String, usize),
+    #[error("failed to set avatar of organisation '{0}', {1}")]
+    SetAvatar(String, ClientError),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to execute request, got status code {0}")]
+    StatusCode(u16),
+    #[error("failed to aggregate body, {0}")]
+    BodyAggregation(hyper::Error),
+    #[error("failed to deserialize response payload, {0}")]
+    Deserialize(serde_json::Error),
+    #[error("failed to get organisation '{0}', {1}")]
+    Get(String, ClientError),
+    #[error("failed to list applications of organisation '{0}', {1}")]
+    ListApplications(String, ClientError),
+    #[error("failed to get application '{0}' of organisation '{1}', {2}")]
+    GetApplication(String, String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the organisation of the given identifier
+pub async fn get<C>(client: &Client<C>, organisation_id: &str) -> Result<Organisation, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/organisations/{}", client.endpoint, organisation_id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get an organisation, path: '{}', organisation: '{}'",
+            &path, organisation_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument(skip(image)))]
+/// upload the image as the avatar of the organisation and returns the new
+/// avatar
+pub async fn set_avatar<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    image: Bytes,
+    content_type: &str,
+) -> Result<Avatar, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    if image.len() > MAX_AVATAR_SIZE {
+        return Err(Error::AvatarTooLarge(
+            organisation_id.to_owned(),
+            image.len(),
+        ));
+    }
+
+    let path = format!(
+        "{}/v2/organisations/{}/avatar",
+        client.endpoint, organisation_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to set the avatar of an organisation, path: '{}', organisation: '{}', content-type: '{}', size: {}",
+            &path, organisation_id, content_type, image.len()
+        );
+    }
+
+    let req = hyper::Request::builder()
+        .method(Method::PUT)
+        .uri(&path)
+        .header(CONTENT_TYPE, content_type)
+        .header(CONTENT_LENGTH, image.len())
+        .body(Body::from(image))
+        .map_err(Error::Request)?;
+
+    let res = client
+        .execute(req)
+        .await
+        .map_err(|err| Error::SetAvatar(organisation_id.to_owned(), err))?;
+
+    let status = res.status();
+    if !status.is_success() {
+        return Err(Error::StatusCode(status.as_u16()));
+    }
+
+    let buf = hyper::body::aggregate(res.into_body())
+        .await
+        .map_err(Error::BodyAggregation)?;
+
+    serde_json::from_reader(buf.reader()).map_err(Error::Deserialize)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of applications of the organisation
+pub async fn list_applications<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+) -> Result<Vec<Application>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/applications",
+        client.endpoint, organisation_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list applications of an organisation, path: '{}', organisation: '{}'",
+            &path, organisation_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::ListApplications(organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the given application of the organisation
+pub async fn get_application<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    application_id: &str,
+) -> Result<Application, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/applications/{}",
+        client.endpoint, organisation_id, application_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get an application of an organisation, path: '{}', organisation: '{}', application: '{}'",
+            &path, organisation_id, application_id
+        );
+    }
+
+    client.get(&path).await.map_err(|err| {
+        Error::GetApplication(application_id.to_owned(), organisation_id.to_owned(), err)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// a transparent png of one pixel
+    const PNG: &[u8] = b"\x89\x50\x4e\x47\x0d\x0a\x1a\x0a\x00\x00\x00\x0d\x49\x48\x44\x52\
+          \x00\x00\x00\x01\x00\x00\x00\x01\x08\x06\x00\x00\x00\x1f\x15\xc4\
+          \x89\x00\x00\x00\x0d\x49\x44\x41\x54\x78\xda\x63\x64\x60\xf8\x5f\
+          \x0f\x00\x02\x87\x01\x80\xeb\x47\xba\x92\x00\x00\x00\x00\x49\x45\
+          \x4e\x44\xae\x42\x60\x82";
+
+    #[tokio::test]
+    async fn set_avatar_uploads_the_image() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"{"url":"https://assets.clever-cloud.com/avatars/orga_1.png"}"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let avatar = set_avatar(&client, "orga_1", Bytes::from_static(PNG), "image/png")
+            .await
+            .expect("avatar");
+
+        assert_eq!(
+            avatar.url,
+            "https://assets.clever-cloud.com/avatars/orga_1.png"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].uri, "/v2/organisations/orga_1/avatar");
+        assert_eq!(requests[0].headers[CONTENT_TYPE], "image/png");
+        assert_eq!(requests[0].headers[CONTENT_LENGTH], PNG.len().to_string());
+        assert_eq!(requests[0].bytes.len(), PNG.len());
+        assert_eq!(requests[0].bytes, PNG);
+    }
+
+    #[tokio::test]
+    async fn set_avatar_rejects_large_images_before_sending_them() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+        let image = Bytes::from(vec![0; MAX_AVATAR_SIZE + 1]);
+
+        assert!(matches!(
+            set_avatar(&client, "orga_1", image, "image/png").await,
+            Err(Error::AvatarTooLarge(_, size)) if size == MAX_AVATAR_SIZE + 1
+        ));
+        assert!(server.requests().is_empty());
+        assert!(matches!(
+            set_avatar(&client, "orga_1", Bytes::from_static(PNG), "image/png").await,
+            Err(Error::StatusCode(404))
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! # Quota module
+//!
+//! This module expose structures and helpers to check the resource quotas of an
+//! organisation before provisioning
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Quota structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct Quota {
+    #[serde(rename = "used")]
+    pub used: u64,
+    #[serde(rename = "max")]
+    pub max: u64,
+}
+
+impl Quota {
+    /// returns true, if no more resource could be provisioned
+    pub fn is_exhausted(&self) -> bool {
+        self.used >= self.max
+    }
+}
+
+// -----------------------------------------------------------------------------
+// OrgQuotas structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct OrgQuotas {
+    #[serde(rename = "applications")]
+    pub applications: Quota,
+    #[serde(rename = "addons")]
+    pub addons: Quota,
+    #[serde(rename = "members")]
+    pub members: Quota,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get quotas of organisation '{0}', {1}")]
+    Get(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the quotas of the organisation
+pub async fn get<C>(client: &Client<C>, organisation_id: &str) -> Result<OrgQuotas, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/organisations/{}/quotas",
+        client.endpoint, organisation_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get quotas of an organisation, path: '{}', organisation: '{}'",
+            &path, organisation_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(organisation_id.to_owned(), err))
+}
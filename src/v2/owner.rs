@@ -0,0 +1,52 @@
+//! # Owner module
+//!
+//! This module expose the owner of resources, which is either the person
+//! logged in through its personal space or an organisation
+
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// -----------------------------------------------------------------------------
+// OwnerId enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub enum OwnerId {
+    Myself,
+    Organisation(String),
+}
+
+impl From<&str> for OwnerId {
+    fn from(organisation_id: &str) -> Self {
+        Self::Organisation(organisation_id.to_owned())
+    }
+}
+
+impl From<String> for OwnerId {
+    fn from(organisation_id: String) -> Self {
+        Self::Organisation(organisation_id)
+    }
+}
+
+impl Display for OwnerId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Myself => write!(f, "self"),
+            Self::Organisation(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+impl OwnerId {
+    /// returns the path segment under which resources of the owner are exposed,
+    /// e.g. 'self' or 'organisations/orga_xxx'
+    pub fn path(&self) -> String {
+        match self {
+            Self::Myself => "self".to_string(),
+            Self::Organisation(id) => format!("organisations/{id}"),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! # SSH key module
+//!
+//! This module expose structures and helpers to interact with the ssh keys of
+//! the current user api version 2
+
+use std::fmt::Debug;
+
+use hyper::{
+    client::connect::Connect,
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Body, Method, StatusCode,
+};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request, RestClient};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Characters to encode in the name of a key when used as a path segment
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+// -----------------------------------------------------------------------------
+// SshKey structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SshKey {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "key")]
+    pub key: String,
+    #[serde(rename = "fingerprint")]
+    pub fingerprint: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list ssh keys of the current user, {0}")]
+    List(ClientError),
+    #[error("failed to add ssh key '{0}' to the current user, {1}")]
+    Add(String, ClientError),
+    #[error("failed to add ssh key '{0}' to the current user, a key with the same name or content already exists")]
+    AlreadyExists(String),
+    #[error("failed to remove ssh key '{0}' of the current user, {1}")]
+    Remove(String, ClientError),
+    #[error("failed to serialize ssh key '{0}', {1}")]
+    Serialize(String, serde_json::Error),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to aggregate body, {0}")]
+    BodyAggregation(hyper::Error),
+    #[error("failed to execute request, got status code {0}")]
+    StatusCode(u16),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of ssh keys of the person logged in
+pub async fn list<C>(client: &Client<C>) -> Result<Vec<SshKey>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/self/keys", client.endpoint);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list ssh keys of the logged in user, path: '{}'",
+            &path
+        );
+    }
+
+    client.get(&path).await.map_err(Error::List)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// add the public key under the given name to the person logged in
+pub async fn add<C>(client: &Client<C>, name: &str, public_key: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/self/keys/{}",
+        client.endpoint,
+        utf8_percent_encode(name, PATH_SEGMENT)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to add a ssh key to the logged in user, path: '{}', name: '{}'",
+            &path, name
+        );
+    }
+
+    let buf =
+        serde_json::to_vec(public_key).map_err(|err| Error::Serialize(name.to_owned(), err))?;
+
+    let req = hyper::Request::builder()
+        .method(Method::PUT)
+        .uri(&path)
+        .header(CONTENT_TYPE, "application/json")
+        .header(CONTENT_LENGTH, buf.len())
+        .body(Body::from(buf))
+        .map_err(Error::Request)?;
+
+    let res = client
+        .execute(req)
+        .await
+        .map_err(|err| Error::Add(name.to_owned(), err))?;
+
+    let status = res.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    if StatusCode::CONFLICT == status {
+        return Err(Error::AlreadyExists(name.to_owned()));
+    }
+
+    let body = hyper::body::to_bytes(res.into_body())
+        .await
+        .map_err(Error::BodyAggregation)?;
+
+    if String::from_utf8_lossy(&body)
+        .to_lowercase()
+        .contains("already exists")
+    {
+        return Err(Error::AlreadyExists(name.to_owned()));
+    }
+
+    Err(Error::StatusCode(status.as_u16()))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// remove the ssh key with the given name from the person logged in
+pub async fn remove<C>(client: &Client<C>, name: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v2/self/keys/{}",
+        client.endpoint,
+        utf8_percent_encode(name, PATH_SEGMENT)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to remove a ssh key of the logged in user, path: '{}', name: '{}'",
+            &path, name
+        );
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::Remove(name.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PUBLIC_KEY: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGqz jane@laptop";
+
+    #[tokio::test]
+    async fn list_returns_the_keys_of_the_current_user() {
+        let server = Server::start(vec![Response::json(
+            200,
+            format!(r#"[{{"name":"laptop","key":"{PUBLIC_KEY}","fingerprint":"SHA256:abc"}}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        let keys = list(&client).await.expect("keys");
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].name, "laptop");
+        assert_eq!(keys[0].key, PUBLIC_KEY);
+        assert_eq!(keys[0].fingerprint, "SHA256:abc");
+        assert_eq!(server.uris(), vec!["/v2/self/keys"]);
+    }
+
+    #[tokio::test]
+    async fn add_puts_the_key_under_its_encoded_name() {
+        let server = Server::start(vec![Response::json(200, "")]).await;
+        let client = server.client();
+
+        add(&client, "jane's laptop/work", PUBLIC_KEY)
+            .await
+            .expect("key to be added");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].uri, "/v2/self/keys/jane's%20laptop%2Fwork");
+        assert_eq!(requests[0].headers[CONTENT_TYPE], "application/json");
+        assert_eq!(requests[0].json(), PUBLIC_KEY);
+    }
+
+    #[tokio::test]
+    async fn add_reports_existing_keys() {
+        let server = Server::start(vec![
+            Response::error(409),
+            Response::json(
+                400,
+                r#"{"id":505,"message":"This SSH key already exists","type":"error"}"#,
+            ),
+            Response::error(500),
+        ])
+        .await;
+        let client = server.client();
+
+        for _ in 0..2 {
+            assert!(matches!(
+                add(&client, "laptop", PUBLIC_KEY).await,
+                Err(Error::AlreadyExists(name)) if name == "laptop"
+            ));
+        }
+
+        assert!(matches!(
+            add(&client, "laptop", PUBLIC_KEY).await,
+            Err(Error::StatusCode(500))
+        ));
+    }
+
+    #[tokio::test]
+    async fn remove_deletes_the_key_under_its_encoded_name() {
+        let server = Server::start(vec![Response::json(204, "")]).await;
+        let client = server.client();
+
+        remove(&client, "old laptop")
+            .await
+            .expect("key to be removed");
+
+        assert_eq!(server.requests()[0].method, "DELETE");
+        assert_eq!(server.uris(), vec!["/v2/self/keys/old%20laptop"]);
+        assert!(matches!(
+            remove(&client, "old laptop").await,
+            Err(Error::Remove(..))
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+//! # Summary module
+//!
+//! This module expose structures and helpers to retrieve in a single call the
+//! current user and its organisations with their resources from the api
+//! version 2
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Prefix of the identifier of the personal space of a user
+pub const PERSONAL_SPACE_PREFIX: &str = "user_";
+
+// -----------------------------------------------------------------------------
+// SummaryUser structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SummaryUser {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name", default)]
+    pub name: Option<String>,
+    #[serde(rename = "email")]
+    pub email: String,
+    #[serde(rename = "avatar", default)]
+    pub avatar: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// SummaryApplication structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SummaryApplication {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "variantLogo")]
+    pub variant_logo: String,
+    #[serde(rename = "state")]
+    pub state: String,
+}
+
+// -----------------------------------------------------------------------------
+// SummaryAddon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SummaryAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name", default)]
+    pub name: Option<String>,
+    #[serde(rename = "realId")]
+    pub real_id: String,
+    #[serde(rename = "providerId")]
+    pub provider_id: String,
+    #[serde(rename = "providerLogo")]
+    pub provider_logo: String,
+    #[serde(rename = "planSlug")]
+    pub plan_slug: String,
+}
+
+// -----------------------------------------------------------------------------
+// SummaryConsumer structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SummaryConsumer {
+    #[serde(rename = "key")]
+    pub key: String,
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+// -----------------------------------------------------------------------------
+// SummaryOrganisation structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SummaryOrganisation {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "avatar", default)]
+    pub avatar: Option<String>,
+    #[serde(rename = "applications", default)]
+    pub applications: Vec<SummaryApplication>,
+    #[serde(rename = "addons", default)]
+    pub addons: Vec<SummaryAddon>,
+    #[serde(rename = "consumers", default)]
+    pub consumers: Vec<SummaryConsumer>,
+    #[serde(rename = "oauthApps", default)]
+    pub oauth_apps: Vec<SummaryConsumer>,
+}
+
+impl SummaryOrganisation {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the organisation is the personal space of the user
+    pub fn is_personal_space(&self) -> bool {
+        self.id.starts_with(PERSONAL_SPACE_PREFIX)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Summary structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Summary {
+    #[serde(rename = "user")]
+    pub user: SummaryUser,
+    #[serde(rename = "organisations", default)]
+    pub organisations: Vec<SummaryOrganisation>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get summary of the current user, {0}")]
+    Get(ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the person logged in with its organisations, applications, addons
+/// and consumers
+pub async fn get<C>(client: &Client<C>) -> Result<Summary, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!("{}/v2/summary", client.endpoint);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get the summary of the logged in user, path: '{}'",
+            &path
+        );
+    }
+
+    client.get(&path).await.map_err(Error::Get)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// summary of a user owning a personal space and member of an
+    /// organisation, fields which are not modelled are kept to ensure that
+    /// they are ignored
+    const SUMMARY: &str = r#"{
+        "user": {
+            "id": "user_8f2b1c",
+            "name": "Jane Doe",
+            "email": "jane@example.com",
+            "avatar": "https://assets.clever-cloud.com/avatars/user_8f2b1c.png",
+            "lang": "EN",
+            "emailValidated": true,
+            "oauthApps": [],
+            "admin": false,
+            "canPay": true
+        },
+        "organisations": [{
+            "id": "user_8f2b1c",
+            "name": "Jane Doe",
+            "avatar": null,
+            "applications": [{
+                "id": "app_1a2b3c",
+                "name": "blog",
+                "variantSlug": "node",
+                "variantLogo": "https://assets.clever-cloud.com/logos/nodejs.svg",
+                "state": "SHOULD_BE_UP",
+                "commitId": "5f2e1a",
+                "instanceType": "node",
+                "homogeneous": true
+            }],
+            "addons": [],
+            "consumers": [],
+            "oauthApps": [],
+            "role": "ADMIN",
+            "canPay": true,
+            "cleverEnterprise": false,
+            "emergencyNumber": null,
+            "canSEPA": false,
+            "isTrusted": false
+        }, {
+            "id": "orga_4d5e6f",
+            "name": "ACME",
+            "avatar": "https://assets.clever-cloud.com/avatars/orga_4d5e6f.png",
+            "applications": [{
+                "id": "app_4d5e6f",
+                "name": "api",
+                "variantSlug": "rust",
+                "variantLogo": "https://assets.clever-cloud.com/logos/rust.svg",
+                "state": "WANTS_TO_BE_UP",
+                "commitId": null,
+                "instanceType": "rust",
+                "homogeneous": false
+            }, {
+                "id": "app_7a8b9c",
+                "name": "worker",
+                "variantSlug": "docker",
+                "variantLogo": "https://assets.clever-cloud.com/logos/docker.svg",
+                "state": "SHOULD_BE_DOWN",
+                "instanceType": "docker",
+                "homogeneous": true
+            }],
+            "addons": [{
+                "id": "addon_1b2c3d",
+                "name": "database",
+                "realId": "postgresql_1b2c3d",
+                "providerId": "postgresql-addon",
+                "providerLogo": "https://assets.clever-cloud.com/logos/pgsql.svg",
+                "planSlug": "xs_sml"
+            }, {
+                "id": "addon_4e5f6a",
+                "realId": "redis_4e5f6a",
+                "providerId": "redis-addon",
+                "providerLogo": "https://assets.clever-cloud.com/logos/redis.svg",
+                "planSlug": "s_mono"
+            }],
+            "consumers": [{
+                "key": "consumer_1",
+                "name": "deploy bot",
+                "url": "https://acme.example.com",
+                "picture": null
+            }],
+            "oauthApps": [{ "key": "consumer_2", "name": "grafana" }],
+            "role": "DEVELOPER",
+            "canPay": false,
+            "cleverEnterprise": true,
+            "emergencyNumber": "+33 1 23 45 67 89",
+            "canSEPA": true,
+            "isTrusted": true
+        }]
+    }"#;
+
+    #[test]
+    fn deserialize_summary_with_personal_space_and_organisation() {
+        let summary: Summary = serde_json::from_str(SUMMARY).expect("summary to deserialize");
+
+        assert_eq!(summary.user.id, "user_8f2b1c");
+        assert_eq!(summary.user.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(summary.organisations.len(), 2);
+
+        let personal = &summary.organisations[0];
+        assert!(personal.is_personal_space());
+        assert_eq!(personal.avatar, None);
+        assert_eq!(personal.applications[0].state, "SHOULD_BE_UP");
+        assert!(personal.addons.is_empty());
+
+        let acme = &summary.organisations[1];
+        assert!(!acme.is_personal_space());
+        assert_eq!(
+            acme.applications
+                .iter()
+                .map(|app| app.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["api", "worker"]
+        );
+        assert_eq!(
+            acme.applications[0].variant_logo,
+            "https://assets.clever-cloud.com/logos/rust.svg"
+        );
+        assert_eq!(acme.addons[0].name.as_deref(), Some("database"));
+        assert_eq!(acme.addons[0].real_id, "postgresql_1b2c3d");
+        assert_eq!(acme.addons[1].name, None);
+        assert_eq!(acme.addons[1].plan_slug, "s_mono");
+        assert_eq!(acme.consumers[0].name, "deploy bot");
+        assert_eq!(acme.oauth_apps[0].key, "consumer_2");
+    }
+
+    #[tokio::test]
+    async fn get_targets_the_summary() {
+        let server = Server::start(vec![Response::json(200, SUMMARY)]).await;
+        let client = server.client();
+
+        let summary = get(&client).await.expect("summary");
+
+        assert_eq!(summary.organisations.len(), 2);
+        assert_eq!(server.uris(), vec!["/v2/summary"]);
+        assert!(matches!(get(&client).await, Err(Error::Get(_))));
+    }
+}
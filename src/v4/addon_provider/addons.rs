@@ -0,0 +1,171 @@
+//! # Addons module
+//!
+//! This module provide helpers and structures to list the addons of an addon
+//! provider owned by an organisation
+
+use std::{collections::HashMap, fmt::Debug};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+use percent_encoding::utf8_percent_encode;
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::{addon::Addon, logs::QUERY_VALUE},
+    v4::addon_provider::AddonProviderId,
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// ProviderAddon structure
+
+/// ProviderAddon structure describes an addon as known by its addon provider,
+/// it is identified by the real identifier of the addon, e.g.
+/// 'postgresql_xxx'
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct ProviderAddon {
+    #[serde(rename = "realId")]
+    pub real_id: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "plan")]
+    pub plan: String,
+    #[serde(rename = "version", default)]
+    pub version: Option<String>,
+    #[serde(rename = "zone")]
+    pub zone: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list addons of addon provider '{0}' of organisation '{1}', {2}")]
+    List(AddonProviderId, String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve the addons of the addon provider owned by the organisation
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn list<C>(
+    client: &Client<C>,
+    provider: AddonProviderId,
+    organisation_id: &str,
+) -> Result<Vec<ProviderAddon>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons?ownerId={}",
+        client.endpoint,
+        provider,
+        utf8_percent_encode(organisation_id, QUERY_VALUE)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list addons of the addon provider, path: '{}', provider: '{}', organisation: '{}'",
+            &path, provider, organisation_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(provider, organisation_id.to_owned(), err))
+}
+
+/// returns the addons paired with their details on the addon provider side,
+/// using their real identifier, addons unknown to the addon provider are left
+/// out
+pub fn join(
+    addons: Vec<Addon>,
+    provider_addons: Vec<ProviderAddon>,
+) -> Vec<(Addon, ProviderAddon)> {
+    let mut provider_addons: HashMap<_, _> = provider_addons
+        .into_iter()
+        .map(|addon| (addon.real_id.to_owned(), addon))
+        .collect();
+
+    addons
+        .into_iter()
+        .filter_map(|addon| {
+            provider_addons
+                .remove(&addon.real_id)
+                .map(|provider_addon| (addon, provider_addon))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{Response, Server},
+        v2::addon::tests::ADDON,
+    };
+
+    const PROVIDER_ADDONS: &str = r#"[
+        {
+            "realId": "postgresql_4c1e2d3f-5a6b-4c7d-8e9f-0a1b2c3d4e5f",
+            "ownerId": "orga_1",
+            "plan": "xs_sml",
+            "version": "15",
+            "zone": "par"
+        },
+        {
+            "realId": "postgresql_unknown",
+            "ownerId": "orga_1",
+            "plan": "xs_sml",
+            "zone": "mtl"
+        }
+    ]"#;
+
+    /// returns the addon of the fixture with the given real identifier
+    fn addon(real_id: &str) -> Addon {
+        let mut addon: serde_json::Value = serde_json::from_str(ADDON).expect("addon to parse");
+        addon["realId"] = real_id.into();
+        serde_json::from_value(addon).expect("addon to deserialize")
+    }
+
+    #[test]
+    fn join_pairs_addons_by_real_id() {
+        let provider_addons: Vec<ProviderAddon> =
+            serde_json::from_str(PROVIDER_ADDONS).expect("provider addons to deserialize");
+        let addons = vec![
+            addon("postgresql_missing"),
+            addon("postgresql_4c1e2d3f-5a6b-4c7d-8e9f-0a1b2c3d4e5f"),
+        ];
+
+        let joined = join(addons, provider_addons);
+
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined[0].0.real_id, joined[0].1.real_id);
+        assert_eq!(joined[0].1.version.as_deref(), Some("15"));
+    }
+
+    #[tokio::test]
+    async fn list_encodes_the_owner() {
+        let server = Server::start(vec![Response::json(200, PROVIDER_ADDONS)]).await;
+        let client = server.client();
+
+        let addons = list(&client, AddonProviderId::PostgreSql, "orga_1&x=y")
+            .await
+            .expect("addons");
+
+        assert_eq!(addons[1].version, None);
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/postgresql-addon/addons?ownerId=orga_1%26x%3Dy"]
+        );
+    }
+}
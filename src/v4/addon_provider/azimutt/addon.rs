@@ -0,0 +1,108 @@
+//! # Azimutt addon module
+//!
+//! This module provide helpers and structures to retrieve the instance of an
+//! azimutt addon
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// AzimuttAddon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct AzimuttAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "url")]
+    pub url: String,
+    #[serde(rename = "organizationId")]
+    pub organization_id: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of azimutt addon '{0}', {1}")]
+    Get(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the azimutt addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<AzimuttAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::Azimutt,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the azimutt addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    #[tokio::test]
+    async fn get_returns_the_url_and_the_organization() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"{
+                "id": "azimutt_1",
+                "url": "https://azimutt.app/organizations/2a7b9c4d",
+                "organizationId": "2a7b9c4d"
+            }"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let addon = get(&client, "azimutt_1").await.expect("addon");
+
+        assert_eq!(
+            addon,
+            AzimuttAddon {
+                id: "azimutt_1".to_string(),
+                url: "https://azimutt.app/organizations/2a7b9c4d".to_string(),
+                organization_id: "2a7b9c4d".to_string(),
+            }
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/azimutt/addons/azimutt_1"]
+        );
+        assert!(matches!(
+            get(&client, "azimutt_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "azimutt_1"
+        ));
+    }
+}
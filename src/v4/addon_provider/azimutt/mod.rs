@@ -0,0 +1,71 @@
+//! # Azimutt addon provider module
+//!
+//! This module provide helpers and structures to interact with the azimutt
+//! addon provider
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+use oauth10a::client::ClientError;
+
+use crate::{
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
+    Client,
+};
+
+pub mod addon;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information about addon provider '{0}', {1}")]
+    Get(AddonProviderId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the azimutt addon provider
+pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    addon_provider::get_provider(client, AddonProviderId::Azimutt)
+        .await
+        .map_err(|err| Error::Get(AddonProviderId::Azimutt, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PROVIDER: &str = r#"{
+        "providerId": "azimutt",
+        "clusters": [],
+        "dedicated": { "2.0.0": [] },
+        "defaultDedicatedVersion": "2.0.0"
+    }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_azimutt_provider() {
+        let server = Server::start(vec![Response::json(200, PROVIDER)]).await;
+        let client = server.client();
+
+        let provider = get(&client).await.expect("provider");
+
+        assert_eq!(provider.provider_id, AddonProviderId::Azimutt);
+        assert_eq!(provider.default, "2.0.0");
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/azimutt"]);
+        assert!(matches!(
+            get(&client).await,
+            Err(Error::Get(
+                AddonProviderId::Azimutt,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+//! # Backups module
+//!
+//! This module provide helpers and structures to list the backups of database
+//! addons and download them
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream};
+use hyper::{
+    body::{Bytes, HttpBody},
+    client::connect::Connect,
+    Body, Method,
+};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// Backup structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Backup {
+    #[serde(rename = "backup_id")]
+    pub id: String,
+    #[serde(rename = "entity_id")]
+    pub addon_id: String,
+    #[serde(rename = "status")]
+    pub status: String,
+    #[serde(rename = "creation_date")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "size", default)]
+    pub size: Option<u64>,
+    #[serde(rename = "download_url", default)]
+    pub download_url: Option<String>,
+    #[serde(rename = "restore_command", default)]
+    pub restore_command: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list backups, addon provider '{0}' does not support backups")]
+    Unsupported(AddonProviderId),
+    #[error("failed to list backups of addon '{0}' of organisation '{1}', {2}")]
+    List(String, String, ClientError),
+    #[error("failed to download backup '{0}', no download url is available")]
+    NoDownloadUrl(String),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to download backup '{0}', {1}")]
+    Download(String, hyper::Error),
+    #[error("failed to download backup '{0}', got status code {1}")]
+    StatusCode(String, u16),
+    #[error("failed to read backup '{0}', {1}")]
+    Body(String, hyper::Error),
+}
+
+// -----------------------------------------------------------------------------
+// State enumeration
+
+/// State enum holds the progression of the download of a backup
+enum State<C>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    Connect(Box<Client<C>>, Backup),
+    Read(Body, String),
+    Done,
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the backups of the given database addon, the identifier of the
+/// addon is the one of the addon provider, e.g. 'postgresql_xxx'
+pub async fn list<C>(
+    client: &Client<C>,
+    provider: &AddonProviderId,
+    organisation_id: &str,
+    addon_id: &str,
+) -> Result<Vec<Backup>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    if !matches!(
+        provider,
+        AddonProviderId::PostgreSql
+            | AddonProviderId::MySql
+            | AddonProviderId::MongoDb
+            | AddonProviderId::Redis
+    ) {
+        return Err(Error::Unsupported(provider.to_owned()));
+    }
+
+    let path = format!(
+        "{}/v2/backups/{}/{}",
+        client.endpoint, organisation_id, addon_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list backups of an addon, path: '{}', provider: '{}', organisation: '{}', addon: '{}'",
+            &path, provider, organisation_id, addon_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(addon_id.to_owned(), organisation_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns a stream of the content of the given backup, chunks are yielded as
+/// they are received, so that large dumps are never held in memory
+pub fn download<C>(client: &Client<C>, backup: &Backup) -> impl Stream<Item = Result<Bytes, Error>>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to download a backup, backup: '{}', addon: '{}'",
+            &backup.id, &backup.addon_id
+        );
+    }
+
+    stream::unfold(
+        State::Connect(Box::new(client.to_owned()), backup.to_owned()),
+        next,
+    )
+}
+
+async fn next<C>(state: State<C>) -> Option<(Result<Bytes, Error>, State<C>)>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    match state {
+        State::Connect(client, backup) => {
+            let Some(url) = &backup.download_url else {
+                return Some((Err(Error::NoDownloadUrl(backup.id)), State::Done));
+            };
+
+            // download urls are pre-signed, so the request is not signed
+            let req = match hyper::Request::builder()
+                .method(Method::GET)
+                .uri(url)
+                .body(Body::empty())
+            {
+                Ok(req) => req,
+                Err(err) => return Some((Err(Error::Request(err)), State::Done)),
+            };
+
+            let res = match client.inner().request(req).await {
+                Ok(res) => res,
+                Err(err) => return Some((Err(Error::Download(backup.id, err)), State::Done)),
+            };
+
+            let status = res.status();
+            if !status.is_success() {
+                return Some((
+                    Err(Error::StatusCode(backup.id, status.as_u16())),
+                    State::Done,
+                ));
+            }
+
+            read(res.into_body(), backup.id).await
+        }
+        State::Read(body, id) => read(body, id).await,
+        State::Done => None,
+    }
+}
+
+async fn read<C>(mut body: Body, id: String) -> Option<(Result<Bytes, Error>, State<C>)>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    match body.data().await {
+        Some(Ok(chunk)) => Some((Ok(chunk), State::Read(body, id))),
+        Some(Err(err)) => Some((Err(Error::Body(id, err)), State::Done)),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{StreamExt, TryStreamExt};
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const DUMP: &str = "--\n-- PostgreSQL database dump\n--\n\nCREATE TABLE t (id integer);\n";
+
+    /// returns a backup which could be downloaded from the given url
+    fn backup(download_url: Option<String>) -> Backup {
+        Backup {
+            id: "backup_1".to_string(),
+            addon_id: "postgresql_1".to_string(),
+            status: "READY".to_string(),
+            created_at: DateTime::parse_from_rfc3339("2024-05-01T02:00:00Z")
+                .expect("date")
+                .with_timezone(&Utc),
+            size: Some(DUMP.len() as u64),
+            download_url,
+            restore_command: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_deserializes_the_backups_of_the_addon() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"[{
+                "backup_id": "backup_1",
+                "entity_id": "postgresql_1",
+                "status": "READY",
+                "creation_date": "2024-05-01T02:00:00Z",
+                "size": 1024,
+                "download_url": "https://backups.clever-cloud.com/backup_1.dump",
+                "restore_command": "pg_restore backup_1.dump"
+            }, {
+                "backup_id": "backup_2",
+                "entity_id": "postgresql_1",
+                "status": "PENDING",
+                "creation_date": "2024-05-02T02:00:00Z"
+            }]"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let backups = list(
+            &client,
+            &AddonProviderId::PostgreSql,
+            "orga_1",
+            "postgresql_1",
+        )
+        .await
+        .expect("backups");
+
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].size, Some(1024));
+        assert_eq!(
+            backups[0].restore_command.as_deref(),
+            Some("pg_restore backup_1.dump")
+        );
+        assert_eq!(backups[1].download_url, None);
+        assert_eq!(server.uris(), vec!["/v2/backups/orga_1/postgresql_1"]);
+        assert!(matches!(
+            list(&client, &AddonProviderId::Cellar, "orga_1", "cellar_1").await,
+            Err(Error::Unsupported(AddonProviderId::Cellar))
+        ));
+    }
+
+    #[tokio::test]
+    async fn download_streams_the_bytes_of_the_dump() {
+        let server = Server::start(vec![Response {
+            status: 200,
+            content_type: "application/octet-stream".to_string(),
+            body: DUMP.to_string(),
+        }])
+        .await;
+        let client = server.client();
+
+        let url = format!("{}/backup_1.dump", server.endpoint());
+        let bytes = download(&client, &backup(Some(url)))
+            .map_ok(|chunk| chunk.to_vec())
+            .try_concat()
+            .await
+            .expect("dump");
+
+        assert_eq!(bytes, DUMP.as_bytes());
+        assert_eq!(server.uris(), vec!["/backup_1.dump"]);
+    }
+
+    #[tokio::test]
+    async fn download_fails_without_url_or_on_error_status() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+
+        let errors: Vec<_> = download(&client, &backup(None)).collect().await;
+        assert!(matches!(errors[..], [Err(Error::NoDownloadUrl(_))]));
+
+        let url = format!("{}/backup_1.dump", server.endpoint());
+        let errors: Vec<_> = download(&client, &backup(Some(url))).collect().await;
+        assert!(matches!(errors[..], [Err(Error::StatusCode(_, 404))]));
+    }
+}
@@ -0,0 +1,240 @@
+//! # Cellar addon module
+//!
+//! This module provide helpers and structures to retrieve the s3 endpoint and
+//! the credentials of a cellar addon
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Formatter},
+};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::{addon, owner::OwnerId},
+    v4::addon_provider::AddonProviderId,
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// CellarAddon structure
+
+/// CellarAddon structure describes a cellar addon, the keys are not returned,
+/// only the names of the environment variables of the addon holding them
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct CellarAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "host")]
+    pub host: String,
+    #[serde(rename = "accessKeyIdEnv")]
+    pub access_key_variable: String,
+    #[serde(rename = "secretKeyEnv")]
+    pub secret_key_variable: String,
+}
+
+impl CellarAddon {
+    #[cfg_attr(feature = "trace", tracing::instrument(skip_all))]
+    /// returns the s3 credentials of the addon read from its environment
+    pub fn credentials(&self, env: &BTreeMap<String, String>) -> Result<CellarCredentials, Error> {
+        let variable = |name: &str| {
+            env.get(name)
+                .cloned()
+                .ok_or_else(|| Error::MissingVariable(name.to_owned()))
+        };
+
+        Ok(CellarCredentials {
+            endpoint: self.host.to_owned(),
+            access_key: variable(&self.access_key_variable)?,
+            secret_key: variable(&self.secret_key_variable)?,
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// CellarCredentials structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct CellarCredentials {
+    #[serde(rename = "endpoint")]
+    pub endpoint: String,
+    #[serde(rename = "accessKey")]
+    pub access_key: String,
+    #[serde(rename = "secretKey")]
+    pub secret_key: String,
+}
+
+impl Debug for CellarCredentials {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellarCredentials")
+            .field("endpoint", &self.endpoint)
+            .field("access_key", &self.access_key)
+            .field("secret_key", &"[redacted]")
+            .finish()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of cellar addon '{0}', {1}")]
+    Get(String, ClientError),
+    #[error("failed to get environment of cellar addon '{0}', {1}")]
+    Environment(String, Box<addon::Error>),
+    #[error("failed to find variable '{0}' in the environment of the cellar addon")]
+    MissingVariable(String),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the cellar addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<CellarAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::Cellar,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the cellar addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+/// Retrieve the s3 endpoint and keys of the cellar addon of the given owner
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn s3_credentials<C>(
+    client: &Client<C>,
+    owner: &OwnerId,
+    id: &str,
+) -> Result<CellarCredentials, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let cellar = get(client, id).await?;
+    let env = addon::environment_by_owner(client, owner, id)
+        .await
+        .map_err(|err| Error::Environment(id.to_string(), Box::new(err)))?;
+
+    cellar.credentials(&env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const ADDON: &str = r#"{
+        "id": "cellar_1",
+        "host": "cellar-c2.services.clever-cloud.com",
+        "accessKeyIdEnv": "CELLAR_ADDON_KEY_ID",
+        "secretKeyEnv": "CELLAR_ADDON_KEY_SECRET"
+    }"#;
+
+    const ENV: &str = r#"[
+        { "name": "CELLAR_ADDON_HOST", "value": "cellar-c2.services.clever-cloud.com" },
+        { "name": "CELLAR_ADDON_KEY_ID", "value": "AKIA0001" },
+        { "name": "CELLAR_ADDON_KEY_SECRET", "value": "s3cr3t" }
+    ]"#;
+
+    /// returns the cellar addon of the fixture
+    fn cellar() -> CellarAddon {
+        serde_json::from_str(ADDON).expect("addon to deserialize")
+    }
+
+    #[test]
+    fn credentials_are_read_from_the_variables_of_the_addon() {
+        let env = BTreeMap::from([
+            ("CELLAR_ADDON_KEY_ID".to_string(), "AKIA0001".to_string()),
+            ("CELLAR_ADDON_KEY_SECRET".to_string(), "s3cr3t".to_string()),
+        ]);
+
+        let credentials = cellar().credentials(&env).expect("credentials");
+
+        assert_eq!(
+            credentials,
+            CellarCredentials {
+                endpoint: "cellar-c2.services.clever-cloud.com".to_string(),
+                access_key: "AKIA0001".to_string(),
+                secret_key: "s3cr3t".to_string(),
+            }
+        );
+
+        let debug = format!("{credentials:?}");
+        assert!(debug.contains("AKIA0001"));
+        assert!(!debug.contains("s3cr3t"));
+    }
+
+    #[test]
+    fn credentials_require_both_keys() {
+        let env = BTreeMap::from([("CELLAR_ADDON_KEY_ID".to_string(), "AKIA0001".to_string())]);
+
+        assert!(matches!(
+            cellar().credentials(&env),
+            Err(Error::MissingVariable(name)) if name == "CELLAR_ADDON_KEY_SECRET"
+        ));
+    }
+
+    #[tokio::test]
+    async fn s3_credentials_combine_the_addon_and_its_environment() {
+        let server =
+            Server::start(vec![Response::json(200, ADDON), Response::json(200, ENV)]).await;
+        let client = server.client();
+        let owner = OwnerId::from("orga_1");
+
+        let credentials = s3_credentials(&client, &owner, "cellar_1")
+            .await
+            .expect("credentials");
+
+        assert_eq!(credentials.endpoint, "cellar-c2.services.clever-cloud.com");
+        assert_eq!(credentials.access_key, "AKIA0001");
+        assert_eq!(credentials.secret_key, "s3cr3t");
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v4/addon-providers/cellar-addon/addons/cellar_1",
+                "/v2/organisations/orga_1/addons/cellar_1/env",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn s3_credentials_report_the_failing_call() {
+        let server = Server::start(vec![Response::json(200, ADDON)]).await;
+        let client = server.client();
+        let owner = OwnerId::from("orga_1");
+
+        assert!(matches!(
+            s3_credentials(&client, &owner, "cellar_1").await,
+            Err(Error::Environment(id, _)) if id == "cellar_1"
+        ));
+        assert!(matches!(
+            s3_credentials(&client, &owner, "cellar_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "cellar_1"
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+//! # Cellar keys module
+//!
+//! This module provide helpers and structures to list and rotate the keys of a
+//! cellar addon
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+use hyper::{body::Buf, client::connect::Connect, Body, Method, StatusCode};
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, Request, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    v4::addon_provider::{cellar::addon::CellarCredentials, AddonProviderId},
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// CellarKey structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct CellarKey {
+    #[serde(rename = "accessKeyId")]
+    pub access_key: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "active")]
+    pub active: bool,
+}
+
+// -----------------------------------------------------------------------------
+// RotationPending structure
+
+/// RotationPending structure is returned when the api accepted to rotate the
+/// keys but has not completed it yet, the old key keeps working until the
+/// rotation completes
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct RotationPending {
+    #[serde(rename = "rotationId")]
+    pub id: String,
+}
+
+impl RotationPending {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the rotation as it goes now
+    pub async fn poll<C>(&self, client: &Client<C>, addon_id: &str) -> Result<Rotation, Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        status(client, addon_id, &self.id).await
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Rotation enumeration
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Rotation {
+    /// the keys are rotated, the returned credentials are the new ones
+    Completed(CellarCredentials),
+    /// the keys are being rotated, the old key keeps working in the meantime
+    Pending(RotationPending),
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list keys of cellar addon '{0}', {1}")]
+    List(String, ClientError),
+    #[error("failed to create request, {0}")]
+    Request(hyper::http::Error),
+    #[error("failed to rotate keys of cellar addon '{0}', {1}")]
+    Rotate(String, ClientError),
+    #[error("failed to rotate keys of cellar addon '{0}', got status code {1}")]
+    StatusCode(String, u16),
+    #[error("failed to aggregate body, {0}")]
+    BodyAggregation(hyper::Error),
+    #[error("failed to deserialize response payload, {0}")]
+    Deserialize(serde_json::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve the keys of the cellar addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn list<C>(client: &Client<C>, id: &str) -> Result<Vec<CellarKey>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/keys",
+        client.endpoint,
+        AddonProviderId::Cellar,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list keys of the cellar addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(id.to_string(), err))
+}
+
+/// Rotate the keys of the cellar addon, the api either rotates them right
+/// away or accepts the rotation which could then be polled, in both cases the
+/// old key keeps working until the rotation completes
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn rotate<C>(client: &Client<C>, id: &str) -> Result<Rotation, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/keys/rotate",
+        client.endpoint,
+        AddonProviderId::Cellar,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to rotate keys of the cellar addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    execute(client, Method::POST, &path, id).await
+}
+
+/// Retrieve the state of the given rotation of the keys of the cellar addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn status<C>(client: &Client<C>, id: &str, rotation_id: &str) -> Result<Rotation, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/keys/rotations/{}",
+        client.endpoint,
+        AddonProviderId::Cellar,
+        id,
+        rotation_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get a keys rotation of the cellar addon, path: '{}', id: '{}', rotation: '{}'",
+            &path, id, rotation_id
+        );
+    }
+
+    execute(client, Method::GET, &path, id).await
+}
+
+/// execute the request and interprets the response as a completed rotation
+/// on '200 OK' or as a pending one on '202 Accepted'
+async fn execute<C>(
+    client: &Client<C>,
+    method: Method,
+    path: &str,
+    id: &str,
+) -> Result<Rotation, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let req = hyper::Request::builder()
+        .method(method)
+        .uri(path)
+        .body(Body::empty())
+        .map_err(Error::Request)?;
+
+    let res = client
+        .execute(req)
+        .await
+        .map_err(|err| Error::Rotate(id.to_string(), err))?;
+
+    match res.status() {
+        StatusCode::OK => Ok(Rotation::Completed(deserialize(res.into_body()).await?)),
+        StatusCode::ACCEPTED => Ok(Rotation::Pending(deserialize(res.into_body()).await?)),
+        status => Err(Error::StatusCode(id.to_string(), status.as_u16())),
+    }
+}
+
+async fn deserialize<T>(body: Body) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let buf = hyper::body::aggregate(body)
+        .await
+        .map_err(Error::BodyAggregation)?;
+
+    serde_json::from_reader(buf.reader()).map_err(Error::Deserialize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const CREDENTIALS: &str = r#"{
+        "endpoint": "cellar-c2.services.clever-cloud.com",
+        "accessKey": "new_access_key",
+        "secretKey": "new_secret_key"
+    }"#;
+
+    #[tokio::test]
+    async fn rotate_returns_the_new_credentials_once_completed() {
+        let server = Server::start(vec![Response::json(200, CREDENTIALS)]).await;
+        let client = server.client();
+
+        match rotate(&client, "cellar_1").await.expect("rotation") {
+            Rotation::Completed(credentials) => {
+                assert_eq!(credentials.access_key, "new_access_key");
+                assert_eq!(credentials.secret_key, "new_secret_key");
+            }
+            rotation => panic!("expected a completed rotation, got {rotation:?}"),
+        }
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(
+            requests[0].uri,
+            "/v4/addon-providers/cellar-addon/addons/cellar_1/keys/rotate"
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_returns_a_pending_rotation_to_poll_when_accepted() {
+        let server = Server::start(vec![
+            Response::json(202, r#"{"rotationId":"rotation_1"}"#),
+            Response::json(202, r#"{"rotationId":"rotation_1"}"#),
+            Response::json(200, CREDENTIALS),
+        ])
+        .await;
+        let client = server.client();
+
+        let pending = match rotate(&client, "cellar_1").await.expect("rotation") {
+            Rotation::Pending(pending) => pending,
+            rotation => panic!("expected a pending rotation, got {rotation:?}"),
+        };
+        assert_eq!(pending.id, "rotation_1");
+
+        assert_eq!(
+            pending.poll(&client, "cellar_1").await.expect("rotation"),
+            Rotation::Pending(pending.to_owned())
+        );
+        assert!(matches!(
+            pending.poll(&client, "cellar_1").await,
+            Ok(Rotation::Completed(_))
+        ));
+        assert_eq!(
+            server.uris()[1..],
+            [
+                "/v4/addon-providers/cellar-addon/addons/cellar_1/keys/rotations/rotation_1",
+                "/v4/addon-providers/cellar-addon/addons/cellar_1/keys/rotations/rotation_1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_fails_on_other_status_codes() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+
+        assert!(matches!(
+            rotate(&client, "cellar_1").await,
+            Err(Error::StatusCode(id, 404)) if id == "cellar_1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_returns_the_keys_of_the_addon() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"[
+                {"accessKeyId":"old_access_key","createdAt":"2024-01-01T00:00:00Z","active":false},
+                {"accessKeyId":"new_access_key","createdAt":"2024-05-01T00:00:00Z","active":true}
+            ]"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let keys = list(&client, "cellar_1").await.expect("keys");
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys[1].active);
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/cellar-addon/addons/cellar_1/keys"]
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! # Cellar addon provider module
+//!
+//! This module provide helpers and structures to interact with the cellar
+//! addon provider
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+use oauth10a::client::ClientError;
+
+use crate::{
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
+    Client,
+};
+
+pub mod addon;
+pub mod keys;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information about addon provider '{0}', {1}")]
+    Get(AddonProviderId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the cellar addon provider
+pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    addon_provider::get_provider(client, AddonProviderId::Cellar)
+        .await
+        .map_err(|err| Error::Get(AddonProviderId::Cellar, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PROVIDER: &str = r#"{
+        "providerId": "cellar-addon",
+        "clusters": [],
+        "dedicated": {},
+        "defaultDedicatedVersion": ""
+    }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_cellar_provider() {
+        let server = Server::start(vec![Response::json(200, PROVIDER)]).await;
+        let client = server.client();
+
+        let provider = get(&client).await.expect("provider");
+
+        assert_eq!(provider.provider_id, AddonProviderId::Cellar);
+        assert!(provider.dedicated.is_empty());
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/cellar-addon"]);
+        assert!(matches!(
+            get(&client).await,
+            Err(Error::Get(
+                AddonProviderId::Cellar,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+}
@@ -3,7 +3,10 @@
 //! This module provide helpers and structures to interact with the config
 //! provider addon's environment
 
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
 
 use hyper::client::connect::Connect;
 #[cfg(feature = "logging")]
@@ -50,6 +53,20 @@ pub enum Error {
     Get(String, ClientError),
     #[error("failed to update variables of config-provider addon '{0}', {1}")]
     Put(String, ClientError),
+    #[error("failed to clear variables of config-provider addon, {0}")]
+    Clear(ClientError),
+    #[error("failed to find variable '{0}'")]
+    KeyNotFound(String),
+}
+
+#[derive(thiserror::Error, PartialEq, Eq, Clone, Debug)]
+pub enum DotenvError {
+    #[error("failed to parse line {0} of dotenv content, expected 'NAME=value'")]
+    MissingSeparator(usize),
+    #[error("failed to parse line {0} of dotenv content, invalid variable name '{1}'")]
+    InvalidName(usize, String),
+    #[error("failed to parse line {0} of dotenv content, unterminated quoted value")]
+    UnterminatedQuote(usize),
 }
 
 // -----------------------------------------------------------------------------
@@ -174,3 +191,291 @@ where
 
     put(client, id, &v).await
 }
+
+/// Set or remove environment variables of the config provider in one update,
+/// a `None` value removes the variable while other variables are left as is,
+/// updates are applied in order so the last one of a variable wins
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn patch<C>(
+    client: &Client<C>,
+    id: &str,
+    updates: &[(String, Option<String>)],
+) -> Result<Vec<Variable>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let mut v = get(client, id).await?;
+    for (name, value) in updates {
+        match (value, v.iter_mut().find(|v| &v.name == name)) {
+            (None, _) => v.retain(|v| &v.name != name),
+            (Some(value), Some(var)) => var.value = value.to_owned(),
+            (Some(value), None) => v.push(Variable::new(name.to_owned(), value.to_owned())),
+        }
+    }
+
+    put(client, id, &v).await
+}
+
+/// Remove all the environment variables of the config provider, this is a
+/// destructive operation, the variables could not be recovered afterwards
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn clear<C>(client: &Client<C>, id: &str) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    match put(client, id, &Vec::new()).await {
+        Ok(_) => Ok(()),
+        Err(Error::Put(_, err)) => Err(Error::Clear(err)),
+        Err(err) => Err(err),
+    }
+}
+
+/// Rename an environment variable of the config provider in one update, so
+/// that its value could not be lost, a variable already named as the new name
+/// is replaced
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn rename_key<C>(
+    client: &Client<C>,
+    id: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<Vec<Variable>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let v = get(client, id).await?;
+    if !v.iter().any(|v| v.name == old_name) {
+        return Err(Error::KeyNotFound(old_name.to_string()));
+    }
+
+    let v: Vec<_> = v
+        .into_iter()
+        .filter(|v| v.name != new_name || old_name == new_name)
+        .map(|mut v| {
+            if v.name == old_name {
+                v.name = new_name.to_string();
+            }
+            v
+        })
+        .collect();
+
+    put(client, id, &v).await
+}
+
+/// Parse the content of a dotenv file as variables, empty lines and comments
+/// are skipped, an `export` prefix is allowed, values could be single-quoted
+/// to be taken as is or double-quoted to interpret '\n', '\"' and '\\', while
+/// a ' #' starts a comment after an unquoted value
+#[cfg_attr(feature = "trace", tracing::instrument(skip_all))]
+pub fn parse_dotenv(content: &str) -> Result<BTreeMap<String, String>, DotenvError> {
+    let mut variables = BTreeMap::new();
+    for (idx, line) in content.lines().enumerate() {
+        let nth = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) = line
+            .split_once('=')
+            .ok_or(DotenvError::MissingSeparator(nth))?;
+
+        let name = name.trim();
+        if name.is_empty()
+            || name.starts_with(|c: char| c.is_ascii_digit())
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || '_' == c || '.' == c)
+        {
+            return Err(DotenvError::InvalidName(nth, name.to_owned()));
+        }
+
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('\'') {
+            quoted
+                .strip_suffix('\'')
+                .ok_or(DotenvError::UnterminatedQuote(nth))?
+                .to_owned()
+        } else if let Some(quoted) = value.strip_prefix('"') {
+            unescape(
+                quoted
+                    .strip_suffix('"')
+                    .ok_or(DotenvError::UnterminatedQuote(nth))?,
+            )
+        } else {
+            match value.split_once(" #") {
+                Some((value, _)) => value.trim_end().to_owned(),
+                None => value.to_owned(),
+            }
+        };
+
+        variables.insert(name.to_owned(), value);
+    }
+
+    Ok(variables)
+}
+
+/// returns the double-quoted value of a dotenv file with its escape sequences
+/// interpreted, an unknown sequence is left as is
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if '\\' != c {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('"') => unescaped.push('"'),
+            Some('\\') => unescaped.push('\\'),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    #[test]
+    fn parse_dotenv_syntax() {
+        for (content, name, value) in [
+            ("NAME=value", "NAME", "value"),
+            ("  NAME = value  ", "NAME", "value"),
+            ("export NAME=value", "NAME", "value"),
+            ("NAME=", "NAME", ""),
+            ("NAME=a=b", "NAME", "a=b"),
+            ("NAME=value # comment", "NAME", "value"),
+            ("NAME=value#not-a-comment", "NAME", "value#not-a-comment"),
+            ("NAME='value # kept'", "NAME", "value # kept"),
+            (r"NAME='a\nb'", "NAME", r"a\nb"),
+            ("NAME=\"value # kept\"", "NAME", "value # kept"),
+            (r#"NAME="a\nb""#, "NAME", "a\nb"),
+            (r#"NAME="say \"hi\"""#, "NAME", "say \"hi\""),
+            (r#"NAME="back\\slash""#, "NAME", "back\\slash"),
+            (r#"NAME="unknown \t""#, "NAME", "unknown \\t"),
+            (r#"NAME="trailing \""#, "NAME", "trailing \\"),
+            ("_private.name=value", "_private.name", "value"),
+        ] {
+            assert_eq!(
+                parse_dotenv(content),
+                Ok(BTreeMap::from([(name.to_string(), value.to_string())])),
+                "{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_dotenv_skips_blank_lines_and_comments() {
+        let content = "# database\n\nHOST=localhost\r\n   # indented comment\nPORT=5432\nHOST=db\n";
+
+        assert_eq!(
+            parse_dotenv(content),
+            Ok(BTreeMap::from([
+                ("HOST".to_string(), "db".to_string()),
+                ("PORT".to_string(), "5432".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_dotenv_errors() {
+        for (content, err) in [
+            ("# comment\nNAME", DotenvError::MissingSeparator(2)),
+            ("export NAME", DotenvError::MissingSeparator(1)),
+            ("=value", DotenvError::InvalidName(1, String::new())),
+            (
+                "1NAME=value",
+                DotenvError::InvalidName(1, "1NAME".to_string()),
+            ),
+            (
+                "MY-NAME=value",
+                DotenvError::InvalidName(1, "MY-NAME".to_string()),
+            ),
+            (
+                "MY NAME=value",
+                DotenvError::InvalidName(1, "MY NAME".to_string()),
+            ),
+            ("A=1\nNAME='value", DotenvError::UnterminatedQuote(2)),
+            ("NAME=\"value", DotenvError::UnterminatedQuote(1)),
+            ("NAME=\"", DotenvError::UnterminatedQuote(1)),
+        ] {
+            assert_eq!(parse_dotenv(content), Err(err), "{content}");
+        }
+    }
+
+    const VARIABLES: &str = r#"[
+        { "name": "HOST", "value": "localhost" },
+        { "name": "PORT", "value": "5432" },
+        { "name": "USER", "value": "admin" },
+        { "name": "DEBUG", "value": "true" }
+    ]"#;
+
+    #[tokio::test]
+    async fn patch_sets_updates_and_removes_variables_in_order() {
+        let server = Server::start(vec![
+            Response::json(200, VARIABLES),
+            Response::json(200, "[]"),
+        ])
+        .await;
+        let client = server.client();
+        let updates = [
+            ("HOST".to_string(), Some("db".to_string())),
+            ("DEBUG".to_string(), None),
+            ("REGION".to_string(), Some("par".to_string())),
+            ("USER".to_string(), None),
+            ("USER".to_string(), Some("reader".to_string())),
+            ("PORT".to_string(), Some("6432".to_string())),
+            ("PORT".to_string(), None),
+            ("MISSING".to_string(), None),
+        ];
+
+        patch(&client, "config_1", &updates).await.expect("patch");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(
+            requests[1].uri,
+            "/v4/addon-providers/config-provider/addons/config_1/env"
+        );
+        assert_eq!(
+            requests[1].json(),
+            serde_json::json!([
+                { "name": "HOST", "value": "db" },
+                { "name": "REGION", "value": "par" },
+                { "name": "USER", "value": "reader" }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn clear_puts_an_empty_environment() {
+        let server = Server::start(vec![Response::json(200, "[]")]).await;
+        let client = server.client();
+
+        clear(&client, "config_1").await.expect("clear");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(
+            requests[0].uri,
+            "/v4/addon-providers/config-provider/addons/config_1/env"
+        );
+        assert_eq!(requests[0].json(), serde_json::json!([]));
+        assert!(matches!(
+            clear(&client, "config_1").await,
+            Err(Error::Clear(ClientError::StatusCode(..)))
+        ));
+    }
+}
@@ -3,4 +3,128 @@
 //! This module provide helpers and structures to interact with the config
 //! provider addons
 
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
 pub mod environment;
+
+// -----------------------------------------------------------------------------
+// ConfigProviderAddon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct ConfigProviderAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "variablesCount", default)]
+    pub variable_count: u64,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of config-provider addon '{0}', {1}")]
+    Get(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the config-provider addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<ConfigProviderAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::ConfigProvider,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the config-provider addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    #[test]
+    fn variable_count_defaults_to_zero() {
+        let addon: ConfigProviderAddon = serde_json::from_str(
+            r#"{ "id": "config_1", "ownerId": "orga_1", "createdAt": "2023-11-14T22:13:20Z" }"#,
+        )
+        .expect("addon to deserialize");
+
+        assert_eq!(addon.variable_count, 0);
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_owner_and_the_variable_count() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"{
+                "id": "config_1",
+                "ownerId": "orga_1",
+                "createdAt": "2023-11-14T22:13:20Z",
+                "variablesCount": 12
+            }"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let addon = get(&client, "config_1").await.expect("addon");
+
+        assert_eq!(
+            addon,
+            ConfigProviderAddon {
+                id: "config_1".to_string(),
+                owner_id: "orga_1".to_string(),
+                created_at: Utc
+                    .with_ymd_and_hms(2023, 11, 14, 22, 13, 20)
+                    .single()
+                    .expect("date"),
+                variable_count: 12,
+            }
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/config-provider/addons/config_1"]
+        );
+        assert!(matches!(
+            get(&client, "config_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "config_1"
+        ));
+    }
+}
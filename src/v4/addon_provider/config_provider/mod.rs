@@ -3,4 +3,78 @@
 //! This module provide helpers and structures to interact with the config
 //! provider addon provider
 
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+use oauth10a::client::ClientError;
+
+use crate::{
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
+    Client,
+};
+
 pub mod addon;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information about addon provider '{0}', {1}")]
+    Get(AddonProviderId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the config provider addon provider
+pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    addon_provider::get_provider(client, AddonProviderId::ConfigProvider)
+        .await
+        .map_err(|err| Error::Get(AddonProviderId::ConfigProvider, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PROVIDER: &str = r#"{
+        "providerId": "config-provider",
+        "clusters": [
+            {
+                "id": "config_1",
+                "label": "config-par",
+                "zone": "par",
+                "version": "1",
+                "features": [{ "name": "availability", "enabled": true }]
+            }
+        ],
+        "dedicated": {},
+        "defaultDedicatedVersion": "1"
+    }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_config_provider() {
+        let server = Server::start(vec![Response::json(200, PROVIDER)]).await;
+        let client = server.client();
+
+        let provider = get(&client).await.expect("provider");
+
+        assert_eq!(provider.provider_id, AddonProviderId::ConfigProvider);
+        assert_eq!(provider.clusters.len(), 1);
+        assert_eq!(provider.clusters[0].zone, "par");
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/config-provider"]);
+        assert!(matches!(
+            get(&client).await,
+            Err(Error::Get(
+                AddonProviderId::ConfigProvider,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! # ElasticSearch addon module
+//!
+//! This module provide helpers and structures to retrieve the details of an
+//! elasticsearch addon and to toggle its companion services, kibana and apm
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// ServiceInfo structure
+
+/// ServiceInfo structure describes a companion service of an elasticsearch
+/// addon, the service runs as an application of the owner of the addon
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct ServiceInfo {
+    #[serde(rename = "enabled")]
+    pub enabled: bool,
+    #[serde(rename = "url", default)]
+    pub url: Option<String>,
+    #[serde(rename = "appId", default)]
+    pub application_id: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// ElasticSearchAddon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct ElasticSearchAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "kibana", default)]
+    pub kibana: Option<ServiceInfo>,
+    #[serde(rename = "apm", default)]
+    pub apm: Option<ServiceInfo>,
+    #[serde(rename = "encryptionAtRest", default)]
+    pub encryption_at_rest: bool,
+}
+
+// -----------------------------------------------------------------------------
+// ServiceToggles structure
+
+/// ServiceToggles structure lists the companion services to enable or
+/// disable, a service which is not given is left as it is
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug, Default)]
+pub struct ServiceToggles {
+    #[serde(rename = "kibana", skip_serializing_if = "Option::is_none", default)]
+    pub kibana: Option<bool>,
+    #[serde(rename = "apm", skip_serializing_if = "Option::is_none", default)]
+    pub apm: Option<bool>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of elasticsearch addon '{0}', {1}")]
+    Get(String, ClientError),
+    #[error("failed to set services of elasticsearch addon '{0}', {1}")]
+    SetServices(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the elasticsearch addon and its companion
+/// services
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<ElasticSearchAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::ElasticSearch,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the elasticsearch addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+/// Enable or disable the companion services of the elasticsearch addon and
+/// returns the updated addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn set_services<C>(
+    client: &Client<C>,
+    id: &str,
+    toggles: &ServiceToggles,
+) -> Result<ElasticSearchAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/services",
+        client.endpoint,
+        AddonProviderId::ElasticSearch,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to set services of the elasticsearch addon, path: '{}', id: '{}', kibana: '{:?}', apm: '{:?}'",
+            &path, id, toggles.kibana, toggles.apm
+        );
+    }
+
+    client
+        .put(&path, toggles)
+        .await
+        .map_err(|err| Error::SetServices(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const WITH_KIBANA: &str = r#"{
+        "id": "elasticsearch_1",
+        "kibana": {
+            "enabled": true,
+            "url": "https://kibana-elasticsearch_1.services.clever-cloud.com",
+            "appId": "app_kibana_1"
+        },
+        "apm": { "enabled": false },
+        "encryptionAtRest": true
+    }"#;
+
+    const WITHOUT_KIBANA: &str = r#"{ "id": "elasticsearch_1" }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_enabled_services() {
+        let server = Server::start(vec![Response::json(200, WITH_KIBANA)]).await;
+        let client = server.client();
+
+        let addon = get(&client, "elasticsearch_1").await.expect("addon");
+
+        assert_eq!(
+            addon,
+            ElasticSearchAddon {
+                id: "elasticsearch_1".to_string(),
+                kibana: Some(ServiceInfo {
+                    enabled: true,
+                    url: Some(
+                        "https://kibana-elasticsearch_1.services.clever-cloud.com".to_string()
+                    ),
+                    application_id: Some("app_kibana_1".to_string()),
+                }),
+                apm: Some(ServiceInfo {
+                    enabled: false,
+                    url: None,
+                    application_id: None,
+                }),
+                encryption_at_rest: true,
+            }
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/es-addon/addons/elasticsearch_1"]
+        );
+        assert!(matches!(
+            get(&client, "elasticsearch_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "elasticsearch_1"
+        ));
+    }
+
+    #[test]
+    fn services_default_to_absent() {
+        let addon: ElasticSearchAddon =
+            serde_json::from_str(WITHOUT_KIBANA).expect("addon to deserialize");
+
+        assert_eq!(addon.kibana, None);
+        assert_eq!(addon.apm, None);
+        assert!(!addon.encryption_at_rest);
+    }
+
+    #[test]
+    fn toggles_omit_the_services_left_as_they_are() {
+        assert_eq!(
+            serde_json::to_value(ServiceToggles::default()).expect("toggles to serialize"),
+            serde_json::json!({})
+        );
+        assert_eq!(
+            serde_json::to_value(ServiceToggles {
+                kibana: None,
+                apm: Some(false),
+            })
+            .expect("toggles to serialize"),
+            serde_json::json!({ "apm": false })
+        );
+    }
+
+    #[tokio::test]
+    async fn set_services_puts_the_toggles_and_returns_the_addon() {
+        let server = Server::start(vec![Response::json(200, WITH_KIBANA)]).await;
+        let client = server.client();
+        let toggles = ServiceToggles {
+            kibana: Some(true),
+            apm: None,
+        };
+
+        let addon = set_services(&client, "elasticsearch_1", &toggles)
+            .await
+            .expect("addon");
+
+        assert_eq!(addon.kibana.map(|kibana| kibana.enabled), Some(true));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(
+            requests[0].uri,
+            "/v4/addon-providers/es-addon/addons/elasticsearch_1/services"
+        );
+        assert_eq!(requests[0].json(), serde_json::json!({ "kibana": true }));
+        assert!(matches!(
+            set_services(&client, "elasticsearch_1", &toggles).await,
+            Err(Error::SetServices(..))
+        ));
+    }
+}
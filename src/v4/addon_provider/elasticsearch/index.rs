@@ -0,0 +1,226 @@
+//! # Index module
+//!
+//! This module provide helpers and structures to manage indices and read the
+//! health of an elasticsearch addon through the api proxy
+
+use std::fmt::{self, Debug, Formatter};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// Index structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct Index {
+    #[serde(rename = "index")]
+    pub name: String,
+    #[serde(rename = "status")]
+    pub status: String,
+    #[serde(rename = "docs.count", deserialize_with = "deserialize_count")]
+    pub docs_count: u64,
+    #[serde(rename = "store.size", default)]
+    pub store_size: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// ClusterHealth structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct ClusterHealth {
+    #[serde(rename = "status")]
+    pub status: String,
+    #[serde(rename = "number_of_nodes")]
+    pub node_count: u32,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list indices of elasticsearch addon '{0}', {1}")]
+    List(String, ClientError),
+    #[error("failed to delete index '{0}' of elasticsearch addon '{1}', {2}")]
+    Delete(String, String, ClientError),
+    #[error("failed to get cluster health of elasticsearch addon '{0}', {1}")]
+    Health(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+/// deserialize a count which elasticsearch's cat api gives as a string
+fn deserialize_count<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct CountVisitor;
+
+    impl<'de> Visitor<'de> for CountVisitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            write!(f, "an unsigned integer or a string containing one")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse().map_err(E::custom)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(0)
+        }
+    }
+
+    deserializer.deserialize_any(CountVisitor)
+}
+
+/// returns the path to the elasticsearch api of the addon proxied by the api
+fn proxy_path<C>(client: &Client<C>, organisation_id: &str, addon_id: &str) -> String
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    format!(
+        "{}/v4/addon-providers/{}/organisations/{}/addons/{}/proxy",
+        client.endpoint,
+        AddonProviderId::ElasticSearch,
+        organisation_id,
+        addon_id
+    )
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of indices of the elasticsearch addon
+pub async fn list_indices<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    addon_id: &str,
+) -> Result<Vec<Index>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/_cat/indices?format=json",
+        proxy_path(client, organisation_id, addon_id)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to list indices of the elasticsearch addon, path: '{}', organisation: '{}', addon: '{}'", &path, organisation_id, addon_id);
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(addon_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// delete the index of the elasticsearch addon
+pub async fn delete_index<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    addon_id: &str,
+    index_name: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/{}",
+        proxy_path(client, organisation_id, addon_id),
+        index_name
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to delete an index of the elasticsearch addon, path: '{}', organisation: '{}', addon: '{}', index: '{}'", &path, organisation_id, addon_id, index_name);
+    }
+
+    client
+        .delete(&path)
+        .await
+        .map_err(|err| Error::Delete(index_name.to_owned(), addon_id.to_owned(), err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the cluster health of the elasticsearch addon
+pub async fn cluster_health<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    addon_id: &str,
+) -> Result<ClusterHealth, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/_cluster/health",
+        proxy_path(client, organisation_id, addon_id)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to get the cluster health of the elasticsearch addon, path: '{}', organisation: '{}', addon: '{}'", &path, organisation_id, addon_id);
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Health(addon_id.to_owned(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const INDICES: &str = r#"[
+        { "index": "logs", "status": "open", "docs.count": "1200", "store.size": "1.2mb" },
+        { "index": "archive", "status": "close", "docs.count": null, "store.size": null }
+    ]"#;
+
+    #[tokio::test]
+    async fn list_indices_including_closed_ones() {
+        let server = Server::start(vec![Response::json(200, INDICES)]).await;
+        let client = server.client();
+
+        let indices = list_indices(&client, "orga_1", "addon_1")
+            .await
+            .expect("indices");
+
+        assert_eq!(indices[0].docs_count, 1200);
+        assert_eq!(indices[0].store_size.as_deref(), Some("1.2mb"));
+        assert_eq!(indices[1].docs_count, 0);
+        assert_eq!(indices[1].store_size, None);
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/es-addon/organisations/orga_1/addons/addon_1/proxy/_cat/indices?format=json"]
+        );
+    }
+}
@@ -5,24 +5,26 @@
 #![allow(deprecated)]
 
 use std::{
+    cmp::Ordering,
     convert::TryFrom,
     fmt::{self, Debug, Display, Formatter},
     str::FromStr,
 };
 
 use hyper::client::connect::Connect;
-#[cfg(feature = "logging")]
-use log::{debug, log_enabled, Level};
-use oauth10a::client::{ClientError, RestClient};
+use oauth10a::client::ClientError;
 #[cfg(feature = "jsonschemas")]
-use schemars::JsonSchema_repr as JsonSchemaRepr;
-use serde_repr::{Deserialize_repr as DeserializeRepr, Serialize_repr as SerializeRepr};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    v4::addon_provider::{AddonProvider, AddonProviderId},
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
     Client,
 };
 
+pub mod addon;
+pub mod index;
+
 // -----------------------------------------------------------------------------
 // Error enumeration
 
@@ -37,15 +39,15 @@ pub enum Error {
 // -----------------------------------------------------------------------------
 // Version enum
 
-#[cfg_attr(feature = "jsonschemas", derive(JsonSchemaRepr))]
-#[derive(SerializeRepr, DeserializeRepr, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
-#[serde(untagged)]
-#[repr(i32)]
+/// Version enum lists the elasticsearch versions, unknown ones are kept by code
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(from = "i32", into = "i32")]
 pub enum Version {
     #[deprecated]
-    V6 = 6,
-    V7 = 7,
-    V8 = 8,
+    V6,
+    V7,
+    V8,
+    Unknown(i32),
 }
 
 impl FromStr for Version {
@@ -56,9 +58,10 @@ impl FromStr for Version {
             "6" => Self::V6,
             "7" => Self::V7,
             "8" => Self::V8,
-            _ => {
-                return Err(Error::ParseVersion(s.to_owned()));
-            }
+            _ => s
+                .parse::<i32>()
+                .map(Self::from)
+                .map_err(|_| Error::ParseVersion(s.to_owned()))?,
         })
     }
 }
@@ -71,6 +74,28 @@ impl TryFrom<String> for Version {
     }
 }
 
+impl From<i32> for Version {
+    fn from(n: i32) -> Self {
+        match n {
+            6 => Self::V6,
+            7 => Self::V7,
+            8 => Self::V8,
+            _ => Self::Unknown(n),
+        }
+    }
+}
+
+impl From<Version> for i32 {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::V6 => 6,
+            Version::V7 => 7,
+            Version::V8 => 8,
+            Version::Unknown(n) => n,
+        }
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<String> for Version {
     fn into(self) -> String {
@@ -78,16 +103,40 @@ impl Into<String> for Version {
     }
 }
 
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        i32::from(self.to_owned()).cmp(&i32::from(other.to_owned()))
+    }
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Self::V6 => write!(f, "6"),
             Self::V7 => write!(f, "7"),
             Self::V8 => write!(f, "8"),
+            Self::Unknown(n) => write!(f, "{n}"),
         }
     }
 }
 
+#[cfg(feature = "jsonschemas")]
+impl JsonSchema for Version {
+    fn schema_name() -> String {
+        "Version".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        i32::json_schema(gen)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Helpers functions
 
@@ -97,19 +146,7 @@ pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<Version>, Error>
 where
     C: Connect + Clone + Debug + Send + Sync + 'static,
 {
-    let path = format!(
-        "{}/v4/addon-providers/{}",
-        client.endpoint,
-        AddonProviderId::ElasticSearch
-    );
-
-    #[cfg(feature = "logging")]
-    if log_enabled!(Level::Debug) {
-        debug!("execute a request to get information about the elasticsearch addon-provider, path: '{}', name: '{}'", &path, AddonProviderId::ElasticSearch);
-    }
-
-    client
-        .get(&path)
+    addon_provider::get_provider(client, AddonProviderId::ElasticSearch)
         .await
         .map_err(|err| Error::Get(AddonProviderId::ElasticSearch, err))
 }
@@ -0,0 +1,125 @@
+//! # Keycloak addon module
+//!
+//! This module provide helpers and structures to retrieve the administration
+//! endpoint of a keycloak addon
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v4::addon_provider::{keycloak::Version, AddonProviderId},
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// KeycloakAddon structure
+
+/// KeycloakAddon structure describes a keycloak addon, the initial admin
+/// credentials are not returned, only the names of the environment variables
+/// of the addon holding them
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct KeycloakAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "version")]
+    pub version: Version,
+    #[serde(rename = "adminUrl")]
+    pub admin_url: String,
+    #[serde(rename = "realm")]
+    pub realm: String,
+    #[serde(rename = "initialAdminUsernameEnv")]
+    pub admin_username_variable: String,
+    #[serde(rename = "initialAdminPasswordEnv")]
+    pub admin_password_variable: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of keycloak addon '{0}', {1}")]
+    Get(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the keycloak addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<KeycloakAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::Keycloak,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the keycloak addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const ADDON: &str = r#"{
+        "id": "keycloak_1",
+        "version": "24.0",
+        "adminUrl": "https://keycloak_1.keycloak.services.clever-cloud.com/admin",
+        "realm": "master",
+        "initialAdminUsernameEnv": "CC_KEYCLOAK_ADMIN",
+        "initialAdminPasswordEnv": "CC_KEYCLOAK_ADMIN_DEFAULT_PASSWORD"
+    }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_admin_url_and_the_variables() {
+        let server = Server::start(vec![Response::json(200, ADDON)]).await;
+        let client = server.client();
+
+        let addon = get(&client, "keycloak_1").await.expect("addon");
+
+        assert_eq!(
+            addon,
+            KeycloakAddon {
+                id: "keycloak_1".to_string(),
+                version: Version::from("24.0".to_string()),
+                admin_url: "https://keycloak_1.keycloak.services.clever-cloud.com/admin"
+                    .to_string(),
+                realm: "master".to_string(),
+                admin_username_variable: "CC_KEYCLOAK_ADMIN".to_string(),
+                admin_password_variable: "CC_KEYCLOAK_ADMIN_DEFAULT_PASSWORD".to_string(),
+            }
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/keycloak/addons/keycloak_1"]
+        );
+        assert!(matches!(
+            get(&client, "keycloak_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "keycloak_1"
+        ));
+    }
+}
@@ -0,0 +1,159 @@
+//! # Keycloak addon provider module
+//!
+//! This module provide helpers and structures to interact with the keycloak
+//! addon provider
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+use hyper::client::connect::Connect;
+use oauth10a::client::ClientError;
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
+    Client,
+};
+
+pub mod addon;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information about addon provider '{0}', {1}")]
+    Get(AddonProviderId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Version structure
+
+/// Version structure holds a keycloak version such as '24.0', as new versions
+/// are released often it is not restricted to a list of known ones, versions
+/// are compared component by component
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub struct Version(String);
+
+impl Version {
+    /// returns the numeric components of the version, a component which is
+    /// not a number, e.g. a qualifier, is ignored
+    fn components(&self) -> Vec<u64> {
+        self.0
+            .split('.')
+            .map_while(|component| component.parse().ok())
+            .collect()
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.components()
+            .cmp(&other.components())
+            .then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl FromStr for Version {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl From<String> for Version {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for Version {
+    fn into(self) -> String {
+        self.0
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+/// returns information about the keycloak addon provider
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<Version>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    addon_provider::get_provider(client, AddonProviderId::Keycloak)
+        .await
+        .map_err(|err| Error::Get(AddonProviderId::Keycloak, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PROVIDER: &str = r#"{
+        "providerId": "keycloak",
+        "clusters": [],
+        "dedicated": { "24.0": [], "9.0": [], "25.0.1": [] },
+        "defaultDedicatedVersion": "25.0.1"
+    }"#;
+
+    #[test]
+    fn versions_compare_component_by_component() {
+        let version = |s: &str| Version::from_str(s).expect("version");
+
+        assert!(version("9.0") < version("24.0"));
+        assert!(version("24.0") < version("24.0.1"));
+        assert!(version("24.0.1") < version("25.0"));
+        assert_eq!(version("24.0").to_string(), "24.0");
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_keycloak_provider() {
+        let server = Server::start(vec![Response::json(200, PROVIDER)]).await;
+        let client = server.client();
+
+        let provider = get(&client).await.expect("provider");
+
+        assert_eq!(provider.provider_id, AddonProviderId::Keycloak);
+        assert_eq!(provider.default, Version::from("25.0.1".to_string()));
+        assert_eq!(
+            provider
+                .dedicated
+                .keys()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["9.0", "24.0", "25.0.1"]
+        );
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/keycloak"]);
+        assert!(matches!(
+            get(&client).await,
+            Err(Error::Get(
+                AddonProviderId::Keycloak,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+}
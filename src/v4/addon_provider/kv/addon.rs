@@ -0,0 +1,146 @@
+//! # Materia KV addon module
+//!
+//! This module provide helpers and structures to retrieve the endpoint and the
+//! credentials of a materia kv addon
+
+use std::fmt::{self, Debug, Formatter};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v2::addon::connection::encode, v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// KvAddon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct KvAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "host")]
+    pub host: String,
+    #[serde(rename = "port")]
+    pub port: u16,
+    #[serde(rename = "token")]
+    pub token: String,
+}
+
+impl Debug for KvAddon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KvAddon")
+            .field("id", &self.id)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("token", &"[redacted]")
+            .finish()
+    }
+}
+
+impl KvAddon {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the connection string of the addon, materia kv speaks the redis
+    /// protocol over tls and authenticates using the token as password
+    pub fn connection_uri(&self) -> String {
+        format!(
+            "rediss://:{}@{}:{}",
+            encode(&self.token),
+            self.host,
+            self.port
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of materia kv addon '{0}', {1}")]
+    Get(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve the endpoint and the token of the materia kv addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<KvAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::KV,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the materia kv addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const ADDON: &str = r#"{
+        "id": "kv_1",
+        "host": "materiadb.eu-fr-1.services.clever-cloud.com",
+        "port": 6379,
+        "token": "tok/en:1"
+    }"#;
+
+    #[test]
+    fn debug_redacts_the_token() {
+        let addon: KvAddon = serde_json::from_str(ADDON).expect("addon to deserialize");
+
+        let debug = format!("{addon:?}");
+
+        assert!(!debug.contains("tok/en:1"));
+        assert!(debug.contains("[redacted]"));
+    }
+
+    #[test]
+    fn connection_uri_encodes_the_token() {
+        let addon: KvAddon = serde_json::from_str(ADDON).expect("addon to deserialize");
+
+        assert_eq!(
+            addon.connection_uri(),
+            "rediss://:tok%2Fen%3A1@materiadb.eu-fr-1.services.clever-cloud.com:6379"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_endpoint_and_the_token() {
+        let server = Server::start(vec![Response::json(200, ADDON)]).await;
+        let client = server.client();
+
+        let addon = get(&client, "kv_1").await.expect("addon");
+
+        assert_eq!(addon.host, "materiadb.eu-fr-1.services.clever-cloud.com");
+        assert_eq!(addon.port, 6379);
+        assert_eq!(addon.token, "tok/en:1");
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/kv/addons/kv_1"]);
+        assert!(matches!(
+            get(&client, "kv_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "kv_1"
+        ));
+    }
+}
@@ -11,18 +11,96 @@ use std::{
     str::FromStr,
 };
 
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
 #[cfg(feature = "jsonschemas")]
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+use crate::Client;
+
+pub mod addons;
+pub mod azimutt;
+pub mod backups;
+pub mod cellar;
 pub mod config_provider;
+pub mod connection;
 pub mod elasticsearch;
+pub mod keycloak;
+pub mod kv;
 pub mod mongodb;
 pub mod mysql;
+pub mod otoroshi;
 pub mod plan;
 pub mod postgresql;
+pub mod pulsar;
 pub mod redis;
 
+// -----------------------------------------------------------------------------
+// FeatureName enumeration
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum FeatureName {
+    Availability,
+    EncryptionAtRest,
+    Kibana,
+    Apm,
+    DirectHostAccess,
+    Other(String),
+}
+
+impl From<String> for FeatureName {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "availability" => Self::Availability,
+            "encryption-at-rest" => Self::EncryptionAtRest,
+            "kibana" => Self::Kibana,
+            "apm" => Self::Apm,
+            "direct-host-access" => Self::DirectHostAccess,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+impl From<&str> for FeatureName {
+    fn from(s: &str) -> Self {
+        Self::from(s.to_owned())
+    }
+}
+
+impl From<FeatureName> for String {
+    fn from(name: FeatureName) -> Self {
+        name.to_string()
+    }
+}
+
+impl Display for FeatureName {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Availability => write!(f, "availability"),
+            Self::EncryptionAtRest => write!(f, "encryption-at-rest"),
+            Self::Kibana => write!(f, "kibana"),
+            Self::Apm => write!(f, "apm"),
+            Self::DirectHostAccess => write!(f, "direct-host-access"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+#[cfg(feature = "jsonschemas")]
+impl JsonSchema for FeatureName {
+    fn schema_name() -> String {
+        "FeatureName".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Feature structure
 
@@ -30,11 +108,31 @@ pub mod redis;
 #[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Clone, Debug)]
 pub struct Feature {
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: FeatureName,
     #[serde(rename = "enabled")]
     pub enabled: bool,
 }
 
+/// Features trait provides lookups on a list of features
+pub trait Features {
+    /// returns if the given feature is part of the list and enabled
+    fn has(&self, name: FeatureName) -> bool;
+
+    /// returns the given feature, if it is part of the list
+    fn get_feature(&self, name: &FeatureName) -> Option<&Feature>;
+}
+
+impl Features for [Feature] {
+    fn has(&self, name: FeatureName) -> bool {
+        self.get_feature(&name)
+            .is_some_and(|feature| feature.enabled)
+    }
+
+    fn get_feature(&self, name: &FeatureName) -> Option<&Feature> {
+        self.iter().find(|feature| &feature.name == name)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Cluster structure
 
@@ -53,6 +151,23 @@ pub struct Cluster<T> {
     pub version: T,
 }
 
+impl<T> Cluster<T> {
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(self)))]
+    /// returns if the cluster has the given feature enabled
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.has(FeatureName::from(name))
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(self)))]
+    /// returns if the cluster is available, a cluster which does not expose
+    /// the 'availability' feature is considered as available
+    pub fn is_available(&self) -> bool {
+        self.features
+            .get_feature(&FeatureName::Availability)
+            .map_or(true, |feature| feature.enabled)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // AddonProvider structure
 
@@ -72,13 +187,52 @@ where
     pub default: T,
 }
 
+impl<T> AddonProvider<T>
+where
+    T: Ord + Clone + Display,
+{
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(self)))]
+    /// returns the versions available for dedicated addons, in ascending order
+    pub fn available_versions(&self) -> Vec<&T> {
+        self.dedicated.keys().collect()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(self, version)))]
+    /// returns if the version is available for dedicated addons
+    pub fn supports(&self, version: &T) -> bool {
+        self.dedicated.contains_key(version)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(self, requested)))]
+    /// returns the requested version if it is available or the default one if
+    /// none is requested
+    pub fn resolve(&self, requested: Option<T>) -> Result<T, VersionError> {
+        match requested {
+            None => Ok(self.default.to_owned()),
+            Some(version) if self.supports(&version) => Ok(version),
+            Some(version) => Err(VersionError::Unavailable(
+                version.to_string(),
+                self.dedicated.keys().map(ToString::to_string).collect(),
+            )),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Error enumeration
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("failed to parse addon provider identifier {0}, available options are 'addon-pulsar', 'postgresql-addon', 'mysql-addon', 'mongodb-addon' or 'redis-addon'")]
+    #[error("failed to parse addon provider identifier {0}, available options are 'addon-pulsar', 'postgresql-addon', 'mysql-addon', 'mongodb-addon', 'redis-addon', 'kv', 'keycloak', 'otoroshi', 'cellar-addon' or 'azimutt'")]
     Parse(String),
+    #[error("failed to list clusters of addon provider '{0}', {1}")]
+    Clusters(AddonProviderId, ClientError),
+}
+
+#[derive(thiserror::Error, PartialEq, Eq, Clone, Debug)]
+pub enum VersionError {
+    #[error("failed to resolve version '{0}', available versions are {1:?}")]
+    Unavailable(String, Vec<String>),
 }
 
 // -----------------------------------------------------------------------------
@@ -95,6 +249,11 @@ pub enum AddonProviderId {
     Pulsar,
     ConfigProvider,
     ElasticSearch,
+    KV,
+    Keycloak,
+    Otoroshi,
+    Cellar,
+    Azimutt,
 }
 
 impl FromStr for AddonProviderId {
@@ -110,6 +269,11 @@ impl FromStr for AddonProviderId {
             "addon-pulsar" => Self::Pulsar,
             "config-provider" => Self::ConfigProvider,
             "es-addon" => Self::ElasticSearch,
+            "kv" => Self::KV,
+            "keycloak" => Self::Keycloak,
+            "otoroshi" | "addon-otoroshi" => Self::Otoroshi,
+            "cellar-addon" => Self::Cellar,
+            "azimutt" => Self::Azimutt,
             _ => return Err(Error::Parse(s.to_owned())),
         })
     }
@@ -142,6 +306,569 @@ impl Display for AddonProviderId {
             Self::Pulsar => write!(f, "addon-pulsar"),
             Self::ConfigProvider => write!(f, "config-provider"),
             Self::ElasticSearch => write!(f, "es-addon"),
+            Self::KV => write!(f, "kv"),
+            Self::Keycloak => write!(f, "keycloak"),
+            Self::Otoroshi => write!(f, "otoroshi"),
+            Self::Cellar => write!(f, "cellar-addon"),
+            Self::Azimutt => write!(f, "azimutt"),
+        }
+    }
+}
+
+impl AddonProviderId {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the addon provider encoded in the prefix of the real identifier
+    /// of an addon, e.g. 'postgresql_xxx', if it is a known one
+    pub fn from_real_id(real_id: &str) -> Option<Self> {
+        let (prefix, _) = real_id.split_once('_')?;
+
+        Some(match prefix.to_lowercase().as_str() {
+            "postgresql" => Self::PostgreSql,
+            "redis" => Self::Redis,
+            "mysql" => Self::MySql,
+            "mongodb" => Self::MongoDb,
+            "pulsar" => Self::Pulsar,
+            "config" => Self::ConfigProvider,
+            "elasticsearch" | "es" => Self::ElasticSearch,
+            "kv" => Self::KV,
+            "keycloak" => Self::Keycloak,
+            "otoroshi" => Self::Otoroshi,
+            "cellar" => Self::Cellar,
+            "azimutt" => Self::Azimutt,
+            _ => return None,
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the given addon provider, the versions it offers
+/// are deserialized as `V`
+pub async fn get_provider<C, V>(
+    client: &Client<C>,
+    id: AddonProviderId,
+) -> Result<AddonProvider<V>, ClientError>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+    V: DeserializeOwned + Ord + Debug + Send + Sync,
+{
+    let path = format!("{}/v4/addon-providers/{}", client.endpoint, id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about an addon-provider, path: '{}', name: '{}'",
+            &path, id
+        );
+    }
+
+    client.get(&path).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the clusters of the given addon provider, which is cheaper than
+/// retrieving the whole addon provider, the versions they run are deserialized
+/// as `V`
+pub async fn clusters<C, V>(
+    client: &Client<C>,
+    id: AddonProviderId,
+) -> Result<Vec<Cluster<V>>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+    V: DeserializeOwned + Debug + Send + Sync,
+{
+    let path = format!("{}/v4/addon-providers/{}/clusters", client.endpoint, id);
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list clusters of an addon-provider, path: '{}', name: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Clusters(id, err))
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument(skip(clusters)))]
+/// returns the first available cluster located in the given zone, if any
+pub fn find_in_zone<'a, T>(clusters: &'a [Cluster<T>], zone: &str) -> Option<&'a Cluster<T>> {
+    clusters
+        .iter()
+        .find(|cluster| cluster.zone == zone && cluster.is_available())
+}
+
+// -----------------------------------------------------------------------------
+// Versions helpers
+
+/// returns the numeric code of a dotted version made of the given number of
+/// single digit parts, e.g. '7.2.4' is 724, which is how the api identifies it
+pub(crate) fn parse_dotted_version(s: &str, parts: usize) -> Option<i32> {
+    let digits = s
+        .split('.')
+        .map(|part| match part.as_bytes() {
+            [digit @ b'0'..=b'9'] => Some(i32::from(digit - b'0')),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if digits.len() != parts {
+        return None;
+    }
+
+    Some(digits.into_iter().fold(0, |code, digit| code * 10 + digit))
+}
+
+/// returns the dotted version of the given numeric code, a code which does not
+/// fit in the given number of single digit parts is written as is
+pub(crate) fn format_dotted_version(code: i32, parts: usize) -> String {
+    let digits = code.to_string();
+    if code < 0 || digits.len() > parts {
+        return digits;
+    }
+
+    format!("{digits:0>parts$}")
+        .chars()
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// returns the description of an addon provider offering the given
+    /// versions, the first one being the default
+    pub(crate) fn provider_fixture(id: &str, versions: &[&str]) -> String {
+        let dedicated = versions
+            .iter()
+            .map(|version| {
+                format!(r#""{version}": [{{ "name": "encryption-at-rest", "enabled": true }}]"#)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            r#"{{
+                "providerId": "{id}",
+                "clusters": [{{
+                    "id": "cluster_1",
+                    "label": "shared",
+                    "zone": "par",
+                    "features": [{{ "name": "availability", "enabled": true }}],
+                    "version": {default}
+                }}],
+                "dedicated": {{ {dedicated} }},
+                "defaultDedicatedVersion": {default}
+            }}"#,
+            default = versions[0],
+        )
+    }
+
+    #[tokio::test]
+    async fn providers_get_their_own_description() {
+        let server = Server::start(vec![
+            Response::json(200, provider_fixture("postgresql-addon", &["15", "14"])),
+            Response::json(200, provider_fixture("mysql-addon", &["80", "57"])),
+            Response::json(200, provider_fixture("redis-addon", &["704"])),
+            Response::json(200, provider_fixture("mongodb-addon", &["403"])),
+            Response::json(200, provider_fixture("es-addon", &["8", "7"])),
+        ])
+        .await;
+        let client = server.client();
+
+        let provider = postgresql::get(&client).await.expect("postgresql");
+        assert_eq!(provider.provider_id, AddonProviderId::PostgreSql);
+        assert_eq!(provider.default, postgresql::Version::V15);
+        assert_eq!(
+            provider.available_versions(),
+            vec![&postgresql::Version::V14, &postgresql::Version::V15]
+        );
+        assert!(provider.clusters[0].is_available());
+
+        let provider = mysql::get(&client).await.expect("mysql");
+        assert_eq!(provider.provider_id, AddonProviderId::MySql);
+        assert_eq!(provider.default, mysql::Version::V8dot0);
+
+        let provider = redis::get(&client).await.expect("redis");
+        assert_eq!(provider.provider_id, AddonProviderId::Redis);
+        assert_eq!(provider.default, redis::Version::V7dot0dot4);
+
+        let provider = mongodb::get(&client).await.expect("mongodb");
+        assert_eq!(provider.provider_id, AddonProviderId::MongoDb);
+        assert_eq!(provider.default, mongodb::Version::V4dot0dot3);
+
+        let provider = elasticsearch::get(&client).await.expect("elasticsearch");
+        assert_eq!(provider.provider_id, AddonProviderId::ElasticSearch);
+        assert_eq!(provider.default, elasticsearch::Version::V8);
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|req| "GET" == req.method));
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v4/addon-providers/postgresql-addon",
+                "/v4/addon-providers/mysql-addon",
+                "/v4/addon-providers/redis-addon",
+                "/v4/addon-providers/mongodb-addon",
+                "/v4/addon-providers/es-addon",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn providers_keep_their_error_on_failure() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+
+        match postgresql::get(&client).await {
+            Err(postgresql::Error::Get(id, ClientError::StatusCode(code, _))) => {
+                assert_eq!(id, AddonProviderId::PostgreSql);
+                assert_eq!(404, code.as_u16());
+            }
+            res => panic!("expected a get error, got {res:?}"),
+        }
+
+        assert!(matches!(
+            mysql::get(&client).await,
+            Err(mysql::Error::Get(
+                AddonProviderId::MySql,
+                ClientError::StatusCode(..)
+            ))
+        ));
+        assert!(matches!(
+            redis::get(&client).await,
+            Err(redis::Error::Get(
+                AddonProviderId::Redis,
+                ClientError::StatusCode(..)
+            ))
+        ));
+        assert!(matches!(
+            mongodb::get(&client).await,
+            Err(mongodb::Error::Get(
+                AddonProviderId::MongoDb,
+                ClientError::StatusCode(..)
+            ))
+        ));
+        assert!(matches!(
+            elasticsearch::get(&client).await,
+            Err(elasticsearch::Error::Get(
+                AddonProviderId::ElasticSearch,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+
+    #[test]
+    fn feature_names_round_trip() {
+        for name in [
+            "availability",
+            "encryption-at-rest",
+            "direct-host-access",
+            "backups",
+        ] {
+            let feature: FeatureName =
+                serde_json::from_value(serde_json::json!(name)).expect("name to deserialize");
+
+            assert_eq!(String::from(feature.to_owned()), name);
+            assert_eq!(
+                serde_json::to_value(&feature).expect("name to serialize"),
+                name
+            );
         }
+
+        assert_eq!(
+            FeatureName::from("backups"),
+            FeatureName::Other("backups".to_string())
+        );
+        assert_eq!(FeatureName::from("kibana"), FeatureName::Kibana);
+    }
+
+    #[cfg(feature = "jsonschemas")]
+    #[test]
+    fn feature_name_schema_is_a_string() {
+        let schema =
+            serde_json::to_value(schemars::schema_for!(FeatureName)).expect("schema to serialize");
+
+        assert_eq!(schema["type"], "string", "{schema}");
+    }
+
+    #[test]
+    fn dotted_versions_round_trip() {
+        assert_eq!(parse_dotted_version("7.2.4", 3), Some(724));
+        assert_eq!(parse_dotted_version("9.0", 2), Some(90));
+        assert_eq!(parse_dotted_version("0.9", 2), Some(9));
+        assert_eq!(parse_dotted_version("7.2", 3), None);
+        assert_eq!(parse_dotted_version("7.12.4", 3), None);
+        assert_eq!(parse_dotted_version("latest", 2), None);
+
+        assert_eq!(format_dotted_version(724, 3), "7.2.4");
+        assert_eq!(format_dotted_version(90, 2), "9.0");
+        assert_eq!(format_dotted_version(9, 2), "0.9");
+        assert_eq!(format_dotted_version(1000, 3), "1000");
+    }
+
+    #[test]
+    fn unknown_versions_print_and_parse_like_known_ones() {
+        for (version, code) in [("7.2.4", 724), ("7.0.4", 704)] {
+            let parsed = redis::Version::from_str(version).expect("redis version");
+            assert_eq!(i32::from(parsed.to_owned()), code);
+            assert_eq!(parsed.to_string(), version);
+        }
+
+        assert_eq!(
+            mysql::Version::from_str("9.0").expect("mysql version"),
+            mysql::Version::Unknown(90)
+        );
+        assert_eq!(mysql::Version::Unknown(84).to_string(), "8.4");
+        assert_eq!(mongodb::Version::Unknown(708).to_string(), "7.0.8");
+        assert_eq!(postgresql::Version::Unknown(18).to_string(), "18");
+        assert_eq!(
+            postgresql::Version::from_str("18").expect("postgresql version"),
+            postgresql::Version::Unknown(18)
+        );
+        assert!(redis::Version::from_str("seven").is_err());
+        assert!(redis::Version::Unknown(724) > redis::Version::V7dot0dot4);
+    }
+
+    #[tokio::test]
+    async fn providers_with_unknown_versions_are_still_usable() {
+        let server = Server::start(vec![
+            Response::json(200, provider_fixture("postgresql-addon", &["18", "15"])),
+            Response::json(200, provider_fixture("redis-addon", &["724", "704"])),
+        ])
+        .await;
+        let client = server.client();
+
+        let provider = postgresql::get(&client).await.expect("postgresql");
+        assert_eq!(provider.default, postgresql::Version::Unknown(18));
+        assert_eq!(provider.clusters[0].zone, "par");
+        assert!(provider.clusters[0].is_available());
+        assert_eq!(
+            provider.available_versions(),
+            vec![&postgresql::Version::V15, &postgresql::Version::Unknown(18)]
+        );
+
+        let provider = redis::get(&client).await.expect("redis");
+        assert_eq!(provider.default.to_string(), "7.2.4");
+        assert_eq!(
+            provider.available_versions(),
+            vec![&redis::Version::V7dot0dot4, &redis::Version::Unknown(724)]
+        );
+    }
+
+    #[test]
+    fn provider_identifiers_round_trip() {
+        for id in [
+            AddonProviderId::PostgreSql,
+            AddonProviderId::Redis,
+            AddonProviderId::MySql,
+            AddonProviderId::MongoDb,
+            AddonProviderId::Pulsar,
+            AddonProviderId::ConfigProvider,
+            AddonProviderId::ElasticSearch,
+            AddonProviderId::KV,
+            AddonProviderId::Keycloak,
+            AddonProviderId::Otoroshi,
+            AddonProviderId::Cellar,
+            AddonProviderId::Azimutt,
+        ] {
+            assert_eq!(AddonProviderId::from_str(&id.to_string()).ok(), Some(id));
+        }
+
+        assert_eq!(AddonProviderId::Otoroshi.to_string(), "otoroshi");
+        assert_eq!(
+            AddonProviderId::from_str("addon-otoroshi").ok(),
+            Some(AddonProviderId::Otoroshi)
+        );
+        assert!(AddonProviderId::from_str("otoroshi-addon").is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn versions_convert_to_and_from_integers() {
+        for (version, n) in [
+            (postgresql::Version::V17, 17),
+            (postgresql::Version::V16, 16),
+            (postgresql::Version::V15, 15),
+            (postgresql::Version::V14, 14),
+            (postgresql::Version::V13, 13),
+            (postgresql::Version::V12, 12),
+            (postgresql::Version::V11, 11),
+            (postgresql::Version::Unknown(18), 18),
+        ] {
+            assert_eq!(i32::from(version.to_owned()), n);
+            assert_eq!(postgresql::Version::from(n), version);
+        }
+
+        for (version, n) in [
+            (mysql::Version::V5dot7, 57),
+            (mysql::Version::V8dot0, 80),
+            (mysql::Version::Unknown(84), 84),
+        ] {
+            assert_eq!(i32::from(version.to_owned()), n);
+            assert_eq!(mysql::Version::from(n), version);
+        }
+
+        for (version, n) in [
+            (elasticsearch::Version::V6, 6),
+            (elasticsearch::Version::V7, 7),
+            (elasticsearch::Version::V8, 8),
+            (elasticsearch::Version::Unknown(9), 9),
+        ] {
+            assert_eq!(i32::from(version.to_owned()), n);
+            assert_eq!(elasticsearch::Version::from(n), version);
+        }
+
+        for (version, n) in [
+            (redis::Version::V6dot2dot6, 626),
+            (redis::Version::V7dot0dot4, 704),
+            (redis::Version::Unknown(724), 724),
+        ] {
+            assert_eq!(i32::from(version.to_owned()), n);
+            assert_eq!(redis::Version::from(n), version);
+        }
+    }
+
+    /// returns a provider offering the versions 13, 14 and 15, the latter
+    /// being the default one
+    fn three_versions_provider() -> AddonProvider<postgresql::Version> {
+        serde_json::from_str(&provider_fixture("postgresql-addon", &["15", "14", "13"]))
+            .expect("provider to deserialize")
+    }
+
+    #[test]
+    fn available_versions_are_sorted_and_supported() {
+        let provider = three_versions_provider();
+
+        assert_eq!(
+            provider.available_versions(),
+            vec![
+                &postgresql::Version::V13,
+                &postgresql::Version::V14,
+                &postgresql::Version::V15
+            ]
+        );
+        assert!(provider.supports(&postgresql::Version::V14));
+        assert!(!provider.supports(&postgresql::Version::V16));
+    }
+
+    #[test]
+    fn resolve_defaults_or_validates_the_requested_version() {
+        let provider = three_versions_provider();
+
+        assert_eq!(provider.resolve(None).ok(), Some(postgresql::Version::V15));
+        assert_eq!(
+            provider.resolve(Some(postgresql::Version::V13)).ok(),
+            Some(postgresql::Version::V13)
+        );
+
+        match provider.resolve(Some(postgresql::Version::V11)) {
+            Err(VersionError::Unavailable(version, available)) => {
+                assert_eq!(version, "11");
+                assert_eq!(available, vec!["13", "14", "15"]);
+            }
+            res => panic!("expected an unavailable version, got {res:?}"),
+        }
+    }
+
+    const CLUSTERS: &str = r#"[
+        {
+            "id": "cluster_par_1",
+            "label": "shared-par-1",
+            "zone": "par",
+            "features": [{ "name": "availability", "enabled": false }],
+            "version": "15"
+        },
+        {
+            "id": "cluster_par_2",
+            "label": "shared-par-2",
+            "zone": "par",
+            "features": [{ "name": "availability", "enabled": true }],
+            "version": "15"
+        },
+        {
+            "id": "cluster_rbx_1",
+            "label": "shared-rbx-1",
+            "zone": "rbx",
+            "features": [],
+            "version": "14"
+        }
+    ]"#;
+
+    #[tokio::test]
+    async fn clusters_are_listed_without_the_provider() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"[
+                {
+                    "id": "cluster_1",
+                    "label": "shared-par",
+                    "zone": "par",
+                    "features": [{ "name": "availability", "enabled": true }],
+                    "version": 15
+                },
+                {
+                    "id": "cluster_2",
+                    "label": "shared-mtl",
+                    "zone": "mtl",
+                    "features": [],
+                    "version": 14
+                }
+            ]"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let clusters: Vec<Cluster<postgresql::Version>> =
+            clusters(&client, AddonProviderId::PostgreSql)
+                .await
+                .expect("clusters");
+
+        assert_eq!(
+            clusters
+                .iter()
+                .map(|cluster| (cluster.id.as_str(), cluster.zone.as_str(), &cluster.version))
+                .collect::<Vec<_>>(),
+            vec![
+                ("cluster_1", "par", &postgresql::Version::V15),
+                ("cluster_2", "mtl", &postgresql::Version::V14),
+            ]
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/postgresql-addon/clusters"]
+        );
+        assert!(matches!(
+            super::clusters::<_, postgresql::Version>(&client, AddonProviderId::PostgreSql).await,
+            Err(Error::Clusters(
+                AddonProviderId::PostgreSql,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+
+    #[test]
+    fn find_in_zone_skips_unavailable_clusters() {
+        let clusters: Vec<Cluster<String>> =
+            serde_json::from_str(CLUSTERS).expect("clusters to deserialize");
+
+        assert_eq!(
+            find_in_zone(&clusters, "par").map(|cluster| cluster.id.as_str()),
+            Some("cluster_par_2")
+        );
+        assert_eq!(
+            find_in_zone(&clusters, "rbx").map(|cluster| cluster.id.as_str()),
+            Some("cluster_rbx_1")
+        );
+        assert_eq!(find_in_zone(&clusters, "mtl"), None);
+        assert_eq!(find_in_zone(&clusters[..1], "par"), None);
     }
 }
@@ -4,21 +4,24 @@
 //! addon provider
 
 use std::{
+    cmp::Ordering,
     convert::TryFrom,
     fmt::{self, Debug, Display, Formatter},
     str::FromStr,
 };
 
 use hyper::client::connect::Connect;
-#[cfg(feature = "logging")]
-use log::{debug, log_enabled, Level};
-use oauth10a::client::{ClientError, RestClient};
+use oauth10a::client::ClientError;
 #[cfg(feature = "jsonschemas")]
-use schemars::JsonSchema_repr as JsonSchemaRepr;
-use serde_repr::{Deserialize_repr as DeserializeRepr, Serialize_repr as SerializeRepr};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    v4::addon_provider::{AddonProvider, AddonProviderId},
+    v4::addon_provider::{
+        self,
+        connection::{self, ConnectionUri},
+        AddonProvider, AddonProviderId,
+    },
     Client,
 };
 
@@ -31,18 +34,20 @@ pub enum Error {
     ParseVersion(String),
     #[error("failed to get information about addon provider '{0}', {1}")]
     Get(AddonProviderId, ClientError),
+    #[error("failed to get connection uri of mysql addon, {0}")]
+    ConnectionUri(connection::Error),
 }
 
 // -----------------------------------------------------------------------------
 // Version enum
 
-#[cfg_attr(feature = "jsonschemas", derive(JsonSchemaRepr))]
-#[derive(SerializeRepr, DeserializeRepr, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
-#[serde(untagged)]
-#[repr(i32)]
+/// Version enum lists the mysql versions, unknown ones are kept by code
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(from = "i32", into = "i32")]
 pub enum Version {
-    V5dot7 = 57,
-    V8dot0 = 80,
+    V5dot7,
+    V8dot0,
+    Unknown(i32),
 }
 
 impl FromStr for Version {
@@ -52,9 +57,10 @@ impl FromStr for Version {
         Ok(match s {
             "5.7" => Self::V5dot7,
             "8.0" => Self::V8dot0,
-            _ => {
-                return Err(Error::ParseVersion(s.to_owned()));
-            }
+            _ => addon_provider::parse_dotted_version(s, 2)
+                .or_else(|| s.parse::<i32>().ok())
+                .map(Self::from)
+                .ok_or_else(|| Error::ParseVersion(s.to_owned()))?,
         })
     }
 }
@@ -67,6 +73,26 @@ impl TryFrom<String> for Version {
     }
 }
 
+impl From<i32> for Version {
+    fn from(n: i32) -> Self {
+        match n {
+            57 => Self::V5dot7,
+            80 => Self::V8dot0,
+            _ => Self::Unknown(n),
+        }
+    }
+}
+
+impl From<Version> for i32 {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::V5dot7 => 57,
+            Version::V8dot0 => 80,
+            Version::Unknown(n) => n,
+        }
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<String> for Version {
     fn into(self) -> String {
@@ -74,15 +100,39 @@ impl Into<String> for Version {
     }
 }
 
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        i32::from(self.to_owned()).cmp(&i32::from(other.to_owned()))
+    }
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::V5dot7 => write!(f, "5.7"),
             Self::V8dot0 => write!(f, "8.0"),
+            Self::Unknown(n) => write!(f, "{}", addon_provider::format_dotted_version(*n, 2)),
         }
     }
 }
 
+#[cfg(feature = "jsonschemas")]
+impl JsonSchema for Version {
+    fn schema_name() -> String {
+        "Version".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        i32::json_schema(gen)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Helpers functions
 
@@ -92,19 +142,19 @@ pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<Version>, Error>
 where
     C: Connect + Clone + Debug + Send + Sync + 'static,
 {
-    let path = format!(
-        "{}/v4/addon-providers/{}",
-        client.endpoint,
-        AddonProviderId::MySql
-    );
-
-    #[cfg(feature = "logging")]
-    if log_enabled!(Level::Debug) {
-        debug!("execute a request to get information about the mysql addon-provider, path: '{}', name: '{}'", &path, AddonProviderId::MySql);
-    }
-
-    client
-        .get(&path)
+    addon_provider::get_provider(client, AddonProviderId::MySql)
         .await
         .map_err(|err| Error::Get(AddonProviderId::MySql, err))
 }
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the connection uris of the given mysql addon, the direct one
+/// reaches the host of the database without going through the proxy
+pub async fn connection_uri<C>(client: &Client<C>, id: &str) -> Result<ConnectionUri, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    connection::get(client, &AddonProviderId::MySql, id)
+        .await
+        .map_err(Error::ConnectionUri)
+}
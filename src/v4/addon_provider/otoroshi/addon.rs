@@ -0,0 +1,113 @@
+//! # Otoroshi addon module
+//!
+//! This module provide helpers and structures to retrieve the api endpoint of
+//! an otoroshi addon
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// OtoroshiAddon structure
+
+/// OtoroshiAddon structure describes an otoroshi addon, the api credentials
+/// are not returned, only the names of the environment variables of the addon
+/// holding them
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct OtoroshiAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "apiUrl")]
+    pub api_url: String,
+    #[serde(rename = "clientIdEnv")]
+    pub client_id_variable: String,
+    #[serde(rename = "clientSecretEnv")]
+    pub client_secret_variable: String,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of otoroshi addon '{0}', {1}")]
+    Get(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the otoroshi addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<OtoroshiAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::Otoroshi,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the otoroshi addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const ADDON: &str = r#"{
+        "id": "otoroshi_1",
+        "apiUrl": "https://otoroshi-api.services.clever-cloud.com",
+        "clientIdEnv": "CC_OTOROSHI_API_CLIENT_ID",
+        "clientSecretEnv": "CC_OTOROSHI_API_CLIENT_SECRET"
+    }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_api_of_the_addon() {
+        let server = Server::start(vec![Response::json(200, ADDON)]).await;
+        let client = server.client();
+
+        let addon = get(&client, "otoroshi_1").await.expect("addon");
+
+        assert_eq!(
+            addon.api_url,
+            "https://otoroshi-api.services.clever-cloud.com"
+        );
+        assert_eq!(addon.client_id_variable, "CC_OTOROSHI_API_CLIENT_ID");
+        assert_eq!(
+            addon.client_secret_variable,
+            "CC_OTOROSHI_API_CLIENT_SECRET"
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/otoroshi/addons/otoroshi_1"]
+        );
+        assert!(matches!(
+            get(&client, "otoroshi_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "otoroshi_1"
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+//! # Otoroshi addon provider module
+//!
+//! This module provide helpers and structures to interact with the otoroshi
+//! addon provider
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+use oauth10a::client::ClientError;
+
+use crate::{
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
+    Client,
+};
+
+pub mod addon;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information about addon provider '{0}', {1}")]
+    Get(AddonProviderId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the otoroshi addon provider
+pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<String>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    addon_provider::get_provider(client, AddonProviderId::Otoroshi)
+        .await
+        .map_err(|err| Error::Get(AddonProviderId::Otoroshi, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PROVIDER: &str = r#"{
+        "providerId": "otoroshi",
+        "clusters": [],
+        "dedicated": { "16.11.2": [] },
+        "defaultDedicatedVersion": "16.11.2"
+    }"#;
+
+    #[tokio::test]
+    async fn get_returns_the_otoroshi_provider() {
+        let server = Server::start(vec![Response::json(200, PROVIDER)]).await;
+        let client = server.client();
+
+        let provider = get(&client).await.expect("provider");
+
+        assert_eq!(provider.provider_id, AddonProviderId::Otoroshi);
+        assert_eq!(provider.default, "16.11.2");
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/otoroshi"]);
+        assert!(matches!(
+            get(&client).await,
+            Err(Error::Get(
+                AddonProviderId::Otoroshi,
+                ClientError::StatusCode(..)
+            ))
+        ));
+    }
+}
@@ -5,15 +5,23 @@
 
 use std::fmt::Debug;
 
-use hyper::client::connect::Connect;
+use hyper::{client::connect::Connect, StatusCode};
 #[cfg(feature = "logging")]
 use log::{debug, log_enabled, Level};
 use oauth10a::client::{ClientError, RestClient};
+use percent_encoding::utf8_percent_encode;
 #[cfg(feature = "jsonschemas")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{v2::addon::Feature, v4::addon_provider::AddonProviderId, Client};
+use crate::{
+    v2::{
+        addon::{Feature, FeatureKind, FeatureValue},
+        logs::QUERY_VALUE,
+    },
+    v4::addon_provider::AddonProviderId,
+    Client,
+};
 
 // -----------------------------------------------------------------------------
 // Constants
@@ -43,6 +51,40 @@ pub struct Plan {
     pub zones: Vec<String>,
 }
 
+impl Plan {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the features of the plan with their typed kind and value
+    pub fn typed_features(&self) -> Vec<PlanFeature> {
+        self.features.iter().map(PlanFeature::from).collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PlanFeature structure
+
+/// PlanFeature structure is a feature of a plan interpreted according to its
+/// kind
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Clone, Debug)]
+pub struct PlanFeature {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: FeatureKind,
+    #[serde(rename = "value")]
+    pub value: FeatureValue,
+}
+
+impl From<&Feature> for PlanFeature {
+    fn from(feature: &Feature) -> Self {
+        Self {
+            name: feature.name.to_owned(),
+            kind: feature.typed_kind(),
+            value: feature.typed_value(),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // AddonProviderPlan structure
 
@@ -88,6 +130,10 @@ pub struct AddonProviderPlan {
 pub enum Error {
     #[error("failed to list plan of addon provider '{0}' of organisation '{1}', {2}")]
     List(AddonProviderId, String, ClientError),
+    #[error("failed to list plan of addon provider '{0}' of organisation '{1}' using the api version 4, {2}")]
+    ListV4(AddonProviderId, String, ClientError),
+    #[error("failed to find a plan of addon provider '{0}' matching the required features")]
+    NoMatchingPlan(AddonProviderId),
 }
 
 // -----------------------------------------------------------------------------
@@ -125,6 +171,65 @@ where
     })
 }
 
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of plan of the addon provider for the organisation using
+/// the api version 4, which is not available for all addon providers
+pub async fn list_v4<C>(
+    client: &Client<C>,
+    addon_provider_id: &AddonProviderId,
+    organisation_id: &str,
+) -> Result<Vec<Plan>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/plans?orga_id={}",
+        client.endpoint,
+        addon_provider_id,
+        utf8_percent_encode(organisation_id, QUERY_VALUE)
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list plans of the addon-provider, path: '{}', name: '{}'",
+            &path, addon_provider_id
+        );
+    }
+
+    client.get(&path).await.map_err(|err| {
+        Error::ListV4(
+            addon_provider_id.to_owned(),
+            organisation_id.to_owned(),
+            err,
+        )
+    })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of plan of the addon provider, using the api version 4
+/// and falling back to the api version 2 if the addon provider does not
+/// expose its plans on the former
+pub async fn plans<C>(
+    client: &Client<C>,
+    addon_provider_id: &AddonProviderId,
+    organisation_id: &str,
+) -> Result<Vec<Plan>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    match list_v4(client, addon_provider_id, organisation_id).await {
+        Err(Error::ListV4(_, _, ClientError::StatusCode(code, _)))
+            if StatusCode::NOT_FOUND == code =>
+        {
+            Ok(list(client, addon_provider_id, organisation_id)
+                .await?
+                .plans)
+        }
+        result => result,
+    }
+}
+
 #[cfg_attr(feature = "trace", tracing::instrument)]
 /// list plans for the organisation and try to find one matching the pattern
 /// returns the plan if found
@@ -137,10 +242,256 @@ pub async fn find<C>(
 where
     C: Connect + Clone + Debug + Send + Sync + 'static,
 {
-    Ok(list(client, addon_provider_id, organisation_id)
+    Ok(plans(client, addon_provider_id, organisation_id)
         .await?
-        .plans
         .iter()
         .find(|plan| plan.slug == pattern || plan.id == pattern || plan.name == pattern)
         .map(ToOwned::to_owned))
 }
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// list plans for the organisation and returns the cheapest one whose features
+/// have all the required name and value pairs, the first listed plan wins a
+/// tie on price
+///
+/// it lives next to the other plan helpers as there is no `v2::plan` module,
+/// and unlike [`find`] it returns [`Error::NoMatchingPlan`] rather than `None`
+/// when no plan matches, so that the error names the addon provider
+pub async fn find_cheapest<C>(
+    client: &Client<C>,
+    addon_provider_id: &AddonProviderId,
+    organisation_id: &str,
+    required_features: &[(String, String)],
+) -> Result<Plan, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    plans(client, addon_provider_id, organisation_id)
+        .await?
+        .into_iter()
+        .filter(|plan| {
+            required_features.iter().all(|(name, value)| {
+                plan.features
+                    .iter()
+                    .any(|feature| &feature.name == name && &feature.value == value)
+            })
+        })
+        .min_by(|a, b| a.price.total_cmp(&b.price))
+        .ok_or_else(|| Error::NoMatchingPlan(addon_provider_id.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const PLANS: &str = r#"[
+        {
+            "id": "plan_xs",
+            "name": "XS Small Space",
+            "slug": "xs_sml",
+            "price": 5.25,
+            "price_id": "price_xs",
+            "features": [
+                { "name": "disk_size", "type": "FILESIZE", "value": "1 GB", "computable_value": null, "name_code": null },
+                { "name": "is_ha", "type": "BOOLEAN", "value": "No", "computable_value": null, "name_code": null }
+            ],
+            "zones": ["par"]
+        },
+        {
+            "id": "plan_m",
+            "name": "M Big Space",
+            "slug": "m_big",
+            "price": 40.5,
+            "price_id": "price_m",
+            "features": [
+                { "name": "disk_size", "type": "FILESIZE", "value": "40 GB", "computable_value": null, "name_code": null },
+                { "name": "is_ha", "type": "BOOLEAN", "value": "Yes", "computable_value": null, "name_code": null }
+            ],
+            "zones": ["par", "mtl"]
+        }
+    ]"#;
+
+    /// returns the description of the postgresql addon provider with the given
+    /// plans as answered by the api version 2
+    fn provider_plans(plans: &str) -> String {
+        format!(
+            r#"{{
+                "id": "postgresql-addon",
+                "name": "PostgreSQL",
+                "website": "https://www.clever-cloud.com",
+                "supportEmail": "support@clever-cloud.com",
+                "googlePlusName": "",
+                "twitterName": "",
+                "analyticsId": "",
+                "shortDesc": "PostgreSQL",
+                "longDesc": "PostgreSQL",
+                "logoUrl": "https://example.com/logo.png",
+                "status": "RELEASE",
+                "openInNewTab": false,
+                "canUpgrade": true,
+                "regions": ["par"],
+                "plans": {plans}
+            }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn plans_of_the_organisation_on_the_api_version_4() {
+        let server = Server::start(vec![Response::json(200, PLANS)]).await;
+        let client = server.client();
+
+        let plan = find(&client, &AddonProviderId::PostgreSql, "orga_1", "m_big")
+            .await
+            .expect("plans")
+            .expect("a plan");
+
+        assert_eq!(plan.id, "plan_m");
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/postgresql-addon/plans?orga_id=orga_1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn plans_fall_back_on_the_api_version_2() {
+        let server = Server::start(vec![
+            Response::error(404),
+            Response::json(200, provider_plans(PLANS)),
+        ])
+        .await;
+        let client = server.client();
+
+        let plans = plans(&client, &AddonProviderId::PostgreSql, "orga_1")
+            .await
+            .expect("plans");
+
+        assert_eq!(plans.len(), 2);
+        assert_eq!(
+            server.uris(),
+            vec![
+                "/v4/addon-providers/postgresql-addon/plans?orga_id=orga_1",
+                "/v2/products/addonproviders/postgresql-addon?orga_id=orga_1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn plans_do_not_fall_back_on_other_errors() {
+        let server = Server::start(vec![Response::error(500)]).await;
+        let client = server.client();
+
+        match plans(&client, &AddonProviderId::PostgreSql, "orga_1").await {
+            Err(Error::ListV4(id, organisation, ClientError::StatusCode(code, _))) => {
+                assert_eq!(id, AddonProviderId::PostgreSql);
+                assert_eq!(organisation, "orga_1");
+                assert_eq!(code.as_u16(), 500);
+            }
+            res => panic!("expected a list error, got {res:?}"),
+        }
+
+        assert_eq!(server.uris().len(), 1);
+    }
+
+    #[test]
+    fn plan_features_are_typed() {
+        let plans: Vec<Plan> = serde_json::from_str(PLANS).expect("plans to deserialize");
+
+        assert_eq!(
+            plans[1].typed_features(),
+            vec![
+                PlanFeature {
+                    name: "disk_size".to_string(),
+                    kind: FeatureKind::FileSize,
+                    value: FeatureValue::Bytes(40_000_000_000),
+                },
+                PlanFeature {
+                    name: "is_ha".to_string(),
+                    kind: FeatureKind::Boolean,
+                    value: FeatureValue::Bool(true),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_cheapest_returns_the_cheapest_matching_plan() {
+        let plans = format!(
+            r#"[
+                {{
+                    "id": "plan_l",
+                    "name": "L Big Space",
+                    "slug": "l_big",
+                    "price": 20.0,
+                    "price_id": "price_l",
+                    "features": [
+                        {{ "name": "is_ha", "type": "BOOLEAN", "value": "Yes", "computable_value": null, "name_code": null }}
+                    ],
+                    "zones": ["par"]
+                }},
+                {PLANS_ITEMS},
+                {{
+                    "id": "plan_l_bis",
+                    "name": "L Big Space bis",
+                    "slug": "l_big_bis",
+                    "price": 20.0,
+                    "price_id": "price_l_bis",
+                    "features": [
+                        {{ "name": "is_ha", "type": "BOOLEAN", "value": "Yes", "computable_value": null, "name_code": null }}
+                    ],
+                    "zones": ["par"]
+                }}
+            ]"#,
+            PLANS_ITEMS = PLANS.trim().trim_start_matches('[').trim_end_matches(']'),
+        );
+        let server = Server::start(vec![
+            Response::json(200, PLANS),
+            Response::json(200, plans.to_owned()),
+            Response::json(200, plans),
+        ])
+        .await;
+        let client = server.client();
+        let provider = AddonProviderId::PostgreSql;
+        let ha = [("is_ha".to_string(), "Yes".to_string())];
+
+        let plan = find_cheapest(&client, &provider, "orga_1", &[])
+            .await
+            .expect("plan");
+        assert_eq!(plan.id, "plan_xs");
+
+        let plan = find_cheapest(&client, &provider, "orga_1", &ha)
+            .await
+            .expect("plan");
+        assert_eq!(plan.id, "plan_l");
+
+        let plan = find_cheapest(
+            &client,
+            &provider,
+            "orga_1",
+            &[
+                ("is_ha".to_string(), "Yes".to_string()),
+                ("disk_size".to_string(), "40 GB".to_string()),
+            ],
+        )
+        .await
+        .expect("plan");
+        assert_eq!(plan.id, "plan_m");
+    }
+
+    #[tokio::test]
+    async fn find_cheapest_fails_without_matching_plan() {
+        let server = Server::start(vec![Response::json(200, PLANS)]).await;
+        let client = server.client();
+
+        assert!(matches!(
+            find_cheapest(
+                &client,
+                &AddonProviderId::PostgreSql,
+                "orga_1",
+                &[("disk_size".to_string(), "1 TB".to_string())],
+            )
+            .await,
+            Err(Error::NoMatchingPlan(AddonProviderId::PostgreSql))
+        ));
+    }
+}
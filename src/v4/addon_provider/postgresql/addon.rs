@@ -0,0 +1,259 @@
+//! # Postgresql addon module
+//!
+//! This module provide helpers and structures to retrieve the details of the
+//! instance of a dedicated postgresql addon
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v4::addon_provider::{
+        connection::{self, ConnectionUri},
+        AddonProviderId, Feature,
+    },
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// DatabaseStatus enumeration
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum DatabaseStatus {
+    Creating,
+    Running,
+    Migrating,
+    ToDelete,
+    Other(String),
+}
+
+impl From<String> for DatabaseStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "CREATING" => Self::Creating,
+            "RUNNING" => Self::Running,
+            "MIGRATING" => Self::Migrating,
+            "TO_DELETE" => Self::ToDelete,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for DatabaseStatus {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for DatabaseStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Creating => write!(f, "CREATING"),
+            Self::Running => write!(f, "RUNNING"),
+            Self::Migrating => write!(f, "MIGRATING"),
+            Self::ToDelete => write!(f, "TO_DELETE"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ReadOnlyUser structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct ReadOnlyUser {
+    #[serde(rename = "user")]
+    pub user: String,
+    #[serde(rename = "password")]
+    pub password: String,
+}
+
+impl Debug for ReadOnlyUser {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadOnlyUser")
+            .field("user", &self.user)
+            .field("password", &"[redacted]")
+            .finish()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DatabaseInfo structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct DatabaseInfo {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "host")]
+    pub host: String,
+    #[serde(rename = "port")]
+    pub port: u16,
+    #[serde(rename = "version")]
+    pub version: String,
+    #[serde(rename = "status")]
+    pub status: DatabaseStatus,
+    #[serde(rename = "readOnlyUsers", default)]
+    pub read_only_users: Vec<ReadOnlyUser>,
+    #[serde(rename = "features", default)]
+    pub features: Vec<Feature>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of postgresql addon '{0}', {1}")]
+    Get(String, ClientError),
+    #[error("failed to get connection uri of postgresql addon, {0}")]
+    ConnectionUri(connection::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve the details of the instance of the dedicated postgresql addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<DatabaseInfo, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::PostgreSql,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the postgresql addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+/// Retrieve the connection uris of the postgresql addon, the direct one
+/// bypasses the connection pooler, e.g. to dump the database
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn connection_uri<C>(client: &Client<C>, id: &str) -> Result<ConnectionUri, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    connection::get(client, &AddonProviderId::PostgreSql, id)
+        .await
+        .map_err(Error::ConnectionUri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{Response, Server},
+        v4::addon_provider::{FeatureName, Features},
+    };
+
+    const DATABASE: &str = r#"{
+        "id": "postgresql_1",
+        "host": "bq2xgdbtp8dcyd1e1tmz-postgresql.services.clever-cloud.com",
+        "port": 50013,
+        "version": "15",
+        "status": "RUNNING",
+        "readOnlyUsers": [
+            { "user": "reader_1", "password": "r3ad3r" }
+        ],
+        "features": [
+            { "name": "encryption-at-rest", "enabled": false },
+            { "name": "direct-host-access", "enabled": true }
+        ]
+    }"#;
+
+    #[test]
+    fn status_round_trips_through_strings() {
+        for (s, status) in [
+            ("CREATING", DatabaseStatus::Creating),
+            ("RUNNING", DatabaseStatus::Running),
+            ("MIGRATING", DatabaseStatus::Migrating),
+            ("TO_DELETE", DatabaseStatus::ToDelete),
+            (
+                "RESTARTING",
+                DatabaseStatus::Other("RESTARTING".to_string()),
+            ),
+        ] {
+            assert_eq!(DatabaseStatus::from(s.to_string()), status);
+            assert_eq!(status.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn read_only_users_redact_their_password() {
+        let info: DatabaseInfo = serde_json::from_str(DATABASE).expect("info to deserialize");
+
+        let debug = format!("{info:?}");
+
+        assert!(debug.contains("reader_1"));
+        assert!(!debug.contains("r3ad3r"));
+    }
+
+    #[test]
+    fn users_and_features_default_to_empty() {
+        let info: DatabaseInfo = serde_json::from_str(
+            r#"{ "id": "postgresql_1", "host": "host", "port": 5432, "version": "16", "status": "CREATING" }"#,
+        )
+        .expect("info to deserialize");
+
+        assert_eq!(info.status, DatabaseStatus::Creating);
+        assert!(info.read_only_users.is_empty());
+        assert!(info.features.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_details_of_the_instance() {
+        let server = Server::start(vec![Response::json(200, DATABASE)]).await;
+        let client = server.client();
+
+        let info = get(&client, "postgresql_1").await.expect("info");
+
+        assert_eq!(
+            info.host,
+            "bq2xgdbtp8dcyd1e1tmz-postgresql.services.clever-cloud.com"
+        );
+        assert_eq!(info.port, 50013);
+        assert_eq!(info.version, "15");
+        assert_eq!(info.status, DatabaseStatus::Running);
+        assert_eq!(
+            info.read_only_users,
+            vec![ReadOnlyUser {
+                user: "reader_1".to_string(),
+                password: "r3ad3r".to_string(),
+            }]
+        );
+        assert!(info.features.has(FeatureName::DirectHostAccess));
+        assert!(!info.features.has(FeatureName::EncryptionAtRest));
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/postgresql-addon/addons/postgresql_1"]
+        );
+        assert!(matches!(
+            get(&client, "postgresql_1").await,
+            Err(Error::Get(id, ClientError::StatusCode(..))) if id == "postgresql_1"
+        ));
+    }
+}
@@ -0,0 +1,397 @@
+//! # Postgresql migrations module
+//!
+//! This module provide helpers and structures to migrate a postgresql addon to
+//! another major version
+
+#[cfg(feature = "tokio")]
+use std::time::Instant;
+use std::{
+    convert::Infallible,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v4::addon_provider::{postgresql::Version, AddonProviderId},
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// MigrationStatus enumeration
+
+/// MigrationStatus enumeration is the status of a migration, it is shared by
+/// the version migrations of postgresql addons and the plan migrations of
+/// [`crate::v2::addon`]
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum MigrationStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+    Error,
+    Other(String),
+}
+
+impl FromStr for MigrationStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "PENDING" => Self::Pending,
+            "RUNNING" => Self::Running,
+            "DONE" => Self::Done,
+            "FAILED" => Self::Failed,
+            "ERROR" => Self::Error,
+            _ => Self::Other(s.to_owned()),
+        })
+    }
+}
+
+impl From<String> for MigrationStatus {
+    fn from(s: String) -> Self {
+        match Self::from_str(&s) {
+            Ok(status) => status,
+            Err(err) => match err {},
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for MigrationStatus {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for MigrationStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Pending => write!(f, "PENDING"),
+            Self::Running => write!(f, "RUNNING"),
+            Self::Done => write!(f, "DONE"),
+            Self::Failed => write!(f, "FAILED"),
+            Self::Error => write!(f, "ERROR"),
+            Self::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl MigrationStatus {
+    /// returns true, if the migration will not evolve anymore
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Done | Self::Failed | Self::Error)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// CreateOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct CreateOpts {
+    #[serde(rename = "targetVersion")]
+    pub target: Version,
+}
+
+// -----------------------------------------------------------------------------
+// Migration structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Migration {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "status")]
+    pub status: MigrationStatus,
+    #[serde(rename = "targetVersion")]
+    pub target: Version,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+// -----------------------------------------------------------------------------
+// PollOpts structure
+
+/// PollOpts structure configures how to wait for a migration to complete
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PollOpts {
+    pub interval: Duration,
+    pub timeout: Duration,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to migrate postgresql addon '{0}' to version '{1}', {2}")]
+    Create(String, Version, ClientError),
+    #[error("failed to get migration '{0}' of postgresql addon '{1}', {2}")]
+    Get(String, String, ClientError),
+    #[error("failed to list migrations of postgresql addon '{0}', {1}")]
+    List(String, ClientError),
+    #[error(
+        "failed to wait for migration '{0}' of postgresql addon '{1}', still '{2}' after {3}s"
+    )]
+    Timeout(String, String, MigrationStatus, u64),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Migrate the postgresql addon to the given version and returns the started
+/// migration
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn create<C>(client: &Client<C>, id: &str, target: Version) -> Result<Migration, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/migrations",
+        client.endpoint,
+        AddonProviderId::PostgreSql,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to migrate the postgresql addon, path: '{}', id: '{}', version: '{}'",
+            &path, id, target
+        );
+    }
+
+    let opts = CreateOpts {
+        target: target.to_owned(),
+    };
+
+    client
+        .post(&path, &opts)
+        .await
+        .map_err(|err| Error::Create(id.to_string(), target, err))
+}
+
+/// Retrieve the given migration of the postgresql addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str, migration_id: &str) -> Result<Migration, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/migrations/{}",
+        client.endpoint,
+        AddonProviderId::PostgreSql,
+        id,
+        migration_id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get a migration of the postgresql addon, path: '{}', id: '{}', migration: '{}'",
+            &path, id, migration_id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(migration_id.to_string(), id.to_string(), err))
+}
+
+/// Retrieve the migrations of the postgresql addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn list<C>(client: &Client<C>, id: &str) -> Result<Vec<Migration>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/migrations",
+        client.endpoint,
+        AddonProviderId::PostgreSql,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list migrations of the postgresql addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::List(id.to_string(), err))
+}
+
+/// Poll the given migration of the postgresql addon until it reaches a
+/// terminal status and returns it, gives up after the timeout
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn wait<C>(
+    client: &Client<C>,
+    id: &str,
+    migration_id: &str,
+    opts: PollOpts,
+) -> Result<Migration, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let start = Instant::now();
+    loop {
+        let migration = get(client, id, migration_id).await?;
+        if migration.status.is_terminal() {
+            return Ok(migration);
+        }
+
+        if start.elapsed() + opts.interval > opts.timeout {
+            return Err(Error::Timeout(
+                migration_id.to_owned(),
+                id.to_owned(),
+                migration.status,
+                opts.timeout.as_secs(),
+            ));
+        }
+
+        tokio::time::sleep(opts.interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// returns a migration of the addon to the version 15 in the given status
+    fn migration(status: &str) -> String {
+        format!(
+            r#"{{"id":"migration_1","status":"{status}","targetVersion":15,"createdAt":"2024-05-01T10:00:00Z"}}"#
+        )
+    }
+
+    #[test]
+    fn statuses_round_trip() {
+        for status in [
+            "PENDING",
+            "RUNNING",
+            "DONE",
+            "FAILED",
+            "ERROR",
+            "ROLLING_BACK",
+        ] {
+            let typed = MigrationStatus::from(status.to_string());
+            assert_eq!(typed.to_string(), status);
+        }
+
+        assert_eq!(
+            MigrationStatus::from("ROLLING_BACK".to_string()),
+            MigrationStatus::Other("ROLLING_BACK".to_string())
+        );
+        assert!(MigrationStatus::Done.is_terminal());
+        assert!(MigrationStatus::Failed.is_terminal());
+        assert!(MigrationStatus::Error.is_terminal());
+        assert!(!MigrationStatus::Running.is_terminal());
+    }
+
+    #[tokio::test]
+    async fn create_sends_the_target_version() {
+        let server = Server::start(vec![Response::json(200, migration("PENDING"))]).await;
+        let client = server.client();
+
+        let migration = create(&client, "postgresql_1", Version::V15)
+            .await
+            .expect("migration");
+
+        assert_eq!(migration.status, MigrationStatus::Pending);
+        assert_eq!(migration.target, Version::V15);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(
+            requests[0].uri,
+            "/v4/addon-providers/postgresql-addon/addons/postgresql_1/migrations"
+        );
+        assert_eq!(
+            requests[0].json(),
+            serde_json::json!({ "targetVersion": 15 })
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_polls_until_the_migration_is_done() {
+        let server = Server::start(vec![
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("DONE")),
+        ])
+        .await;
+        let client = server.client();
+        let opts = PollOpts {
+            interval: Duration::from_millis(1),
+            timeout: Duration::from_secs(5),
+        };
+
+        let migration = wait(&client, "postgresql_1", "migration_1", opts)
+            .await
+            .expect("migration");
+
+        assert_eq!(migration.status, MigrationStatus::Done);
+        assert_eq!(server.uris().len(), 3);
+        assert!(server.uris().iter().all(|uri| uri
+            == "/v4/addon-providers/postgresql-addon/addons/postgresql_1/migrations/migration_1"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_stops_on_a_failed_migration() {
+        let server = Server::start(vec![
+            Response::json(200, migration("RUNNING")),
+            Response::json(200, migration("FAILED")),
+            Response::json(200, migration("DONE")),
+        ])
+        .await;
+        let client = server.client();
+        let opts = PollOpts {
+            interval: Duration::from_millis(1),
+            timeout: Duration::from_secs(5),
+        };
+
+        let migration = wait(&client, "postgresql_1", "migration_1", opts)
+            .await
+            .expect("migration");
+
+        assert_eq!(migration.status, MigrationStatus::Failed);
+        assert_eq!(server.uris().len(), 2);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_gives_up_after_the_timeout() {
+        let server = Server::start(vec![Response::json(200, migration("RUNNING"))]).await;
+        let client = server.client();
+        let opts = PollOpts {
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_millis(10),
+        };
+
+        assert!(matches!(
+            wait(&client, "postgresql_1", "migration_1", opts).await,
+            Err(Error::Timeout(_, _, MigrationStatus::Running, _))
+        ));
+    }
+}
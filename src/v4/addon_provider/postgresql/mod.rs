@@ -0,0 +1,257 @@
+//! # Postgresql addon provider module
+//!
+//! This module provide helpers and structures to interact with the postgresql
+//! addon provider
+
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+use hyper::client::connect::Connect;
+use oauth10a::client::ClientError;
+#[cfg(feature = "jsonschemas")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
+    Client,
+};
+
+pub mod addon;
+pub mod migrations;
+pub mod plan;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(
+        "failed to parse version from '{0}', available versions are 17, 16, 15, 14, 13, 12 and 11"
+    )]
+    ParseVersion(String),
+    #[error("failed to get information about addon provider '{0}', {1}")]
+    Get(AddonProviderId, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Version enum
+
+/// Version enum lists the postgresql versions, unknown ones are kept by code
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(from = "i32", into = "i32")]
+pub enum Version {
+    V17,
+    V16,
+    V15,
+    V14,
+    V13,
+    V12,
+    V11,
+    Unknown(i32),
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "17" => Self::V17,
+            "16" => Self::V16,
+            "15" => Self::V15,
+            "14" => Self::V14,
+            "13" => Self::V13,
+            "12" => Self::V12,
+            "11" => Self::V11,
+            _ => s
+                .parse::<i32>()
+                .map(Self::from)
+                .map_err(|_| Error::ParseVersion(s.to_owned()))?,
+        })
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl From<i32> for Version {
+    fn from(n: i32) -> Self {
+        match n {
+            17 => Self::V17,
+            16 => Self::V16,
+            15 => Self::V15,
+            14 => Self::V14,
+            13 => Self::V13,
+            12 => Self::V12,
+            11 => Self::V11,
+            _ => Self::Unknown(n),
+        }
+    }
+}
+
+impl From<Version> for i32 {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::V17 => 17,
+            Version::V16 => 16,
+            Version::V15 => 15,
+            Version::V14 => 14,
+            Version::V13 => 13,
+            Version::V12 => 12,
+            Version::V11 => 11,
+            Version::Unknown(n) => n,
+        }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<String> for Version {
+    fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        i32::from(self.to_owned()).cmp(&i32::from(other.to_owned()))
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V17 => write!(f, "17"),
+            Self::V16 => write!(f, "16"),
+            Self::V15 => write!(f, "15"),
+            Self::V14 => write!(f, "14"),
+            Self::V13 => write!(f, "13"),
+            Self::V12 => write!(f, "12"),
+            Self::V11 => write!(f, "11"),
+            Self::Unknown(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+#[cfg(feature = "jsonschemas")]
+impl JsonSchema for Version {
+    fn schema_name() -> String {
+        "Version".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        i32::json_schema(gen)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns information about the postgresql addon provider
+pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<Version>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    addon_provider::get_provider(client, AddonProviderId::PostgreSql)
+        .await
+        .map_err(|err| Error::Get(AddonProviderId::PostgreSql, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    /// payload of the addon provider as returned when only the versions 11 to
+    /// 15 were offered, along with a cluster still running the retired 10
+    const LEGACY_PROVIDER: &str = r#"{
+        "providerId": "postgresql-addon",
+        "clusters": [{
+            "id": "cluster_1",
+            "label": "shared",
+            "zone": "par",
+            "features": [],
+            "version": 10
+        }],
+        "dedicated": {
+            "15": [{ "name": "encryption-at-rest", "enabled": true }],
+            "14": [],
+            "13": [],
+            "12": [],
+            "11": []
+        },
+        "defaultDedicatedVersion": 15
+    }"#;
+
+    #[test]
+    fn legacy_payloads_still_deserialize() {
+        let provider: AddonProvider<Version> =
+            serde_json::from_str(LEGACY_PROVIDER).expect("provider to deserialize");
+
+        assert_eq!(provider.default, Version::V15);
+        assert_eq!(provider.clusters[0].version, Version::Unknown(10));
+        assert_eq!(
+            provider.available_versions(),
+            vec![
+                &Version::V11,
+                &Version::V12,
+                &Version::V13,
+                &Version::V14,
+                &Version::V15
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&provider).expect("provider to serialize")
+                ["defaultDedicatedVersion"],
+            15
+        );
+    }
+
+    #[test]
+    fn versions_parse_like_they_print() {
+        for version in ["17", "16", "15", "14", "13", "12", "11", "10"] {
+            let parsed = Version::from_str(version).expect("version");
+            assert_eq!(parsed.to_string(), version);
+        }
+
+        assert_eq!(Version::from_str("16").ok(), Some(Version::V16));
+        assert!(Version::from_str("9.6").is_err());
+        assert!(Version::V17 > Version::V16);
+    }
+
+    #[tokio::test]
+    async fn get_returns_the_modern_versions() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"{
+                "providerId": "postgresql-addon",
+                "clusters": [],
+                "dedicated": { "17": [], "16": [], "15": [] },
+                "defaultDedicatedVersion": 16
+            }"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let provider = get(&client).await.expect("provider");
+
+        assert_eq!(provider.default, Version::V16);
+        assert!(provider.supports(&Version::V17));
+        assert_eq!(server.uris(), vec!["/v4/addon-providers/postgresql-addon"]);
+    }
+}
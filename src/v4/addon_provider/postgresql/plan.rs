@@ -0,0 +1,165 @@
+//! # Postgresql addon provider plan module
+//!
+//! This module provide helpers and structures to interact with the plans of
+//! the postgresql addon provider and their specific features
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    v2::addon::{Feature, FeatureValue},
+    v4::addon_provider::{
+        plan::{self, Plan},
+        AddonProviderId,
+    },
+    Client,
+};
+
+// -----------------------------------------------------------------------------
+// PgPlanFeatures structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct PgPlanFeatures {
+    #[serde(rename = "connection_limit")]
+    pub connection_limit: Option<u64>,
+    #[serde(rename = "disk_size")]
+    pub disk_size: Option<u64>,
+    #[serde(rename = "high_availability")]
+    pub high_availability: bool,
+    #[serde(rename = "max_allowed_packet")]
+    pub max_allowed_packet: Option<u64>,
+}
+
+impl From<&[Feature]> for PgPlanFeatures {
+    fn from(features: &[Feature]) -> Self {
+        let mut pg_features = Self::default();
+
+        for feature in features {
+            let code = feature
+                .name_code
+                .as_deref()
+                .unwrap_or(&feature.name)
+                .trim()
+                .to_lowercase()
+                .replace([' ', '-'], "_");
+
+            match (code.as_str(), feature.typed_value()) {
+                ("connection_limit" | "max_connection_limit", value) => {
+                    pg_features.connection_limit = as_u64(value);
+                }
+                ("disk_size" | "max_db_size", value) => {
+                    pg_features.disk_size = as_u64(value);
+                }
+                ("high_availability" | "is_ha", FeatureValue::Bool(b)) => {
+                    pg_features.high_availability = b;
+                }
+                ("max_allowed_packet", value) => {
+                    pg_features.max_allowed_packet = as_u64(value);
+                }
+                _ => {}
+            }
+        }
+
+        pg_features
+    }
+}
+
+/// returns the value as an unsigned integer, if it represents one
+fn as_u64(value: FeatureValue) -> Option<u64> {
+    match value {
+        FeatureValue::Bytes(n) => Some(n),
+        FeatureValue::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as u64),
+        FeatureValue::Text(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PostgreSqlPlan structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Clone, Debug)]
+pub struct PostgreSqlPlan {
+    #[serde(flatten)]
+    pub plan: Plan,
+    #[serde(rename = "pg_features")]
+    pub pg_features: PgPlanFeatures,
+}
+
+impl From<Plan> for PostgreSqlPlan {
+    fn from(plan: Plan) -> Self {
+        let pg_features = PgPlanFeatures::from(plan.features.as_slice());
+
+        Self { plan, pg_features }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list plans of the postgresql addon provider for organisation '{0}', {1}")]
+    List(String, plan::Error),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers functions
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of plans of the postgresql addon provider with their
+/// specific features
+pub async fn list<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+) -> Result<Vec<PostgreSqlPlan>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to list plans of the postgresql addon-provider, organisation: '{}'",
+            organisation_id
+        );
+    }
+
+    Ok(
+        plan::list(client, &AddonProviderId::PostgreSql, organisation_id)
+            .await
+            .map_err(|err| Error::List(organisation_id.to_owned(), err))?
+            .plans
+            .into_iter()
+            .map(PostgreSqlPlan::from)
+            .collect(),
+    )
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the plan with the lowest connection limit that allows at least the
+/// given number of connections, the cheapest one is picked on equality
+pub fn find_by_connection_limit(
+    plans: &[PostgreSqlPlan],
+    min_connections: u64,
+) -> Option<&PostgreSqlPlan> {
+    plans
+        .iter()
+        .filter(|plan| {
+            plan.pg_features
+                .connection_limit
+                .is_some_and(|limit| limit >= min_connections)
+        })
+        .min_by(|a, b| {
+            a.pg_features
+                .connection_limit
+                .cmp(&b.pg_features.connection_limit)
+                .then(a.plan.price.total_cmp(&b.plan.price))
+        })
+}
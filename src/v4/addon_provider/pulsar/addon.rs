@@ -0,0 +1,274 @@
+//! # Pulsar addon module
+//!
+//! This module provide helpers and structures to interact with the pulsar
+//! addon, its tenant, namespace and policies
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{v4::addon_provider::AddonProviderId, Client};
+
+// -----------------------------------------------------------------------------
+// PulsarAddon structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct PulsarAddon {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "tenant")]
+    pub tenant: String,
+    #[serde(rename = "namespace")]
+    pub namespace: String,
+    #[serde(rename = "brokerUrl")]
+    pub broker_url: String,
+    #[serde(rename = "webServiceUrl")]
+    pub web_service_url: String,
+}
+
+// -----------------------------------------------------------------------------
+// Policies structure
+
+/// Policies structure holds the retention, time to live and offload settings
+/// of the namespace, sizes are expressed in bytes and durations in seconds, an
+/// unset value stands for the default of the cluster
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct Policies {
+    #[serde(
+        rename = "retentionSizeInBytes",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub retention_size: Option<u64>,
+    #[serde(
+        rename = "retentionTimeInSeconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub retention_time: Option<u64>,
+    #[serde(
+        rename = "messageTtlInSeconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub message_ttl: Option<u64>,
+    #[serde(
+        rename = "offloadThresholdInBytes",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub offload_threshold: Option<u64>,
+    #[serde(
+        rename = "offloadDeletionLagInSeconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub offload_deletion_lag: Option<u64>,
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to get information of pulsar addon '{0}', {1}")]
+    Get(String, ClientError),
+    #[error("failed to get policies of pulsar addon '{0}', {1}")]
+    Policies(String, ClientError),
+    #[error("failed to update policies of pulsar addon '{0}', {1}")]
+    SetPolicies(String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+/// Retrieve information about the pulsar addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn get<C>(client: &Client<C>, id: &str) -> Result<PulsarAddon, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}",
+        client.endpoint,
+        AddonProviderId::Pulsar,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get information about the pulsar addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Get(id.to_string(), err))
+}
+
+/// Retrieve the retention, time to live and offload policies of the pulsar
+/// addon
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn policies<C>(client: &Client<C>, id: &str) -> Result<Policies, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/policies",
+        client.endpoint,
+        AddonProviderId::Pulsar,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to get policies of the pulsar addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .get(&path)
+        .await
+        .map_err(|err| Error::Policies(id.to_string(), err))
+}
+
+/// Update the retention, time to live and offload policies of the pulsar
+/// addon and returns them as applied
+#[cfg_attr(feature = "trace", tracing::instrument)]
+pub async fn set_policies<C>(
+    client: &Client<C>,
+    id: &str,
+    policies: &Policies,
+) -> Result<Policies, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/addon-providers/{}/addons/{}/policies",
+        client.endpoint,
+        AddonProviderId::Pulsar,
+        id
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!(
+            "execute a request to update policies of the pulsar addon, path: '{}', id: '{}'",
+            &path, id
+        );
+    }
+
+    client
+        .put(&path, policies)
+        .await
+        .map_err(|err| Error::SetPolicies(id.to_string(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    #[tokio::test]
+    async fn get_returns_the_tenant_and_namespace() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"{
+                "id": "pulsar_1",
+                "ownerId": "orga_1",
+                "tenant": "orga_1",
+                "namespace": "pulsar_1",
+                "brokerUrl": "pulsar+ssl://c2-pulsar-clevercloud-customers.services.clever-cloud.com:2002",
+                "webServiceUrl": "https://c2-pulsar-clevercloud-customers.services.clever-cloud.com:2001"
+            }"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let addon = get(&client, "pulsar_1").await.expect("addon");
+
+        assert_eq!(addon.tenant, "orga_1");
+        assert_eq!(addon.namespace, "pulsar_1");
+        assert!(addon.broker_url.starts_with("pulsar+ssl://"));
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/addon-pulsar/addons/pulsar_1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn policies_are_typed() {
+        let server = Server::start(vec![Response::json(
+            200,
+            r#"{ "retentionSizeInBytes": 1073741824, "retentionTimeInSeconds": 604800 }"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let policies = policies(&client, "pulsar_1").await.expect("policies");
+
+        assert_eq!(
+            policies,
+            Policies {
+                retention_size: Some(1_073_741_824),
+                retention_time: Some(604_800),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            server.uris(),
+            vec!["/v4/addon-providers/addon-pulsar/addons/pulsar_1/policies"]
+        );
+    }
+
+    #[tokio::test]
+    async fn set_policies_puts_only_the_set_values() {
+        let body = r#"{ "messageTtlInSeconds": 3600, "offloadThresholdInBytes": 536870912 }"#;
+        let server = Server::start(vec![Response::json(200, body)]).await;
+        let client = server.client();
+        let policies = Policies {
+            message_ttl: Some(3_600),
+            offload_threshold: Some(536_870_912),
+            ..Default::default()
+        };
+
+        let applied = set_policies(&client, "pulsar_1", &policies)
+            .await
+            .expect("policies");
+
+        assert_eq!(applied, policies);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(
+            requests[0].uri,
+            "/v4/addon-providers/addon-pulsar/addons/pulsar_1/policies"
+        );
+        assert_eq!(
+            requests[0].json(),
+            serde_json::json!({
+                "messageTtlInSeconds": 3600,
+                "offloadThresholdInBytes": 536870912
+            })
+        );
+        assert!(matches!(
+            set_policies(&client, "pulsar_1", &policies).await,
+            Err(Error::SetPolicies(..))
+        ));
+    }
+}
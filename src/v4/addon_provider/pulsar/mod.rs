@@ -0,0 +1,6 @@
+//! # Pulsar addon provider module
+//!
+//! This module provide helpers and structures to interact with the pulsar
+//! addon provider
+
+pub mod addon;
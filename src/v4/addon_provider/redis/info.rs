@@ -0,0 +1,119 @@
+//! # Info module
+//!
+//! This module provide structures to read statistics of a redis addon from the
+//! output of the `INFO` command, see <https://redis.io/docs/latest/commands/info/>
+
+use std::{collections::BTreeMap, str::FromStr};
+
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// -----------------------------------------------------------------------------
+// RedisInfo structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct RedisInfo {
+    #[serde(rename = "used_memory")]
+    pub used_memory: u64,
+    #[serde(rename = "connected_clients")]
+    pub connected_clients: u32,
+    #[serde(rename = "total_commands_processed")]
+    pub total_commands_processed: u64,
+    #[serde(rename = "keyspace_hits")]
+    pub keyspace_hits: u64,
+    #[serde(rename = "keyspace_misses")]
+    pub keyspace_misses: u64,
+}
+
+impl FromStr for RedisInfo {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: BTreeMap<&str, &str> = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once(':'))
+            .collect();
+
+        Ok(Self {
+            used_memory: field(&fields, "used_memory")?,
+            connected_clients: field(&fields, "connected_clients")?,
+            total_commands_processed: field(&fields, "total_commands_processed")?,
+            keyspace_hits: field(&fields, "keyspace_hits")?,
+            keyspace_misses: field(&fields, "keyspace_misses")?,
+        })
+    }
+}
+
+fn field<T>(fields: &BTreeMap<&str, &str>, name: &str) -> Result<T, Error>
+where
+    T: FromStr,
+{
+    fields
+        .get(name)
+        .ok_or_else(|| Error::ParseInfo(format!("missing field '{name}'")))?
+        .parse()
+        .map_err(|_| Error::ParseInfo(format!("invalid value for field '{name}'")))
+}
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to parse redis info, {0}")]
+    ParseInfo(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO: &str = "# Server\r\n\
+                        redis_version:7.0.4\r\n\
+                        \r\n\
+                        # Clients\r\n\
+                        connected_clients:3\r\n\
+                        \r\n\
+                        # Memory\r\n\
+                        used_memory:1048576\r\n\
+                        \r\n\
+                        # Stats\r\n\
+                        total_commands_processed:42\r\n\
+                        keyspace_hits:30\r\n\
+                        keyspace_misses:12\r\n";
+
+    #[test]
+    fn parse_info_output() {
+        let info = RedisInfo::from_str(INFO).expect("info to parse");
+
+        assert_eq!(
+            info,
+            RedisInfo {
+                used_memory: 1_048_576,
+                connected_clients: 3,
+                total_commands_processed: 42,
+                keyspace_hits: 30,
+                keyspace_misses: 12,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_info_output_with_missing_or_invalid_fields() {
+        let missing = INFO.replace("keyspace_hits:30\r\n", "");
+        assert!(matches!(
+            RedisInfo::from_str(&missing),
+            Err(Error::ParseInfo(msg)) if msg == "missing field 'keyspace_hits'"
+        ));
+
+        let invalid = INFO.replace("connected_clients:3", "connected_clients:-3");
+        assert!(matches!(
+            RedisInfo::from_str(&invalid),
+            Err(Error::ParseInfo(msg)) if msg == "invalid value for field 'connected_clients'"
+        ));
+    }
+}
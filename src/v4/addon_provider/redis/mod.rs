@@ -5,24 +5,25 @@
 #![allow(deprecated)]
 
 use std::{
+    cmp::Ordering,
     convert::TryFrom,
     fmt::{self, Debug, Display, Formatter},
     str::FromStr,
 };
 
 use hyper::client::connect::Connect;
-#[cfg(feature = "logging")]
-use log::{debug, log_enabled, Level};
-use oauth10a::client::{ClientError, RestClient};
+use oauth10a::client::ClientError;
 #[cfg(feature = "jsonschemas")]
-use schemars::JsonSchema_repr as JsonSchemaRepr;
-use serde_repr::{Deserialize_repr as DeserializeRepr, Serialize_repr as SerializeRepr};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    v4::addon_provider::{AddonProvider, AddonProviderId},
+    v4::addon_provider::{self, AddonProvider, AddonProviderId},
     Client,
 };
 
+pub mod info;
+
 // -----------------------------------------------------------------------------
 // Error enumeration
 
@@ -37,14 +38,14 @@ pub enum Error {
 // -----------------------------------------------------------------------------
 // Version enum
 
-#[cfg_attr(feature = "jsonschemas", derive(JsonSchemaRepr))]
-#[derive(SerializeRepr, DeserializeRepr, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
-#[serde(untagged)]
-#[repr(i32)]
+/// Version enum lists the redis versions, unknown ones are kept by code
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(from = "i32", into = "i32")]
 pub enum Version {
     #[deprecated]
-    V6dot2dot6 = 626,
-    V7dot0dot4 = 704,
+    V6dot2dot6,
+    V7dot0dot4,
+    Unknown(i32),
 }
 
 impl FromStr for Version {
@@ -54,9 +55,10 @@ impl FromStr for Version {
         Ok(match s {
             "6.2.6" => Self::V6dot2dot6,
             "7.0.4" => Self::V7dot0dot4,
-            _ => {
-                return Err(Error::ParseVersion(s.to_owned()));
-            }
+            _ => addon_provider::parse_dotted_version(s, 3)
+                .or_else(|| s.parse::<i32>().ok())
+                .map(Self::from)
+                .ok_or_else(|| Error::ParseVersion(s.to_owned()))?,
         })
     }
 }
@@ -69,6 +71,26 @@ impl TryFrom<String> for Version {
     }
 }
 
+impl From<i32> for Version {
+    fn from(n: i32) -> Self {
+        match n {
+            626 => Self::V6dot2dot6,
+            704 => Self::V7dot0dot4,
+            _ => Self::Unknown(n),
+        }
+    }
+}
+
+impl From<Version> for i32 {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::V6dot2dot6 => 626,
+            Version::V7dot0dot4 => 704,
+            Version::Unknown(n) => n,
+        }
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<String> for Version {
     fn into(self) -> String {
@@ -76,15 +98,39 @@ impl Into<String> for Version {
     }
 }
 
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        i32::from(self.to_owned()).cmp(&i32::from(other.to_owned()))
+    }
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::V6dot2dot6 => write!(f, "6.2.6"),
             Self::V7dot0dot4 => write!(f, "7.0.4"),
+            Self::Unknown(n) => write!(f, "{}", addon_provider::format_dotted_version(*n, 3)),
         }
     }
 }
 
+#[cfg(feature = "jsonschemas")]
+impl JsonSchema for Version {
+    fn schema_name() -> String {
+        "Version".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        i32::json_schema(gen)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Helpers functions
 
@@ -94,19 +140,7 @@ pub async fn get<C>(client: &Client<C>) -> Result<AddonProvider<Version>, Error>
 where
     C: Connect + Clone + Debug + Send + Sync + 'static,
 {
-    let path = format!(
-        "{}/v4/addon-providers/{}",
-        client.endpoint,
-        AddonProviderId::Redis
-    );
-
-    #[cfg(feature = "logging")]
-    if log_enabled!(Level::Debug) {
-        debug!("execute a request to get information about the redis addon-provider, path: '{}', name: '{}'", &path, AddonProviderId::Redis);
-    }
-
-    client
-        .get(&path)
+    addon_provider::get_provider(client, AddonProviderId::Redis)
         .await
         .map_err(|err| Error::Get(AddonProviderId::Redis, err))
 }
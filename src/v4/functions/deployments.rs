@@ -3,9 +3,15 @@
 //! This module provides structures to interact with functions' deployments.
 
 use std::{
+    cmp::Reverse,
     fmt::{self, Debug, Display, Formatter},
     str::FromStr,
 };
+#[cfg(feature = "tokio")]
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
 
 use chrono::{DateTime, Utc};
 use hyper::{
@@ -48,6 +54,14 @@ pub enum Error {
     Execute(ClientError),
     #[error("failed to execute request, got status code {0}")]
     StatusCode(u16),
+    #[error("failed to deploy '{0}', {1}")]
+    Failed(String, String),
+    #[error("failed to wait for deployment '{0}' to be ready, still '{1}' after {2}s")]
+    Timeout(String, Status, u64),
+    #[error("failed to read file '{0}', {1}")]
+    ReadFile(String, std::io::Error),
+    #[error("failed to upload file '{0}', it is not a WebAssembly module")]
+    InvalidWasmFile(String),
 }
 
 // ----------------------------------------------------------------------------
@@ -90,6 +104,95 @@ impl FromStr for Platform {
     }
 }
 
+// ----------------------------------------------------------------------------
+// Platform detection
+
+/// Magic number and version at the start of a WebAssembly module
+const WASM_HEADER: &[u8] = b"\0asm\x01\0\0\0";
+const WASM_SECTION_CUSTOM: u8 = 0;
+const WASM_SECTION_EXPORT: u8 = 7;
+
+/// returns if the buffer starts like a WebAssembly module
+pub fn is_wasm(buf: &[u8]) -> bool {
+    buf.starts_with(WASM_HEADER)
+}
+
+/// returns the platform that produced the WebAssembly module, this is a
+/// best-effort heuristic based on custom sections and exports left by the
+/// toolchains, `None` is returned when uncertain or if the module is malformed
+pub fn detect_platform(buf: &[u8]) -> Option<Platform> {
+    let mut names = vec![];
+    let mut cursor = buf.strip_prefix(WASM_HEADER)?;
+
+    while let Some((&id, rest)) = cursor.split_first() {
+        let (size, rest) = read_leb128(rest)?;
+        let size = usize::try_from(size).ok()?;
+        if size > rest.len() {
+            return None;
+        }
+
+        let (section, rest) = rest.split_at(size);
+        match id {
+            WASM_SECTION_CUSTOM => names.push(read_name(section)?.0),
+            WASM_SECTION_EXPORT => {
+                let (count, mut entries) = read_leb128(section)?;
+                for _ in 0..count {
+                    let (name, rest) = read_name(entries)?;
+                    // skip the export kind and the index
+                    let (_, rest) = rest.split_first()?;
+                    let (_, rest) = read_leb128(rest)?;
+
+                    names.push(name);
+                    entries = rest;
+                }
+            }
+            _ => {}
+        }
+
+        cursor = rest;
+    }
+
+    if names
+        .iter()
+        .any(|name| name.starts_with("__wbindgen") || name.starts_with("__wasm_bindgen"))
+    {
+        return Some(Platform::Rust);
+    }
+
+    if names.contains(&"__js_startup") {
+        return Some(Platform::JavaScript);
+    }
+
+    None
+}
+
+/// returns the unsigned LEB128 integer at the start of the buffer and the
+/// remaining bytes
+fn read_leb128(buf: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (idx, byte) in buf.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * idx);
+        if byte & 0x80 == 0 {
+            return Some((value, &buf[idx + 1..]));
+        }
+    }
+
+    None
+}
+
+/// returns the utf-8 name prefixed by its length at the start of the buffer
+/// and the remaining bytes
+fn read_name(buf: &[u8]) -> Option<(&str, &[u8])> {
+    let (len, rest) = read_leb128(buf)?;
+    let len = usize::try_from(len).ok()?;
+    if len > rest.len() {
+        return None;
+    }
+
+    let (name, rest) = rest.split_at(len);
+    Some((std::str::from_utf8(name).ok()?, rest))
+}
+
 // ----------------------------------------------------------------------------
 // Status
 
@@ -134,6 +237,29 @@ impl FromStr for Status {
     }
 }
 
+impl Status {
+    /// returns the http status code matching the deployment status, terminal
+    /// states are mapped to 200 (ready) or 500 (error) and pending ones to 202
+    pub fn to_http_status_code(&self) -> Option<u16> {
+        match self {
+            Self::WaitingForUpload | Self::Packaging | Self::Deploying => Some(202),
+            Self::Ready => Some(200),
+            Self::Error => Some(500),
+        }
+    }
+
+    /// returns the deployment status matching the http status code, pending
+    /// code 202 is mapped to the deploying status
+    pub fn try_from_http_status(code: u16) -> Option<Self> {
+        match code {
+            200 => Some(Self::Ready),
+            202 => Some(Self::Deploying),
+            500 => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Opts
 
@@ -149,6 +275,65 @@ pub struct Opts {
     pub platform: Platform,
 }
 
+// ----------------------------------------------------------------------------
+// BuildError
+
+#[derive(thiserror::Error, PartialEq, Eq, Clone, Debug)]
+pub enum BuildError {
+    #[error("failed to build deployment options, the platform is required")]
+    MissingPlatform,
+}
+
+// ----------------------------------------------------------------------------
+// DeploymentOptsBuilder
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct DeploymentOptsBuilder {
+    name: Option<String>,
+    description: Option<String>,
+    tag: Option<String>,
+    platform: Option<Platform>,
+}
+
+impl DeploymentOptsBuilder {
+    pub fn name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn tag(mut self, tag: String) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    pub fn platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    /// returns the deployment options, the platform must have been set as it
+    /// is required by the api to create a deployment
+    pub fn build(self) -> Result<Opts, BuildError> {
+        Ok(Opts {
+            name: self.name,
+            description: self.description,
+            tag: self.tag,
+            platform: self.platform.ok_or(BuildError::MissingPlatform)?,
+        })
+    }
+}
+
+impl Opts {
+    pub fn builder() -> DeploymentOptsBuilder {
+        DeploymentOptsBuilder::default()
+    }
+}
+
 // ----------------------------------------------------------------------------
 // DeploymentCreation
 
@@ -172,12 +357,74 @@ pub struct DeploymentCreation {
     pub reason: Option<String>,
     #[serde(rename = "uploadUrl")]
     pub upload_url: String,
+    #[cfg_attr(
+        feature = "serde_with",
+        serde(
+            serialize_with = "super::serialize_timestamp",
+            deserialize_with = "super::deserialize_timestamp"
+        )
+    )]
     #[serde(rename = "createdAt")]
     pub created_at: DateTime<Utc>,
+    #[cfg_attr(
+        feature = "serde_with",
+        serde(
+            serialize_with = "super::serialize_timestamp",
+            deserialize_with = "super::deserialize_timestamp"
+        )
+    )]
     #[serde(rename = "updatedAt")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl DeploymentCreation {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the identifier of the deployment
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the current state of the deployment
+    pub async fn fetch<C>(
+        &self,
+        client: &Client<C>,
+        organisation_id: &str,
+        function_id: &str,
+    ) -> Result<Deployment, Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        get(client, organisation_id, function_id, &self.id).await
+    }
+
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// poll the deployment every `poll_interval` until it is ready and returns
+    /// it, gives up after `timeout`
+    pub async fn wait_until_ready<C>(
+        &self,
+        client: &Client<C>,
+        organisation_id: &str,
+        function_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Deployment, Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        wait_until_ready(
+            client,
+            organisation_id,
+            function_id,
+            &self.id,
+            poll_interval,
+            timeout,
+        )
+        .await
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Deployment
 
@@ -201,12 +448,67 @@ pub struct Deployment {
     pub reason: Option<String>,
     #[serde(rename = "url")]
     pub url: Option<String>,
+    #[cfg_attr(
+        feature = "serde_with",
+        serde(
+            serialize_with = "super::serialize_timestamp",
+            deserialize_with = "super::deserialize_timestamp"
+        )
+    )]
     #[serde(rename = "createdAt")]
     pub created_at: DateTime<Utc>,
+    #[cfg_attr(
+        feature = "serde_with",
+        serde(
+            serialize_with = "super::serialize_timestamp",
+            deserialize_with = "super::deserialize_timestamp"
+        )
+    )]
     #[serde(rename = "updatedAt")]
     pub updated_at: DateTime<Utc>,
 }
 
+// ----------------------------------------------------------------------------
+// Sorting
+
+#[derive(Hash, Ord, PartialOrd, Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// sort the deployments by creation date in the given order
+pub fn sort_by_created_at(deployments: &mut [Deployment], order: SortOrder) {
+    match order {
+        SortOrder::Asc => deployments.sort_by_key(|deployment| deployment.created_at),
+        SortOrder::Desc => deployments.sort_by_key(|deployment| Reverse(deployment.created_at)),
+    }
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// sort the deployments by status, ready ones first, then the ones in progress
+/// from the most to the least advanced and the ones in error last, the order
+/// of deployments sharing the same status is preserved
+pub fn sort_by_status(deployments: &mut [Deployment]) {
+    deployments.sort_by_key(|deployment| match deployment.status {
+        Status::Ready => 0,
+        Status::Deploying => 1,
+        Status::Packaging => 2,
+        Status::WaitingForUpload => 3,
+        Status::Error => 4,
+    });
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the most recently created deployment, if any
+pub fn latest(deployments: &[Deployment]) -> Option<&Deployment> {
+    deployments
+        .iter()
+        .max_by_key(|deployment| deployment.created_at)
+}
+
 // ----------------------------------------------------------------------------
 // Helpers
 
@@ -236,6 +538,41 @@ where
         .map_err(|err| Error::List(function_id.to_string(), organisation_id.to_string(), err))
 }
 
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of deployments for a function built for the given platform
+pub async fn list_by_platform<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    function_id: &str,
+    platform: &Platform,
+) -> Result<Vec<Deployment>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    Ok(list(client, organisation_id, function_id)
+        .await?
+        .into_iter()
+        .filter(|deployment| platform == &deployment.platform)
+        .collect())
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of deployments for a function which are ready
+pub async fn list_ready<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    function_id: &str,
+) -> Result<Vec<Deployment>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    Ok(list(client, organisation_id, function_id)
+        .await?
+        .into_iter()
+        .filter(|deployment| Status::Ready == deployment.status)
+        .collect())
+}
+
 #[cfg_attr(feature = "trace", tracing::instrument)]
 /// create a deployment on the given function
 pub async fn create<C>(
@@ -369,6 +706,29 @@ where
     Ok(())
 }
 
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// Read the WebAssembly file and upload it on the endpoint, the file is
+/// checked to be a WebAssembly module before being uploaded
+pub async fn upload_from_file<C>(
+    client: &Client<C>,
+    endpoint: &str,
+    path: &Path,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let buf = tokio::fs::read(path)
+        .await
+        .map_err(|err| Error::ReadFile(path.display().to_string(), err))?;
+
+    if !is_wasm(&buf) {
+        return Err(Error::InvalidWasmFile(path.display().to_string()));
+    }
+
+    upload(client, endpoint, buf).await
+}
+
 #[cfg_attr(feature = "trace", tracing::instrument)]
 /// delete the deployment from the function
 pub async fn delete<C>(
@@ -401,3 +761,316 @@ where
         )
     })
 }
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// poll the deployment every `poll_interval` until it is ready and returns it,
+/// a deployment in error is reported as failed and polling gives up after
+/// `timeout`
+pub async fn wait_until_ready<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    function_id: &str,
+    deployment_id: &str,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<Deployment, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let start = Instant::now();
+    loop {
+        let deployment = get(client, organisation_id, function_id, deployment_id).await?;
+        match deployment.status {
+            Status::Ready => return Ok(deployment),
+            Status::Error => {
+                return Err(Error::Failed(
+                    deployment_id.to_string(),
+                    deployment.reason.unwrap_or_default(),
+                ));
+            }
+            _ => {}
+        }
+
+        if start.elapsed() + poll_interval > timeout {
+            return Err(Error::Timeout(
+                deployment_id.to_string(),
+                deployment.status,
+                timeout.as_secs(),
+            ));
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "tokio")]
+    use crate::mock::{Response, Server};
+
+    /// returns a deployment of the function in the given status
+    fn deployment(status: &str) -> String {
+        format!(
+            r#"{{
+                "id": "deployment_1",
+                "functionId": "function_1",
+                "name": null,
+                "description": null,
+                "tag": null,
+                "platform": "RUST",
+                "status": "{status}",
+                "errorReason": null,
+                "url": null,
+                "createdAt": "2024-03-01T10:00:00.123Z",
+                "updatedAt": "2024-03-02T11:30:00Z"
+            }}"#
+        )
+    }
+
+    /// returns the name prefixed by its length
+    fn name(name: &str) -> Vec<u8> {
+        let mut buf = vec![name.len() as u8];
+        buf.extend(name.as_bytes());
+        buf
+    }
+
+    /// returns a module made of the given sections
+    fn module(sections: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut buf = WASM_HEADER.to_vec();
+        for (id, section) in sections {
+            buf.push(*id);
+            buf.push(section.len() as u8);
+            buf.extend(section);
+        }
+        buf
+    }
+
+    /// returns an export section exporting functions with the given names
+    fn exports(names: &[&str]) -> Vec<u8> {
+        let mut buf = vec![names.len() as u8];
+        for (idx, export) in names.iter().enumerate() {
+            buf.extend(name(export));
+            buf.extend([0x00, idx as u8]);
+        }
+        buf
+    }
+
+    #[test]
+    fn detect_platform_from_exports_and_custom_sections() {
+        for (sections, platform) in [
+            (
+                vec![(
+                    WASM_SECTION_EXPORT,
+                    exports(&["memory", "__wbindgen_malloc"]),
+                )],
+                Some(Platform::Rust),
+            ),
+            (
+                vec![(WASM_SECTION_CUSTOM, name("__wasm_bindgen_unstable"))],
+                Some(Platform::Rust),
+            ),
+            (
+                vec![
+                    (1, vec![0x00]),
+                    (WASM_SECTION_EXPORT, exports(&["_start", "__js_startup"])),
+                ],
+                Some(Platform::JavaScript),
+            ),
+            (
+                vec![(WASM_SECTION_EXPORT, exports(&["_start", "memory"]))],
+                None,
+            ),
+            (vec![], None),
+        ] {
+            let module = module(&sections);
+
+            assert!(is_wasm(&module));
+            assert_eq!(detect_platform(&module), platform);
+        }
+    }
+
+    #[test]
+    fn detect_platform_rejects_malformed_modules() {
+        let truncated = module(&[(WASM_SECTION_EXPORT, exports(&["__wbindgen_malloc"]))]);
+        let truncated = &truncated[..truncated.len() - 3];
+
+        let mut oversized = module(&[(WASM_SECTION_EXPORT, exports(&["__js_startup"]))]);
+        oversized[WASM_HEADER.len() + 1] = 0x7f;
+
+        let mut overlong = WASM_HEADER.to_vec();
+        overlong.push(WASM_SECTION_CUSTOM);
+        overlong.extend([0x80; 11]);
+
+        let mut truncated_name = module(&[(WASM_SECTION_CUSTOM, name("__wbindgen"))]);
+        truncated_name[WASM_HEADER.len() + 2] = 0x20;
+
+        assert!(!is_wasm(b"#!/bin/sh"));
+
+        for buf in [
+            truncated,
+            &oversized,
+            &overlong,
+            &truncated_name,
+            b"",
+            b"\0asm",
+            b"\0asm\x02\0\0\0",
+            b"#!/bin/sh\necho __js_startup",
+        ] {
+            assert_eq!(detect_platform(buf), None);
+        }
+    }
+
+    #[test]
+    fn read_leb128_decodes_multi_byte_integers() {
+        assert_eq!(read_leb128(&[0x02, 0xff]), Some((2, &[0xff][..])));
+        assert_eq!(read_leb128(&[0xe5, 0x8e, 0x26]), Some((624_485, &[][..])));
+        assert_eq!(read_leb128(&[0x80, 0x80]), None);
+        assert_eq!(read_leb128(&[0xff; 10]), None);
+    }
+
+    #[test]
+    fn deserialize_deployment_with_rfc3339_dates() {
+        let deployment: Deployment =
+            serde_json::from_str(&deployment("READY")).expect("deployment to deserialize");
+
+        assert_eq!(deployment.status, Status::Ready);
+        assert_eq!(deployment.created_at.timestamp_millis(), 1_709_287_200_123);
+        assert_eq!(deployment.updated_at.timestamp_millis(), 1_709_379_000_000);
+    }
+
+    #[cfg(not(feature = "serde_with"))]
+    #[test]
+    fn deployment_dates_round_trip_as_rfc3339() {
+        let deployment: Deployment =
+            serde_json::from_str(&deployment("READY")).expect("deployment to deserialize");
+        let value = serde_json::to_value(&deployment).expect("deployment to serialize");
+
+        assert!(value["createdAt"].is_string());
+        assert_eq!(
+            serde_json::from_value::<Deployment>(value).expect("deployment to deserialize"),
+            deployment
+        );
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[test]
+    fn deployment_dates_round_trip_as_milliseconds() {
+        let deployment: Deployment =
+            serde_json::from_str(&deployment("READY")).expect("deployment to deserialize");
+        let value = serde_json::to_value(&deployment).expect("deployment to serialize");
+
+        assert_eq!(value["createdAt"], 1_709_287_200_123_i64);
+        assert_eq!(
+            serde_json::from_value::<Deployment>(value).expect("deployment to deserialize"),
+            deployment
+        );
+
+        let creation: DeploymentCreation = serde_json::from_str(
+            r#"{
+                "id": "deployment_1",
+                "functionId": "function_1",
+                "name": null,
+                "description": null,
+                "tag": null,
+                "platform": "RUST",
+                "status": "WAITING_FOR_UPLOAD",
+                "errorReason": null,
+                "uploadUrl": "https://upload.example.com/deployment_1",
+                "createdAt": "2024-03-01T10:00:00.123Z",
+                "updatedAt": "2024-03-02T11:30:00Z"
+            }"#,
+        )
+        .expect("creation to deserialize");
+        let value = serde_json::to_value(&creation).expect("creation to serialize");
+
+        assert_eq!(value["updatedAt"], 1_709_379_000_000_i64);
+        assert_eq!(
+            serde_json::from_value::<DeploymentCreation>(value).expect("creation to deserialize"),
+            creation
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_until_ready_polls_until_the_deployment_is_ready() {
+        let server = Server::start(vec![
+            Response::json(200, deployment("PACKAGING")),
+            Response::json(200, deployment("DEPLOYING")),
+            Response::json(200, deployment("READY")),
+        ])
+        .await;
+        let client = server.client();
+
+        let deployment = wait_until_ready(
+            &client,
+            "orga_1",
+            "function_1",
+            "deployment_1",
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("deployment");
+
+        assert_eq!(deployment.status, Status::Ready);
+        assert_eq!(server.uris().len(), 3);
+        assert!(server.uris().iter().all(|uri| uri
+            == "/v4/functions/organisations/orga_1/functions/function_1/deployments/deployment_1"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_until_ready_stops_on_a_deployment_in_error() {
+        let server = Server::start(vec![
+            Response::json(200, deployment("DEPLOYING")),
+            Response::json(
+                200,
+                deployment("ERROR").replace(
+                    r#""errorReason": null"#,
+                    r#""errorReason": "module has no entrypoint""#,
+                ),
+            ),
+            Response::json(200, deployment("READY")),
+        ])
+        .await;
+        let client = server.client();
+
+        assert!(matches!(
+            wait_until_ready(
+                &client,
+                "orga_1",
+                "function_1",
+                "deployment_1",
+                Duration::from_millis(1),
+                Duration::from_secs(5),
+            )
+            .await,
+            Err(Error::Failed(id, reason))
+                if id == "deployment_1" && reason == "module has no entrypoint"
+        ));
+        assert_eq!(server.uris().len(), 2);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn wait_until_ready_gives_up_after_the_timeout() {
+        let server = Server::start(vec![Response::json(200, deployment("PACKAGING"))]).await;
+        let client = server.client();
+
+        assert!(matches!(
+            wait_until_ready(
+                &client,
+                "orga_1",
+                "function_1",
+                "deployment_1",
+                Duration::from_secs(1),
+                Duration::from_millis(10),
+            )
+            .await,
+            Err(Error::Timeout(id, Status::Packaging, _)) if id == "deployment_1"
+        ));
+        assert_eq!(server.uris().len(), 1);
+    }
+}
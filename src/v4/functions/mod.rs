@@ -3,18 +3,39 @@
 //! This module provides all structures and helpers to interact with functions
 //! product at Clever Cloud.
 
-use std::{collections::BTreeMap, fmt::Debug};
+#[cfg(feature = "tokio")]
+use std::time::Duration;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
 
 use chrono::{DateTime, Utc};
-use hyper::{body::Buf, Body, Method, Request};
+#[cfg(feature = "tokio")]
+use futures::{stream, Stream};
+use hyper::{
+    body::Buf,
+    header::{HeaderName, HeaderValue, CONTENT_TYPE},
+    Body, Method, Request,
+};
 use log::{debug, log_enabled, Level};
 use oauth10a::client::{connector::Connect, ClientError, RestClient};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::Client;
+use crate::{
+    v4::addon_provider::config_provider::addon::environment::{parse_dotenv, DotenvError},
+    Client,
+};
 
 pub mod deployments;
 
+// -----------------------------------------------------------------------------
+// Constants
+
+/// Maximum size in bytes of the environment of a function once serialized as
+/// json, the api rejects larger environments
+pub const MAX_ENVIRONMENT_BYTES: usize = 65536;
+
 // -----------------------------------------------------------------------------
 // Error
 
@@ -38,10 +59,30 @@ pub enum Error {
     Request(hyper::http::Error),
     #[error("failed to execute request, {0}")]
     Execute(hyper::Error),
+    #[error("failed to set header '{0}' on request, invalid name or value")]
+    InvalidHeader(String),
     #[error("failed to execute request, got status code {0}")]
     StatusCode(u16),
+    #[error("failed to serialize request payload, {0}")]
+    SerializePayload(serde_json::Error),
+    #[error("failed to deserialize response payload, {0}")]
+    DeserializeResponse(serde_json::Error),
+    #[error("failed to validate options, {0}")]
+    Validation(ValidationError),
 }
 
+#[derive(thiserror::Error, PartialEq, Eq, Clone, Debug)]
+pub enum ValidationError {
+    #[error("environment is {0} bytes large, at most {MAX_ENVIRONMENT_BYTES} bytes are allowed")]
+    EnvironmentTooLarge(usize),
+}
+
+/// MissingEnvError is returned when a required variable is not set in the
+/// environment of a function, it carries the name of the variable
+#[derive(thiserror::Error, PartialEq, Eq, Clone, Debug)]
+#[error("failed to find variable '{0}' in the environment of the function")]
+pub struct MissingEnvError(pub String);
+
 // -----------------------------------------------------------------------------
 // CreateOpts structure
 
@@ -95,12 +136,261 @@ pub struct Function {
     pub max_memory: u64,
     #[serde(rename = "maxInstances")]
     pub max_instances: u64,
+    #[cfg_attr(
+        feature = "serde_with",
+        serde(
+            serialize_with = "serialize_timestamp",
+            deserialize_with = "deserialize_timestamp"
+        )
+    )]
     #[serde(rename = "createdAt")]
     pub created_at: DateTime<Utc>,
+    #[cfg_attr(
+        feature = "serde_with",
+        serde(
+            serialize_with = "serialize_timestamp",
+            deserialize_with = "deserialize_timestamp"
+        )
+    )]
     #[serde(rename = "updatedAt")]
     pub updated_at: DateTime<Utc>,
 }
 
+impl Opts {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the tags as a map, a tag without value is mapped to `None`
+    pub fn tags(&self) -> BTreeMap<String, Option<String>> {
+        self.tag.as_deref().map(parse_tags).unwrap_or_default()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// set the tags from the given map, replacing the previous ones
+    pub fn set_tags(&mut self, tags: &BTreeMap<String, Option<String>>) -> &mut Self {
+        self.tag = format_tags(tags);
+        self
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument(skip(dotenv_content)))]
+    /// set the variables of the given dotenv content in the environment of the
+    /// function, replacing the previous values of the same variables
+    pub fn set_environment_from_dotenv(
+        &mut self,
+        dotenv_content: &str,
+    ) -> Result<&mut Self, DotenvError> {
+        let variables = parse_dotenv(dotenv_content)?;
+        Ok(self.merge_environment(&variables))
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// set the given variables in the environment of the function, replacing
+    /// the previous values of the same variables
+    pub fn merge_environment(&mut self, other: &BTreeMap<String, String>) -> &mut Self {
+        self.environment.extend(
+            other
+                .iter()
+                .map(|(name, value)| (name.to_owned(), value.to_owned())),
+        );
+        self
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the size in bytes of the environment once serialized as json
+    pub fn environment_byte_size(&self) -> usize {
+        environment_byte_size(&self.environment)
+    }
+}
+
+impl Function {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the size in bytes of the environment once serialized as json
+    pub fn environment_byte_size(&self) -> usize {
+        environment_byte_size(&self.environment)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the tags of the function as a map, a tag without value is
+    /// mapped to `None`
+    pub fn tags(&self) -> BTreeMap<String, Option<String>> {
+        self.tag.as_deref().map(parse_tags).unwrap_or_default()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns true, if the function has the given tag with the given value,
+    /// use `None` to look for a tag without value
+    pub fn has_tag(&self, key: &str, value: Option<&str>) -> bool {
+        self.tags().get(key).map(Option::as_deref) == Some(value)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns which fields of the function would change if updated with the
+    /// given options
+    pub fn diff(&self, new_opts: &Opts) -> FunctionDiff {
+        FunctionDiff {
+            name_changed: self.name != new_opts.name,
+            description_changed: self.description != new_opts.description,
+            tag_changed: self.tags() != new_opts.tags(),
+            environment_changed: self.environment != new_opts.environment,
+            max_memory_changed: self.max_memory != new_opts.max_memory,
+            max_instances_changed: self.max_instances != new_opts.max_instances,
+        }
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the value of the given variable of the environment, if set
+    pub fn env(&self, key: &str) -> Option<&str> {
+        self.environment.get(key).map(String::as_str)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the value of the given variable of the environment or an error
+    /// naming the missing variable
+    pub fn required_env(&self, key: &str) -> Result<&str, MissingEnvError> {
+        self.env(key).ok_or_else(|| MissingEnvError(key.to_owned()))
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument(skip_all))]
+    /// returns the function with the given variable set in its environment,
+    /// replacing the previous value if any
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.environment.insert(key.into(), value.into());
+        self
+    }
+}
+
+// -----------------------------------------------------------------------------
+// FunctionDiff structure
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+pub struct FunctionDiff {
+    #[serde(rename = "nameChanged")]
+    pub name_changed: bool,
+    #[serde(rename = "descriptionChanged")]
+    pub description_changed: bool,
+    #[serde(rename = "tagChanged")]
+    pub tag_changed: bool,
+    #[serde(rename = "environmentChanged")]
+    pub environment_changed: bool,
+    #[serde(rename = "maxMemoryChanged")]
+    pub max_memory_changed: bool,
+    #[serde(rename = "maxInstancesChanged")]
+    pub max_instances_changed: bool,
+}
+
+impl FunctionDiff {
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns true, if no field would change
+    pub fn is_empty(&self) -> bool {
+        !(self.name_changed
+            || self.description_changed
+            || self.tag_changed
+            || self.environment_changed
+            || self.max_memory_changed
+            || self.max_instances_changed)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// update the function with the given options, if there is nothing to
+    /// change, the request is skipped and `None` is returned
+    pub async fn apply<C>(
+        &self,
+        client: &Client<C>,
+        organisation_id: &str,
+        function_id: &str,
+        opts: &Opts,
+    ) -> Result<Option<Function>, Error>
+    where
+        C: Connect + Clone + Debug + Send + Sync + 'static,
+    {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        update(client, organisation_id, function_id, opts)
+            .await
+            .map(Some)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Validation helpers
+
+/// returns the size in bytes of the environment once serialized as json
+fn environment_byte_size(environment: &BTreeMap<String, String>) -> usize {
+    serde_json::to_string(environment)
+        .map(|s| s.len())
+        .unwrap_or(0)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// check that the options would be accepted by the api, so that invalid ones
+/// are rejected without a round trip
+pub fn validate_opts(opts: &Opts) -> Result<(), ValidationError> {
+    let size = opts.environment_byte_size();
+    if size > MAX_ENVIRONMENT_BYTES {
+        return Err(ValidationError::EnvironmentTooLarge(size));
+    }
+
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Tags helpers
+
+/// returns the map of tags from their space-separated 'k:v' representation, a
+/// tag without ':' is kept without value
+pub fn parse_tags(tag: &str) -> BTreeMap<String, Option<String>> {
+    tag.split_whitespace()
+        .map(|pair| match pair.split_once(':') {
+            Some((k, v)) => (k.to_string(), Some(v.to_string())),
+            None => (pair.to_string(), None),
+        })
+        .collect()
+}
+
+/// returns the space-separated 'k:v' representation of tags or `None` if there
+/// is no tag, a tag without value is written as its key alone
+pub fn format_tags(tags: &BTreeMap<String, Option<String>>) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+
+    Some(
+        tags.iter()
+            .map(|(k, v)| match v {
+                Some(v) => format!("{k}:{v}"),
+                None => k.to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Dates helpers
+
+/// serialize the date as an unix timestamp in milliseconds
+#[cfg(feature = "serde_with")]
+pub(crate) fn serialize_timestamp<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde_with::{SerializeAs, TimestampMilliSeconds};
+
+    TimestampMilliSeconds::<i64>::serialize_as(date, serializer)
+}
+
+/// deserialize the date from an unix timestamp in milliseconds, as written by
+/// [`serialize_timestamp`], or from a rfc 3339 date, as sent by the api
+#[cfg(feature = "serde_with")]
+pub(crate) fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde_with::{DeserializeAs, PickFirst, Same, TimestampMilliSeconds};
+
+    PickFirst::<(TimestampMilliSeconds<i64>, Same)>::deserialize_as(deserializer)
+}
+
 // -----------------------------------------------------------------------------
 // ExecuteResult structure
 
@@ -158,6 +448,30 @@ impl ExecutionResult {
     pub fn is_err(&self) -> bool {
         !self.is_ok()
     }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    pub fn stdout_lines(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::Ok { stdout, .. } => Some(stdout.lines().collect()),
+            Self::Err { .. } => None,
+        }
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    pub fn stderr_lines(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::Ok { stderr, .. } => Some(stderr.lines().collect()),
+            Self::Err { .. } => None,
+        }
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    pub fn dmesg_lines(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::Ok { dmesg, .. } => Some(dmesg.lines().collect()),
+            Self::Err { .. } => None,
+        }
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -185,6 +499,26 @@ where
         .map_err(|err| Error::List(organisation_id.to_string(), err))
 }
 
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// list functions of the given organisations concurrently and returns the
+/// outcome of each listing along with its organisation
+pub async fn list_all_organisations<C>(
+    client: &Client<C>,
+    organisation_ids: &[&str],
+) -> Vec<(String, Result<Vec<Function>, Error>)>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    futures::future::join_all(organisation_ids.iter().map(|organisation_id| async move {
+        (
+            organisation_id.to_string(),
+            list(client, organisation_id).await,
+        )
+    }))
+    .await
+}
+
 #[cfg_attr(feature = "trace", tracing::instrument)]
 /// create a function on the given organisation
 pub async fn create<C>(
@@ -207,6 +541,8 @@ where
         );
     }
 
+    validate_opts(opts).map_err(Error::Validation)?;
+
     client
         .post(&path, opts)
         .await
@@ -241,6 +577,40 @@ where
         .map_err(|err| Error::Get(function_id.to_string(), organisation_id.to_string(), err))
 }
 
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns a stream of the function information retrieved every interval, the
+/// first one right away, the stream goes on until it is dropped and a failed
+/// retrieval is yielded as an error without ending it
+pub fn watch<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    function_id: &str,
+    interval: Duration,
+) -> impl Stream<Item = Result<Function, Error>>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let state = (
+        client.to_owned(),
+        organisation_id.to_owned(),
+        function_id.to_owned(),
+        false,
+    );
+
+    stream::unfold(
+        state,
+        move |(client, organisation_id, function_id, started)| async move {
+            if started {
+                tokio::time::sleep(interval).await;
+            }
+
+            let result = get(&client, &organisation_id, &function_id).await;
+            Some((result, (client, organisation_id, function_id, true)))
+        },
+    )
+}
+
 #[cfg_attr(feature = "trace", tracing::instrument)]
 /// Update the function information of the organisation
 pub async fn update<C>(
@@ -264,6 +634,8 @@ where
         );
     }
 
+    validate_opts(opts).map_err(Error::Validation)?;
+
     client
         .put(&path, opts)
         .await
@@ -304,16 +676,226 @@ pub async fn execute<C>(client: &Client<C>, endpoint: &str) -> Result<ExecutionR
 where
     C: Connect + Clone + Debug + Send + Sync + 'static,
 {
+    execute_with_headers(client, endpoint, HashMap::new()).await
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// Execute a GET HTTP request on the given endpoint with the given headers
+pub async fn execute_with_headers<C>(
+    client: &Client<C>,
+    endpoint: &str,
+    headers: HashMap<String, String>,
+) -> Result<ExecutionResult, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let mut builder = Request::builder().method(Method::GET).uri(endpoint);
+    for (name, value) in &headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| Error::InvalidHeader(name.to_owned()))?;
+        let value =
+            HeaderValue::from_str(value).map_err(|_| Error::InvalidHeader(name.to_string()))?;
+
+        builder = builder.header(name, value);
+    }
+
+    let req = builder.body(Body::empty()).map_err(Error::Request)?;
+    let res = client.inner().request(req).await.map_err(Error::Execute)?;
+    let buf = hyper::body::aggregate(res.into_body())
+        .await
+        .map_err(Error::BodyAggregation)?;
+
+    serde_json::from_reader(buf.reader()).map_err(Error::Deserialize)
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// Execute a POST HTTP request on the given endpoint with the payload as json
+/// body and returns the deserialized json response
+pub async fn execute_json<C, T, U>(
+    client: &Client<C>,
+    endpoint: &str,
+    payload: &T,
+) -> Result<U, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+    T: Serialize + Debug,
+    U: DeserializeOwned,
+{
+    let body = serde_json::to_vec(payload).map_err(Error::SerializePayload)?;
     let req = Request::builder()
-        .method(Method::GET)
+        .method(Method::POST)
         .uri(endpoint)
-        .body(Body::empty())
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
         .map_err(Error::Request)?;
 
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to post json on function, endpoint: '{endpoint}'");
+    }
+
     let res = client.inner().request(req).await.map_err(Error::Execute)?;
+    let status = res.status();
+    if !status.is_success() {
+        return Err(Error::StatusCode(status.as_u16()));
+    }
+
     let buf = hyper::body::aggregate(res.into_body())
         .await
         .map_err(Error::BodyAggregation)?;
 
-    serde_json::from_reader(buf.reader()).map_err(Error::Deserialize)
+    serde_json::from_reader(buf.reader()).map_err(Error::DeserializeResponse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FUNCTION: &str = r#"{
+        "id": "function_5b4ecb9b-0e3a-4c2e-8d6e-2f0f6a7e1c3d",
+        "ownerId": "orga_2a6c1b4e-1b2c-4d3e-9f8a-7b6c5d4e3f2a",
+        "name": "hello",
+        "tag": "env:prod team:core",
+        "environment": { "FOO": "bar" },
+        "maxMemory": 536870912,
+        "maxInstances": 1,
+        "createdAt": "2024-03-01T10:00:00.123Z",
+        "updatedAt": "2024-03-02T11:30:00Z"
+    }"#;
+
+    #[test]
+    fn deserialize_function_with_rfc3339_dates() {
+        let function: Function = serde_json::from_str(FUNCTION).expect("function to deserialize");
+
+        assert_eq!(function.created_at.timestamp_millis(), 1_709_287_200_123);
+        assert_eq!(function.updated_at.timestamp_millis(), 1_709_379_000_000);
+    }
+
+    #[cfg(not(feature = "serde_with"))]
+    #[test]
+    fn function_dates_round_trip_as_rfc3339() {
+        let function: Function = serde_json::from_str(FUNCTION).expect("function to deserialize");
+        let value = serde_json::to_value(&function).expect("function to serialize");
+
+        assert!(value["createdAt"].is_string());
+        assert_eq!(
+            serde_json::from_value::<Function>(value).expect("function to deserialize"),
+            function
+        );
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[test]
+    fn function_dates_round_trip_as_milliseconds() {
+        let function: Function = serde_json::from_str(FUNCTION).expect("function to deserialize");
+        let value = serde_json::to_value(&function).expect("function to serialize");
+
+        assert_eq!(value["createdAt"], 1_709_287_200_123_i64);
+        assert_eq!(value["updatedAt"], 1_709_379_000_000_i64);
+        assert_eq!(
+            serde_json::from_value::<Function>(value).expect("function to deserialize"),
+            function
+        );
+    }
+
+    #[test]
+    fn tags_round_trip_with_valueless_tags() {
+        let tag = "env:prod public team:";
+        let tags = parse_tags(tag);
+
+        assert_eq!(tags.get("env"), Some(&Some("prod".to_string())));
+        assert_eq!(tags.get("public"), Some(&None));
+        assert_eq!(tags.get("team"), Some(&Some(String::new())));
+        assert_eq!(format_tags(&tags).as_deref(), Some(tag));
+        assert_eq!(format_tags(&BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn function_has_tag() {
+        let mut function: Function =
+            serde_json::from_str(FUNCTION).expect("function to deserialize");
+        function.tag = Some("env:prod public".to_string());
+
+        assert!(function.has_tag("env", Some("prod")));
+        assert!(function.has_tag("public", None));
+        assert!(!function.has_tag("public", Some("")));
+        assert!(!function.has_tag("team", None));
+    }
+
+    #[test]
+    fn opts_set_tags_keeps_tag_string() {
+        let mut opts = Opts {
+            tag: Some("public env:prod".to_string()),
+            ..Default::default()
+        };
+
+        let tags = opts.tags();
+        opts.set_tags(&tags);
+
+        assert_eq!(opts.tag.as_deref(), Some("env:prod public"));
+        assert_eq!(opts.tags(), tags);
+
+        opts.set_tags(&BTreeMap::new());
+        assert_eq!(opts.tag, None);
+    }
+
+    #[test]
+    fn merge_environment_overrides_existing_variables() {
+        let mut opts = Opts {
+            environment: BTreeMap::from([
+                ("HOST".to_string(), "localhost".to_string()),
+                ("PORT".to_string(), "5432".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        opts.merge_environment(&BTreeMap::from([
+            ("HOST".to_string(), "db".to_string()),
+            ("USER".to_string(), "admin".to_string()),
+        ]));
+
+        assert_eq!(
+            opts.environment,
+            BTreeMap::from([
+                ("HOST".to_string(), "db".to_string()),
+                ("PORT".to_string(), "5432".to_string()),
+                ("USER".to_string(), "admin".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn set_environment_from_dotenv_merges_the_parsed_variables() {
+        let mut opts = Opts {
+            environment: BTreeMap::from([("HOST".to_string(), "localhost".to_string())]),
+            ..Default::default()
+        };
+
+        opts.set_environment_from_dotenv("export HOST=db\nGREETING=\"hello\\nworld\"\n")
+            .expect("dotenv to parse");
+
+        assert_eq!(opts.environment.get("HOST").map(String::as_str), Some("db"));
+        assert_eq!(
+            opts.environment.get("GREETING").map(String::as_str),
+            Some("hello\nworld")
+        );
+        assert_eq!(
+            opts.set_environment_from_dotenv("PORT=1\nUSER").err(),
+            Some(DotenvError::MissingSeparator(2))
+        );
+        assert_eq!(opts.environment.len(), 2);
+    }
+
+    #[test]
+    fn diff_ignores_tags_order() {
+        let function: Function = serde_json::from_str(FUNCTION).expect("function to deserialize");
+        let opts = Opts {
+            name: function.name.to_owned(),
+            tag: Some("team:core env:prod".to_string()),
+            environment: function.environment.to_owned(),
+            ..Default::default()
+        };
+
+        assert!(function.diff(&opts).is_empty());
+    }
 }
@@ -4,4 +4,5 @@
 
 pub mod addon_provider;
 pub mod functions;
+pub mod network_group;
 pub mod products;
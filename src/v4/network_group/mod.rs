@@ -0,0 +1,6 @@
+//! # Network group module
+//!
+//! This module provides structures and helpers to interact with network groups
+//! product at Clever Cloud.
+
+pub mod peer;
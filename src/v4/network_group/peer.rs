@@ -0,0 +1,152 @@
+//! # Peer module
+//!
+//! This module provides structures and helpers to interact with the wireguard
+//! peers of a network group.
+
+use std::fmt::Debug;
+
+use hyper::client::connect::Connect;
+#[cfg(feature = "logging")]
+use log::{debug, log_enabled, Level};
+use oauth10a::client::{ClientError, RestClient};
+#[cfg(feature = "jsonschemas")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Client;
+
+// -----------------------------------------------------------------------------
+// Error enumeration
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to list peers of network group '{0}' for organisation '{1}', {2}")]
+    List(String, String, ClientError),
+    #[error("failed to add peer to network group '{0}' for organisation '{1}', {2}")]
+    Add(String, String, ClientError),
+    #[error("failed to remove peer '{0}' of network group '{1}' for organisation '{2}', {3}")]
+    Remove(String, String, String, ClientError),
+}
+
+// -----------------------------------------------------------------------------
+// Peer structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Peer {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    #[serde(rename = "endpoint")]
+    pub endpoint: String,
+    #[serde(rename = "allowedIps")]
+    pub allowed_ips: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// CreateOpts structure
+
+#[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct CreateOpts {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    #[serde(rename = "endpoint")]
+    pub endpoint: String,
+    #[serde(rename = "allowedIps")]
+    pub allowed_ips: Vec<String>,
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// returns the list of peers of the network group
+pub async fn list<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    network_group_id: &str,
+) -> Result<Vec<Peer>, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/networkgroups/organisations/{organisation_id}/networkgroups/{network_group_id}/peers",
+        client.endpoint
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to list peers of network group, path: '{path}', organisation: '{organisation_id}', network group: '{network_group_id}'");
+    }
+
+    client.get(&path).await.map_err(|err| {
+        Error::List(
+            network_group_id.to_string(),
+            organisation_id.to_string(),
+            err,
+        )
+    })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// add a peer to the network group and returns it
+pub async fn add<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    network_group_id: &str,
+    opts: &CreateOpts,
+) -> Result<Peer, Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/networkgroups/organisations/{organisation_id}/networkgroups/{network_group_id}/external-peers",
+        client.endpoint
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to add peer to network group, path: '{path}', organisation: '{organisation_id}', network group: '{network_group_id}'");
+    }
+
+    client.post(&path, opts).await.map_err(|err| {
+        Error::Add(
+            network_group_id.to_string(),
+            organisation_id.to_string(),
+            err,
+        )
+    })
+}
+
+#[cfg_attr(feature = "trace", tracing::instrument)]
+/// remove the peer from the network group
+pub async fn remove<C>(
+    client: &Client<C>,
+    organisation_id: &str,
+    network_group_id: &str,
+    peer_id: &str,
+) -> Result<(), Error>
+where
+    C: Connect + Clone + Debug + Send + Sync + 'static,
+{
+    let path = format!(
+        "{}/v4/networkgroups/organisations/{organisation_id}/networkgroups/{network_group_id}/external-peers/{peer_id}",
+        client.endpoint
+    );
+
+    #[cfg(feature = "logging")]
+    if log_enabled!(Level::Debug) {
+        debug!("execute a request to remove peer of network group, path: '{path}', organisation: '{organisation_id}', network group: '{network_group_id}', peer: '{peer_id}'");
+    }
+
+    client.delete(&path).await.map_err(|err| {
+        Error::Remove(
+            peer_id.to_string(),
+            network_group_id.to_string(),
+            organisation_id.to_string(),
+            err,
+        )
+    })
+}
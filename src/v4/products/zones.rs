@@ -2,7 +2,12 @@
 //!
 //! This module provide helpers and structures to interact with zones of products
 
-use std::fmt::Debug;
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
 
 use hyper::client::connect::Connect;
 #[cfg(feature = "logging")]
@@ -18,14 +23,36 @@ use crate::Client;
 // -----------------------------------------------------------------------------
 // Constants
 
+/// Zone could host applications and addons
 pub const TAG_APPLICATION: &str = "for:applications";
+/// Zone could host workers of applications
+pub const TAG_FOR_WORKERS: &str = "for:workers";
+/// Zone could host pulsar addons
+pub const TAG_FOR_PULSAR: &str = "for:pulsar";
+/// Zone is operated on the infrastructure of Clever Cloud
+pub const TAG_INFRA_CLEVER_CLOUD: &str = "infra:clever-cloud";
+/// Zone is operated on the infrastructure of OVHcloud
+pub const TAG_INFRA_OVH: &str = "infra:ovh";
+/// Zone is located in Europe
+pub const TAG_REGION_EU: &str = "region:eu";
+/// Zone is located in North America
+pub const TAG_REGION_NA: &str = "region:na";
+/// Zone is located in Asia-Pacific
+pub const TAG_REGION_APAC: &str = "region:apac";
+/// Zone is certified for hosting health data (HDS)
 pub const TAG_HDS: &str = "certification:hds";
+/// Zone is certified ISO 27001
+pub const TAG_CERTIFICATION_ISO27001: &str = "certification:iso27001";
 
 // -----------------------------------------------------------------------------
 // Zone structure
 
+/// Zone structure describes a zone, zones are identified by their identifier
+/// which is the only field compared or hashed, so that they could be used as
+/// keys of maps or in sets despite their coordinates
 #[cfg_attr(feature = "jsonschemas", derive(JsonSchema))]
-#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Clone, Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Zone {
     #[serde(rename = "id")]
     pub id: Uuid,
@@ -36,7 +63,7 @@ pub struct Zone {
     #[serde(rename = "name")]
     pub name: String,
     #[serde(rename = "countryCode")]
-    pub coutry_code: String,
+    pub country_code: String,
     #[serde(rename = "lat")]
     pub latitude: f64,
     #[serde(rename = "lon")]
@@ -45,6 +72,85 @@ pub struct Zone {
     pub tags: Vec<String>,
 }
 
+/// Set of zones, unique by identifier
+pub type ZoneSet = HashSet<Zone>;
+
+impl PartialEq for Zone {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Zone {}
+
+impl PartialOrd for Zone {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.id.cmp(&other.id))
+    }
+}
+
+impl Hash for Zone {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Zone {
+    #[deprecated(note = "use the `country_code` field instead")]
+    /// returns the country code of the zone
+    pub fn coutry_code(&self) -> &str {
+        &self.country_code
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns if the zone has the given tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the tags of the zone as a set
+    pub fn tags_as_set(&self) -> HashSet<&str> {
+        self.tags.iter().map(String::as_str).collect()
+    }
+
+    #[cfg_attr(feature = "trace", tracing::instrument)]
+    /// returns the zone with the given identifier, if any
+    pub fn by_id(zones: &[Zone], id: Uuid) -> Option<&Zone> {
+        zones.iter().find(|zone| id == zone.id)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Fuzzing
+
+#[cfg(feature = "fuzzing")]
+/// fuzz target which builds a zone from the given bytes, round-trips it through
+/// its json representation and checks that every field is preserved
+pub fn fuzz_round_trip(data: &[u8]) {
+    let mut unstructured = arbitrary::Unstructured::new(data);
+    let Ok(zone) = <Zone as arbitrary::Arbitrary>::arbitrary(&mut unstructured) else {
+        return;
+    };
+
+    // json has no representation of nan and infinite numbers
+    if !zone.latitude.is_finite() || !zone.longitude.is_finite() {
+        return;
+    }
+
+    let json = serde_json::to_string(&zone).expect("zone to serialize");
+    let other: Zone = serde_json::from_str(&json).expect("zone to deserialize");
+
+    assert_eq!(zone.id, other.id);
+    assert_eq!(zone.city, other.city);
+    assert_eq!(zone.country, other.country);
+    assert_eq!(zone.name, other.name);
+    assert_eq!(zone.country_code, other.country_code);
+    assert_eq!(zone.latitude.to_bits(), other.latitude.to_bits());
+    assert_eq!(zone.longitude.to_bits(), other.longitude.to_bits());
+    assert_eq!(zone.tags, other.tags);
+}
+
 // -----------------------------------------------------------------------------
 // Error enumeration
 
@@ -82,7 +188,7 @@ where
     Ok(list(client)
         .await?
         .iter()
-        .filter(|zone| zone.tags.contains(&TAG_APPLICATION.to_string()))
+        .filter(|zone| zone.has_tag(TAG_APPLICATION))
         .map(ToOwned::to_owned)
         .collect())
 }
@@ -97,8 +203,198 @@ where
     Ok(list(client)
         .await?
         .iter()
-        .filter(|zone| zone.tags.contains(&TAG_APPLICATION.to_string()))
-        .filter(|zone| zone.tags.contains(&TAG_HDS.to_string()))
+        .filter(|zone| zone.has_tag(TAG_APPLICATION))
+        .filter(|zone| zone.has_tag(TAG_HDS))
         .map(ToOwned::to_owned)
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Response, Server};
+
+    const ZONES: &str = r#"[
+        {
+            "id": "bc5e1b1b-0c0e-4b9e-8d79-2c6a5e1a2b3c",
+            "city": "Paris",
+            "country": "France",
+            "name": "par",
+            "countryCode": "FR",
+            "lat": 48.8566,
+            "lon": 2.3522,
+            "tags": ["region:eu", "infra:clever-cloud", "for:applications", "certification:hds"]
+        },
+        {
+            "id": "8f3a2d4e-5b6c-4d7e-9f8a-1b2c3d4e5f6a",
+            "city": "Montreal",
+            "country": "Canada",
+            "name": "mtl",
+            "countryCode": "CA",
+            "lat": 45.5017,
+            "lon": -73.5673,
+            "tags": ["region:na", "infra:ovh", "for:applications"]
+        }
+    ]"#;
+
+    #[test]
+    fn deserialize_zones() {
+        let zones: Vec<Zone> = serde_json::from_str(ZONES).expect("zones to deserialize");
+
+        assert_eq!(zones.len(), 2);
+
+        let par = &zones[0];
+        assert_eq!(par.id.to_string(), "bc5e1b1b-0c0e-4b9e-8d79-2c6a5e1a2b3c");
+        assert_eq!(par.city, "Paris");
+        assert_eq!(par.country, "France");
+        assert_eq!(par.name, "par");
+        assert_eq!(par.country_code, "FR");
+        assert_eq!(par.latitude, 48.8566);
+        assert_eq!(par.longitude, 2.3522);
+        assert!(par.has_tag(TAG_HDS));
+
+        let mtl = &zones[1];
+        assert_eq!(mtl.name, "mtl");
+        assert_eq!(mtl.country_code, "CA");
+        assert_eq!(mtl.latitude, 45.5017);
+        assert_eq!(mtl.longitude, -73.5673);
+        assert!(mtl.has_tag(TAG_INFRA_OVH));
+        assert!(!mtl.has_tag(TAG_HDS));
+    }
+
+    #[test]
+    fn deserialize_zone_without_country_code_fails() {
+        let json = r#"{
+            "id": "bc5e1b1b-0c0e-4b9e-8d79-2c6a5e1a2b3c",
+            "city": "Paris",
+            "country": "France",
+            "name": "par",
+            "coutryCode": "FR",
+            "lat": 48.8566,
+            "lon": 2.3522,
+            "tags": []
+        }"#;
+
+        assert!(serde_json::from_str::<Zone>(json).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_coutry_code_returns_the_country_code() {
+        let zones: Vec<Zone> = serde_json::from_str(ZONES).expect("zones to deserialize");
+
+        assert_eq!(zones[0].coutry_code(), "FR");
+    }
+
+    #[cfg(feature = "fuzzing")]
+    #[test]
+    fn fuzz_round_trip_on_seeds() {
+        for seed in 0..=255u8 {
+            let data: Vec<u8> = (0..128u8)
+                .map(|i| i.wrapping_mul(seed).wrapping_add(seed))
+                .collect();
+            fuzz_round_trip(&data);
+        }
+    }
+
+    #[test]
+    fn tag_constants_match_the_api() {
+        for (constant, tag) in [
+            (TAG_APPLICATION, "for:applications"),
+            (TAG_FOR_WORKERS, "for:workers"),
+            (TAG_FOR_PULSAR, "for:pulsar"),
+            (TAG_INFRA_CLEVER_CLOUD, "infra:clever-cloud"),
+            (TAG_INFRA_OVH, "infra:ovh"),
+            (TAG_REGION_EU, "region:eu"),
+            (TAG_REGION_NA, "region:na"),
+            (TAG_REGION_APAC, "region:apac"),
+            (TAG_HDS, "certification:hds"),
+            (TAG_CERTIFICATION_ISO27001, "certification:iso27001"),
+        ] {
+            assert_eq!(constant, tag);
+        }
+    }
+
+    #[test]
+    fn tags_of_a_zone_are_looked_up() {
+        let zones: Vec<Zone> = serde_json::from_str(ZONES).expect("zones to deserialize");
+        let par = &zones[0];
+
+        for tag in [
+            TAG_REGION_EU,
+            TAG_INFRA_CLEVER_CLOUD,
+            TAG_APPLICATION,
+            TAG_HDS,
+        ] {
+            assert!(par.has_tag(tag), "tag '{tag}'");
+        }
+
+        for tag in [
+            TAG_FOR_WORKERS,
+            TAG_FOR_PULSAR,
+            TAG_INFRA_OVH,
+            TAG_REGION_NA,
+            TAG_REGION_APAC,
+            TAG_CERTIFICATION_ISO27001,
+        ] {
+            assert!(!par.has_tag(tag), "tag '{tag}'");
+        }
+
+        assert_eq!(
+            par.tags_as_set(),
+            HashSet::from([
+                TAG_REGION_EU,
+                TAG_INFRA_CLEVER_CLOUD,
+                TAG_APPLICATION,
+                TAG_HDS
+            ])
+        );
+    }
+
+    #[test]
+    fn zones_are_identified_by_their_id() {
+        let zones: Vec<Zone> = serde_json::from_str(ZONES).expect("zones to deserialize");
+        let mut moved = zones[0].clone();
+        moved.latitude = 43.2965;
+        moved.longitude = 5.3698;
+
+        let set: ZoneSet = [zones[0].clone(), moved, zones[1].clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&zones[0]));
+        assert!(set.contains(&zones[1]));
+    }
+
+    #[test]
+    fn by_id_looks_up_a_zone() {
+        let zones: Vec<Zone> = serde_json::from_str(ZONES).expect("zones to deserialize");
+        let id = "8f3a2d4e-5b6c-4d7e-9f8a-1b2c3d4e5f6a"
+            .parse()
+            .expect("id to parse");
+        let unknown = "00000000-0000-4000-8000-000000000000"
+            .parse()
+            .expect("id to parse");
+
+        assert_eq!(
+            Zone::by_id(&zones, id).map(|zone| zone.name.as_str()),
+            Some("mtl")
+        );
+        assert!(Zone::by_id(&zones, unknown).is_none());
+    }
+
+    #[tokio::test]
+    async fn helpers_filter_zones_by_tag() {
+        let server =
+            Server::start(vec![Response::json(200, ZONES), Response::json(200, ZONES)]).await;
+        let client = server.client();
+
+        let zones = applications(&client).await.expect("zones");
+        assert_eq!(zones.len(), 2);
+
+        let zones = hds(&client).await.expect("zones");
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].name, "par");
+        assert_eq!(server.uris(), vec!["/v4/products/zones"; 2]);
+    }
+}